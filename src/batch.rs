@@ -0,0 +1,328 @@
+//! Lookup of many words at once.
+//!
+//! Looking words up one at a time takes a query for each word's entries, each entry's
+//! details and each sense's synset. Here the words are resolved together in a few
+//! set-based queries: the base forms of all words, then the entries of all lemmas with
+//! their senses, then the details of all entries, then all synsets with their
+//! attributes, each bound to `IN` lists of up to [`IN_BATCH_SIZE`] values.
+//!
+//! [`IN_BATCH_SIZE`]: crate::db::IN_BATCH_SIZE
+
+use crate::db::{part_of_speech_to_string, query_in_batches, stored_parts_of_speech};
+use crate::error::Result;
+use crate::models::{Form, LexicalEntry, PartOfSpeech, Pronunciation, Synset, Tag};
+use crate::{
+    GlossRows, SYNSET_ATTRIBUTE_COLUMNS, morphy, push_entry_row, push_synset_row,
+    read_synset_attributes, row_to_syntactic_behaviour,
+};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// The entries and synsets found for a word by [`WordNet::lookup_many`].
+///
+/// [`WordNet::lookup_many`]: crate::WordNet::lookup_many
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordLookup {
+    /// The word as given
+    pub word: String,
+    /// The word's entries with their senses, as [`WordNet::lookup_entries`] returns them
+    ///
+    /// [`WordNet::lookup_entries`]: crate::WordNet::lookup_entries
+    pub entries: Vec<LexicalEntry>,
+    /// The synsets of the entries' senses, in sense order, each once
+    pub synsets: Vec<Synset>,
+}
+
+impl WordLookup {
+    /// Returns `true` if the word has at least one entry.
+    pub fn is_found(&self) -> bool {
+        !self.entries.is_empty()
+    }
+
+    /// Returns the synset with the given ID, if it is one of the word's synsets.
+    pub fn synset(&self, synset_id: &str) -> Option<&Synset> {
+        self.synsets.iter().find(|synset| synset.id == synset_id)
+    }
+}
+
+/// Looks up each of `words`, returning one result per word in the same order.
+///
+/// Entries match as in [`WordNet::lookup_entries`], including the entries of base forms
+/// if `morphy_fallback` is set.
+///
+/// [`WordNet::lookup_entries`]: crate::WordNet::lookup_entries
+pub(crate) fn lookup_many(
+    conn: &Connection,
+    words: &[&str],
+    pos_filter: Option<PartOfSpeech>,
+    morphy_fallback: bool,
+) -> Result<Vec<WordLookup>> {
+    // The lemmas whose entries each word has, as (lowercase lemma, part of speech)
+    let mut word_lemmas: Vec<Vec<(String, Option<PartOfSpeech>)>> = words
+        .iter()
+        .map(|word| vec![(word.to_lowercase(), pos_filter)])
+        .collect();
+    if morphy_fallback {
+        let base_forms = morphy::lemmatize_many(conn, words, pos_filter)?;
+        for (lemmas, base_forms) in word_lemmas.iter_mut().zip(base_forms) {
+            for (base_form, pos) in base_forms {
+                lemmas.push((base_form.to_lowercase(), Some(pos)));
+            }
+        }
+    }
+
+    let mut lemmas: Vec<String> = word_lemmas
+        .iter()
+        .flatten()
+        .map(|(lemma, _)| lemma.clone())
+        .collect();
+    lemmas.sort();
+    lemmas.dedup();
+    let mut entries = fetch_entries_by_lemmas(conn, &lemmas)?;
+    fetch_entries_details(conn, entries.iter_mut().map(|(_, entry)| entry))?;
+
+    let mut synset_ids: Vec<String> = entries
+        .iter()
+        .flat_map(|(_, entry)| entry.senses.iter().map(|sense| sense.synset.clone()))
+        .collect();
+    synset_ids.sort();
+    synset_ids.dedup();
+    let synsets = fetch_synsets(conn, &synset_ids)?;
+
+    let mut entries_by_lemma: HashMap<&str, Vec<&LexicalEntry>> = HashMap::new();
+    for (lemma, entry) in &entries {
+        entries_by_lemma.entry(lemma).or_default().push(entry);
+    }
+    let mut results = Vec::with_capacity(words.len());
+    for (word, lemmas) in words.iter().zip(&word_lemmas) {
+        let mut result = WordLookup {
+            word: word.to_string(),
+            entries: Vec::new(),
+            synsets: Vec::new(),
+        };
+        for (lemma, pos) in lemmas {
+            let lemma_entries = entries_by_lemma.get(lemma.as_str()).into_iter().flatten();
+            for entry in lemma_entries {
+                // Adjectives include satellites, as in `WordNet::lookup_entries`
+                let pos_matches = pos.is_none_or(|pos| {
+                    let stored = part_of_speech_to_string(entry.lemma.part_of_speech);
+                    let (first, second) = stored_parts_of_speech(pos);
+                    stored == first || stored == second
+                });
+                if pos_matches && !result.entries.iter().any(|e| e.id == entry.id) {
+                    result.entries.push((*entry).clone());
+                }
+            }
+        }
+        for sense in result.entries.iter().flat_map(|entry| &entry.senses) {
+            if result.synset(&sense.synset).is_none()
+                && let Some(synset) = synsets.get(&sense.synset)
+            {
+                result.synsets.push(synset.clone());
+            }
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Loads the entries of the given lowercase lemmas with their senses, in source order,
+/// each paired with its lowercase lemma.
+fn fetch_entries_by_lemmas(
+    conn: &Connection,
+    lemmas: &[String],
+) -> Result<Vec<(String, LexicalEntry)>> {
+    // As in `WordNet::fetch_entries_by_lemma`, for all lemmas at once
+    let sql = "
+        SELECT
+            le.lemma_written_form_lower AS lemma_lower,
+            le.id AS entry_id, le.lemma_written_form, le.part_of_speech, le.lemma_script,
+            p.variety, p.notation, p.phonemic, p.audio, p.text AS pron_text,
+            s.id AS sense_id, s.synset_id,
+            sr.target_sense_id AS sense_rel_target, sr.rel_type AS sense_rel_type,
+            s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+            s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+            (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
+        FROM lexical_entries le
+        LEFT JOIN pronunciations p ON le.id = p.entry_id AND p.form_index IS NULL
+        LEFT JOIN senses s ON le.id = s.entry_id
+        LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id
+        WHERE le.lemma_written_form_lower IN ({values})
+        ORDER BY le.position, s.n IS NULL, s.n, s.position, sr.rowid, p.rowid
+    ";
+    let mut entries: Vec<LexicalEntry> = Vec::new();
+    let mut entry_lemmas: Vec<String> = Vec::new();
+    query_in_batches(conn, sql, lemmas, |row| {
+        push_entry_row(&mut entries, row)?;
+        if entry_lemmas.len() < entries.len() {
+            entry_lemmas.push(row.get("lemma_lower")?);
+        }
+        Ok(())
+    })?;
+    Ok(entry_lemmas.into_iter().zip(entries).collect())
+}
+
+/// Fills in the lemma tags, variant forms and entry-level syntactic behaviours of
+/// entries, as `fetch_entry_details` does for one entry.
+fn fetch_entries_details<'a>(
+    conn: &Connection,
+    entries: impl Iterator<Item = &'a mut LexicalEntry>,
+) -> Result<()> {
+    let mut by_id: HashMap<String, &mut LexicalEntry> =
+        entries.map(|entry| (entry.id.clone(), entry)).collect();
+    let mut entry_ids: Vec<String> = by_id.keys().cloned().collect();
+    entry_ids.sort();
+
+    let forms = query_in_batches(
+        conn,
+        "SELECT entry_id, id, written_form, script FROM forms
+         WHERE entry_id IN ({values}) ORDER BY entry_id, form_index",
+        &entry_ids,
+        |row| {
+            let form = Form {
+                id: row.get(1)?,
+                written_form: row.get(2)?,
+                script: row.get(3)?,
+                pronunciations: Vec::new(),
+                tags: Vec::new(),
+            };
+            Ok((row.get::<_, String>(0)?, form))
+        },
+    )?;
+    for (entry_id, form) in forms {
+        if let Some(entry) = by_id.get_mut(&entry_id) {
+            entry.forms.push(form);
+        }
+    }
+
+    let tags = query_in_batches(
+        conn,
+        "SELECT entry_id, form_index, category, text FROM tags
+         WHERE entry_id IN ({values}) ORDER BY rowid",
+        &entry_ids,
+        |row| {
+            let tag = Tag {
+                category: row.get(2)?,
+                text: row.get(3)?,
+            };
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<usize>>(1)?,
+                tag,
+            ))
+        },
+    )?;
+    for (entry_id, form_index, tag) in tags {
+        let Some(entry) = by_id.get_mut(&entry_id) else {
+            continue;
+        };
+        match form_index {
+            None => entry.lemma.tags.push(tag),
+            Some(index) => {
+                if let Some(form) = entry.forms.get_mut(index) {
+                    form.tags.push(tag);
+                }
+            }
+        }
+    }
+
+    let prons = query_in_batches(
+        conn,
+        "SELECT entry_id, form_index, variety, notation, phonemic, audio, text
+         FROM pronunciations
+         WHERE entry_id IN ({values}) AND form_index IS NOT NULL
+         ORDER BY rowid",
+        &entry_ids,
+        |row| {
+            let pron = Pronunciation {
+                variety: row.get(2)?,
+                notation: row.get(3)?,
+                phonemic: row.get(4)?,
+                audio: row.get(5)?,
+                text: row.get(6)?,
+            };
+            Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?, pron))
+        },
+    )?;
+    for (entry_id, form_index, pron) in prons {
+        if let Some(form) = by_id
+            .get_mut(&entry_id)
+            .and_then(|entry| entry.forms.get_mut(form_index))
+        {
+            form.pronunciations.push(pron);
+        }
+    }
+
+    let behaviours = query_in_batches(
+        conn,
+        "SELECT id, subcategorization_frame, senses, entry_id FROM syntactic_behaviours
+         WHERE entry_id IN ({values}) ORDER BY rowid",
+        &entry_ids,
+        |row| Ok((row.get::<_, String>(3)?, row_to_syntactic_behaviour(row)?)),
+    )?;
+    for (entry_id, behaviour) in behaviours {
+        if let Some(entry) = by_id.get_mut(&entry_id) {
+            entry.syntactic_behaviours.push(behaviour);
+        }
+    }
+    Ok(())
+}
+
+/// Loads synsets with their glosses, relations and attributes, by ID.
+fn fetch_synsets(conn: &Connection, synset_ids: &[String]) -> Result<HashMap<String, Synset>> {
+    // As in `WordNet::fetch_full_synset_by_id`, grouped by synset
+    let sql = "
+        SELECT
+            s.id, s.ili, s.part_of_speech,
+            d.text AS def_text, d.dc_source AS def_source,
+            id.text AS ili_def_text, id.dc_source AS ili_def_source,
+            e.text AS ex_text, e.dc_source AS ex_source,
+            sr.target_synset_id, sr.rel_type,
+            d.rowid AS def_rowid, e.rowid AS ex_rowid
+        FROM synsets s
+        LEFT JOIN definitions d ON s.id = d.synset_id
+        LEFT JOIN ili_definitions id ON s.id = id.synset_id
+        LEFT JOIN examples e ON s.id = e.synset_id
+        LEFT JOIN synset_relations sr ON s.id = sr.source_synset_id
+        WHERE s.id IN ({values})
+        ORDER BY s.id, d.rowid, e.rowid, sr.rowid
+    ";
+    let mut synsets: Vec<Synset> = Vec::new();
+    let mut gloss_rows = GlossRows::default();
+    query_in_batches(conn, sql, synset_ids, |row| {
+        push_synset_row(&mut synsets, &mut gloss_rows, row)
+    })?;
+    let mut by_id: HashMap<String, Synset> = synsets
+        .into_iter()
+        .map(|synset| (synset.id.clone(), synset))
+        .collect();
+
+    query_in_batches(
+        conn,
+        &format!("SELECT {SYNSET_ATTRIBUTE_COLUMNS}, id FROM synsets WHERE id IN ({{values}})"),
+        synset_ids,
+        |row| {
+            let synset_id: String = row.get(19)?;
+            match by_id.get_mut(&synset_id) {
+                Some(synset) => read_synset_attributes(synset, row),
+                None => Ok(()),
+            }
+        },
+    )?;
+    let members = query_in_batches(
+        conn,
+        "SELECT synset_id, sense_id FROM synset_members
+         WHERE synset_id IN ({values}) ORDER BY synset_id, position",
+        synset_ids,
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+    )?;
+    for (synset_id, sense_id) in members {
+        if let Some(synset) = by_id.get_mut(&synset_id) {
+            if !synset.members.is_empty() {
+                synset.members.push(' ');
+            }
+            synset.members.push_str(&sense_id);
+        }
+    }
+    Ok(by_id)
+}
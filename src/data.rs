@@ -1,74 +1,438 @@
 //! Data download and management for OEWN.
 //!
-//! This module handles downloading the OEWN XML data from GitHub releases,
-//! caching it locally, and decompressing it as needed.
+//! This module handles downloading the OEWN XML data from GitHub releases and
+//! caching it locally. Gzipped archives are kept as they are and streamed by the
+//! parser; only ZIP sources are extracted.
+//!
+//! Downloaded and decompressed files are written to a `.part` file and renamed into
+//! place once complete. Their SHA-256 is recorded in a `.sha256` sidecar and checked
+//! again before a cached file is reused; files that fail verification are deleted and
+//! fetched again, except in offline mode. A download is refused unless a SHA-256 is
+//! known for it, from the release registry or the caller, or unverified downloads are
+//! explicitly allowed.
+//!
+//! Cached files without a sidecar, such as those cached before checksums were recorded,
+//! are checked against a known SHA-256 and have it recorded if they match. A GZ archive
+//! with nothing to check it against is used as it is, without recording its checksum;
+//! such an XML file is skipped, since the archive it came from can be used instead.
 
+use crate::LoadOptions;
 use crate::error::{OewnError, Result};
+use crate::fetch::{Fetcher, HttpFetcher, LocalFileFetcher};
 use crate::progress::{ProgressReporter, ProgressUpdate, report_progress_async};
 use directories_next::ProjectDirs;
-use flate2::read::GzDecoder;
 use futures::StreamExt;
-use log::info;
+use log::{debug, info, warn};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use std::fmt;
 use std::fs::{self, File};
 use std::io::{self, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-/// OEWN version being targeted
+/// Latest OEWN edition known to this crate
 pub const OEWN_VERSION: &str = "2024";
 /// Subdirectory name within user's data directory
 pub const OEWN_SUBDIR: &str = "oewn-rs";
-const OEWN_FILENAME_GZ: &str = "english-wordnet-2024.xml.gz";
-const OEWN_FILENAME_XML: &str = "english-wordnet-2024.xml";
-const OEWN_DOWNLOAD_URL: &str = "https://github.com/globalwordnet/english-wordnet/releases/download/2024-edition/english-wordnet-2024.xml.gz";
-
-/// Gets the project's data directory path.
-/// Creates the directory if it doesn't exist.
-fn get_data_dir() -> Result<PathBuf> {
-    let proj_dirs =
-        ProjectDirs::from("org", "OewnRs", OEWN_SUBDIR).ok_or(OewnError::DataDirNotFound)?;
-    let data_dir = proj_dirs.data_dir().to_path_buf();
+
+/// A published OEWN release and the files it is distributed as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Release {
+    /// Edition year (e.g., "2024")
+    pub edition: &'static str,
+    /// Download URL of the GZipped LMF XML file
+    pub download_url: &'static str,
+    /// Filename of the cached GZ archive
+    pub filename_gz: &'static str,
+    /// Filename of the decompressed XML file
+    pub filename_xml: &'static str,
+    /// SHA-256 of the GZ archive, if one has been pinned for this release.
+    /// Without it, downloads are checked against the checksum recorded at download time.
+    pub sha256_gz: Option<&'static str>,
+}
+
+/// Registry of known OEWN releases, oldest first.
+pub const RELEASES: &[Release] = &[
+    Release {
+        edition: "2019",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2019/english-wordnet-2019.xml.gz",
+        filename_gz: "english-wordnet-2019.xml.gz",
+        filename_xml: "english-wordnet-2019.xml",
+        sha256_gz: None,
+    },
+    Release {
+        edition: "2020",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2020-edition/english-wordnet-2020.xml.gz",
+        filename_gz: "english-wordnet-2020.xml.gz",
+        filename_xml: "english-wordnet-2020.xml",
+        sha256_gz: None,
+    },
+    Release {
+        edition: "2021",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2021-edition/english-wordnet-2021.xml.gz",
+        filename_gz: "english-wordnet-2021.xml.gz",
+        filename_xml: "english-wordnet-2021.xml",
+        sha256_gz: None,
+    },
+    Release {
+        edition: "2022",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2022-edition/english-wordnet-2022.xml.gz",
+        filename_gz: "english-wordnet-2022.xml.gz",
+        filename_xml: "english-wordnet-2022.xml",
+        sha256_gz: None,
+    },
+    Release {
+        edition: "2023",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2023-edition/english-wordnet-2023.xml.gz",
+        filename_gz: "english-wordnet-2023.xml.gz",
+        filename_xml: "english-wordnet-2023.xml",
+        sha256_gz: None,
+    },
+    Release {
+        edition: "2024",
+        download_url: "https://github.com/globalwordnet/english-wordnet/releases/download/2024-edition/english-wordnet-2024.xml.gz",
+        filename_gz: "english-wordnet-2024.xml.gz",
+        filename_xml: "english-wordnet-2024.xml",
+        sha256_gz: None,
+    },
+];
+
+/// Selects which OEWN edition to load.
+///
+/// Parses from `"latest"` or an edition year such as `"2021"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Edition {
+    /// The most recent edition in [`RELEASES`]
+    #[default]
+    Latest,
+    /// A specific edition year
+    Year(u16),
+}
+
+impl Edition {
+    /// Looks up the release for this edition in the registry.
+    pub fn release(&self) -> Result<&'static Release> {
+        match self {
+            Edition::Latest => RELEASES
+                .iter()
+                .find(|r| r.edition == OEWN_VERSION)
+                .ok_or_else(|| OewnError::UnknownEdition(OEWN_VERSION.to_string())),
+            Edition::Year(year) => {
+                let year = year.to_string();
+                RELEASES
+                    .iter()
+                    .find(|r| r.edition == year)
+                    .ok_or(OewnError::UnknownEdition(year))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edition::Latest => write!(f, "latest"),
+            Edition::Year(year) => write!(f, "{}", year),
+        }
+    }
+}
+
+impl FromStr for Edition {
+    type Err = OewnError;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("latest") {
+            return Ok(Edition::Latest);
+        }
+        let edition = s
+            .parse::<u16>()
+            .map(Edition::Year)
+            .map_err(|_| OewnError::UnknownEdition(s.to_string()))?;
+        // Reject years we have no release for up front
+        edition.release()?;
+        Ok(edition)
+    }
+}
+
+/// Environment variable that overrides the download URL (see [`resolve_download_url`]).
+pub const DOWNLOAD_URL_ENV: &str = "OEWN_DOWNLOAD_URL";
+
+/// Gets the data directory path: `override_dir` if given, otherwise the project's
+/// data directory. Creates the directory if it doesn't exist.
+pub(crate) fn get_data_dir(override_dir: Option<&Path>) -> Result<PathBuf> {
+    let data_dir = match override_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => ProjectDirs::from("org", "OewnRs", OEWN_SUBDIR)
+            .ok_or(OewnError::DataDirNotFound)?
+            .data_dir()
+            .to_path_buf(),
+    };
     fs::create_dir_all(&data_dir)?;
     Ok(data_dir)
 }
 
-/// Downloads a file from a URL to a specified path using streaming with progress reporting.
-async fn download_file(
-    url: &str,
-    dest_path: &Path,
-    reporter: Option<ProgressReporter>,
-) -> Result<()> {
-    let stage_desc = "Downloading OEWN data".to_string();
+/// Resolves the URL to download a release from.
+///
+/// The URL is taken from `options.download_url`, then the `OEWN_DOWNLOAD_URL`
+/// environment variable, then the release registry. An override ending in `/` is
+/// treated as a mirror base URL and the release's archive filename is appended.
+pub fn resolve_download_url(options: &LoadOptions, release: &Release) -> String {
+    let override_url = options
+        .download_url
+        .clone()
+        .or_else(|| std::env::var(DOWNLOAD_URL_ENV).ok())
+        .filter(|url| !url.trim().is_empty());
+    match override_url {
+        Some(url) if url.ends_with('/') => format!("{}{}", url, release.filename_gz),
+        Some(url) => url,
+        None => release.download_url.to_string(),
+    }
+}
 
-    info!(
-        "Downloading data from {} to {:?} (streaming)...",
-        url, dest_path
-    );
-    let response = reqwest::get(url).await?.error_for_status()?;
+/// Path of the temporary file a download or decompression writes to before it is
+/// verified and renamed into place.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
 
-    let total_size = response.content_length();
+/// Path of the file recording the validator of the response a partial download came
+/// from, so that it is only resumed from the same version of the resource.
+fn validator_path(partial: &Path) -> PathBuf {
+    let mut name = partial.file_name().unwrap_or_default().to_os_string();
+    name.push(".validator");
+    partial.with_file_name(name)
+}
 
-    if let Some(ref reporter) = reporter {
+/// Deletes a partial download together with its validator.
+fn remove_partial_file(partial: &Path) {
+    let _ = fs::remove_file(partial);
+    let _ = fs::remove_file(validator_path(partial));
+}
+
+/// Path of the sidecar file recording the SHA-256 of a verified cached file.
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+/// A writer that computes the SHA-256 of everything written through it.
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        HashingWriter {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Flushes the inner writer and returns the hex digest.
+    fn finish(mut self) -> io::Result<String> {
+        self.inner.flush()?;
+        Ok(to_hex(&self.hasher.finalize()))
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Computes the SHA-256 of a file as a lowercase hex string.
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut writer = HashingWriter::new(io::sink());
+    io::copy(&mut BufReader::new(File::open(path)?), &mut writer)?;
+    writer.finish()
+}
+
+/// Moves a verified temporary file into place and records its checksum next to it.
+fn commit_verified_file(partial: &Path, dest_path: &Path, sha256: &str) -> Result<()> {
+    fs::rename(partial, dest_path)?;
+    fs::write(checksum_path(dest_path), format!("{}\n", sha256))?;
+    Ok(())
+}
+
+/// Deletes a cached file together with its checksum sidecar.
+fn remove_cached_file(path: &Path) {
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(checksum_path(path));
+}
+
+/// Verifies a cached file against a pinned checksum, or the checksum recorded when it
+/// was written.
+///
+/// Returns `false` if there is nothing to verify the file against, such as a file
+/// cached before checksums were recorded. A file verified against a pinned checksum
+/// has it recorded if it had no sidecar yet.
+async fn verify_cached_file(path: &Path, pinned: Option<&str>) -> Result<bool> {
+    let expected = match pinned {
+        Some(sha256) => sha256.to_lowercase(),
+        None => match fs::read_to_string(checksum_path(path)) {
+            Ok(recorded) => recorded.trim().to_lowercase(),
+            Err(_) => return Ok(false),
+        },
+    };
+
+    let owned_path = path.to_path_buf();
+    let actual = tokio::task::spawn_blocking(move || sha256_file(&owned_path)).await??;
+    if actual == expected {
+        debug!("Checksum verified for {:?}", path);
+        if !checksum_path(path).exists() {
+            fs::write(checksum_path(path), format!("{}\n", actual))?;
+        }
+        Ok(true)
+    } else {
+        Err(OewnError::ChecksumMismatch {
+            path: path.display().to_string(),
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Timeout, retry and backoff policy for downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadPolicy {
+    /// Maximum time to wait for a connection to be established
+    pub connect_timeout: Duration,
+    /// Maximum time to wait between successive reads of the response body
+    pub read_timeout: Duration,
+    /// Number of retries after the first failed attempt
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for DownloadPolicy {
+    fn default() -> Self {
+        DownloadPolicy {
+            connect_timeout: Duration::from_secs(30),
+            read_timeout: Duration::from_secs(60),
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl DownloadPolicy {
+    /// Exponential backoff delay before the given retry (1-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Whether a failed download attempt is worth retrying.
+fn is_retryable(err: &OewnError) -> bool {
+    match err {
+        OewnError::Network(e) => match e.status() {
+            Some(status) => {
+                status.is_server_error()
+                    || status == StatusCode::TOO_MANY_REQUESTS
+                    || status == StatusCode::REQUEST_TIMEOUT
+            }
+            None => !e.is_builder() && !e.is_redirect(),
+        },
+        _ => false,
+    }
+}
+
+/// Performs a single download attempt, resuming from the partial file if one exists.
+///
+/// Returns the number of bytes in the partial file once the response body is complete.
+async fn download_attempt(
+    fetcher: &dyn Fetcher,
+    url: &str,
+    partial: &Path,
+    stage_desc: &str,
+    reporter: &Option<ProgressReporter>,
+) -> Result<u64> {
+    // Partial data of unknown origin cannot be resumed safely
+    let validator = fs::read_to_string(validator_path(partial)).ok();
+    let existing = match validator {
+        Some(_) => fs::metadata(partial).map(|m| m.len()).unwrap_or(0),
+        None => 0,
+    };
+    if existing > 0 {
+        info!("Resuming download at byte {}", existing);
+    }
+
+    let response = fetcher.fetch(url, existing, validator.as_deref()).await?;
+    let resumed = existing > 0 && response.resumed;
+    if !resumed {
+        match &response.validator {
+            Some(validator) => fs::write(validator_path(partial), validator)?,
+            None => {
+                let _ = fs::remove_file(validator_path(partial));
+            }
+        }
+    }
+    let mut downloaded = if resumed { existing } else { 0 };
+    let total_size = response.content_length.map(|len| len + downloaded);
+
+    if let Some(reporter) = reporter {
         report_progress_async(
             reporter,
-            ProgressUpdate::new(stage_desc.clone(), 0, total_size, None),
+            ProgressUpdate::new(
+                stage_desc.to_string(),
+                downloaded,
+                total_size,
+                resumed.then(|| format!("Resuming at {} bytes", downloaded)),
+            ),
         )
         .await;
     }
 
-    let mut dest_file = BufWriter::new(File::create(dest_path)?);
-    let mut stream = response.bytes_stream();
-    let mut downloaded: u64 = 0;
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(partial)?;
+    let mut dest_file = BufWriter::new(file);
+    let mut stream = response.body;
 
     while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result?;
+        let chunk = match chunk_result {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                // Keep what we have so the next attempt can resume from it
+                dest_file.flush()?;
+                return Err(e);
+            }
+        };
         dest_file.write_all(&chunk)?;
         downloaded += chunk.len() as u64;
 
-        if let Some(ref reporter) = reporter {
+        if let Some(reporter) = reporter {
             report_progress_async(
                 reporter,
                 ProgressUpdate {
-                    stage_description: stage_desc.clone(),
+                    stage_description: stage_desc.to_string(),
                     current_item: downloaded,
                     total_items: total_size,
                     message: None,
@@ -79,46 +443,107 @@ async fn download_file(
     }
 
     dest_file.flush()?;
+    Ok(downloaded)
+}
 
-    if let Some(ref reporter) = reporter {
-        if let Some(total) = total_size {
-            report_progress_async(
-                reporter,
-                ProgressUpdate {
-                    stage_description: stage_desc.clone(),
-                    current_item: total,
-                    total_items: Some(total),
-                    message: Some("Download complete.".to_string()),
-                },
-            )
-            .await;
-        } else {
-            report_progress_async(
-                reporter,
-                ProgressUpdate {
-                    stage_description: stage_desc.clone(),
-                    current_item: downloaded,
-                    total_items: None,
-                    message: Some("Download complete.".to_string()),
-                },
-            )
-            .await;
+/// Downloads a file from a URL to a specified path using streaming with progress reporting.
+///
+/// The data is written to a `.part` file first and only renamed into place once its
+/// SHA-256 matches `expected_sha256` (when given), so an interrupted download never
+/// leaves a truncated file at `dest_path`. A `.part` file left behind by an earlier
+/// attempt is resumed if the resource is unchanged since (with an HTTP Range request
+/// guarded by `If-Range` for [`HttpFetcher`]), and failed
+/// attempts are retried with exponential backoff according to `policy`.
+async fn download_file(
+    fetcher: &dyn Fetcher,
+    url: &str,
+    dest_path: &Path,
+    expected_sha256: Option<&str>,
+    policy: &DownloadPolicy,
+    reporter: Option<ProgressReporter>,
+) -> Result<()> {
+    let stage_desc = "Downloading OEWN data".to_string();
+
+    info!(
+        "Downloading data from {} to {:?} (streaming)...",
+        url, dest_path
+    );
+    let partial = partial_path(dest_path);
+
+    let mut retry = 0;
+    let downloaded = loop {
+        match download_attempt(fetcher, url, &partial, &stage_desc, &reporter).await {
+            Ok(downloaded) => break downloaded,
+            Err(e) if retry < policy.max_retries && is_retryable(&e) => {
+                retry += 1;
+                let delay = policy.backoff(retry);
+                warn!(
+                    "Download attempt failed ({}). Retry {}/{} in {:?}...",
+                    e, retry, policy.max_retries, delay
+                );
+                if let Some(ref reporter) = reporter {
+                    let current = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+                    report_progress_async(
+                        reporter,
+                        ProgressUpdate {
+                            stage_description: stage_desc.clone(),
+                            current_item: current,
+                            total_items: None,
+                            message: Some(format!(
+                                "Retry {}/{} in {:?}: {}",
+                                retry, policy.max_retries, delay, e
+                            )),
+                        },
+                    )
+                    .await;
+                }
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
         }
+    };
+
+    let hash_path = partial.clone();
+    let actual_sha256 = tokio::task::spawn_blocking(move || sha256_file(&hash_path)).await??;
+    if let Some(expected) = expected_sha256
+        && !expected.eq_ignore_ascii_case(&actual_sha256)
+    {
+        remove_partial_file(&partial);
+        return Err(OewnError::ChecksumMismatch {
+            path: dest_path.display().to_string(),
+            expected: expected.to_lowercase(),
+            actual: actual_sha256,
+        });
+    }
+    commit_verified_file(&partial, dest_path, &actual_sha256)?;
+    let _ = fs::remove_file(validator_path(&partial));
+
+    if let Some(ref reporter) = reporter {
+        report_progress_async(
+            reporter,
+            ProgressUpdate {
+                stage_description: stage_desc.clone(),
+                current_item: downloaded,
+                total_items: Some(downloaded),
+                message: Some("Download complete.".to_string()),
+            },
+        )
+        .await;
     }
 
     info!("Download complete.");
     Ok(())
 }
 
-/// Decompresses a GZipped file with progress reporting.
-async fn decompress_gz(
-    gz_path: &Path,
+/// Extracts the first `.xml` entry of a ZIP archive with progress reporting.
+async fn extract_zip(
+    zip_path: &Path,
     dest_path: &Path,
     reporter: Option<ProgressReporter>,
 ) -> Result<()> {
-    let stage_desc = "Decompressing OEWN data".to_string();
+    let stage_desc = "Extracting OEWN data".to_string();
 
-    info!("Decompressing {:?} to {:?}...", gz_path, dest_path);
+    info!("Extracting {:?} to {:?}...", zip_path, dest_path);
 
     if let Some(ref reporter) = reporter {
         report_progress_async(
@@ -128,16 +553,26 @@ async fn decompress_gz(
         .await;
     }
 
-    let gz_path = gz_path.to_path_buf();
+    let zip_path = zip_path.to_path_buf();
     let dest_path = dest_path.to_path_buf();
 
     tokio::task::spawn_blocking(move || {
-        let gz_file = File::open(&gz_path)?;
-        let mut decoder = GzDecoder::new(BufReader::new(gz_file));
-        let mut dest_file = BufWriter::new(File::create(&dest_path)?);
-        io::copy(&mut decoder, &mut dest_file)?;
-        dest_file.flush()?;
-        Ok::<(), std::io::Error>(())
+        let mut archive = zip::ZipArchive::new(BufReader::new(File::open(&zip_path)?))?;
+        let index = (0..archive.len())
+            .find(|&i| {
+                archive
+                    .name_for_index(i)
+                    .is_some_and(|name| name.to_lowercase().ends_with(".xml"))
+            })
+            .ok_or_else(|| {
+                OewnError::DataFileNotFound(format!("No .xml file inside {:?}", zip_path))
+            })?;
+        let mut entry = archive.by_index(index)?;
+        let partial = partial_path(&dest_path);
+        let mut dest_file = HashingWriter::new(BufWriter::new(File::create(&partial)?));
+        io::copy(&mut entry, &mut dest_file)?;
+        let sha256 = dest_file.finish()?;
+        commit_verified_file(&partial, &dest_path, &sha256)
     })
     .await??;
 
@@ -148,41 +583,199 @@ async fn decompress_gz(
                 stage_description: stage_desc.clone(),
                 current_item: 1,
                 total_items: Some(1),
-                message: Some("Decompression complete.".to_string()),
+                message: Some("Extraction complete.".to_string()),
             },
         )
         .await;
     }
 
-    info!("Decompression complete.");
+    info!("Extraction complete.");
     Ok(())
 }
 
-/// Ensures the OEWN XML data file is present in the data directory.
-/// This function downloads and/or decompresses the data if necessary.
-pub async fn ensure_data(reporter: Option<ProgressReporter>) -> Result<PathBuf> {
-    let data_dir = get_data_dir()?;
-    let xml_path = data_dir.join(OEWN_FILENAME_XML);
-    let gz_path = data_dir.join(OEWN_FILENAME_GZ);
+/// Resolves an explicit local LMF source to a readable file without any network access.
+///
+/// `source` may be a `.xml`, `.xml.gz` or `.zip` file, or a directory holding the
+/// cached files of the requested edition. XML and gzipped XML are used in place;
+/// ZIP archives are extracted into the `local` subdirectory of the data directory
+/// on every call.
+async fn resolve_local_source(
+    source: &Path,
+    release: &Release,
+    data_dir: Option<&Path>,
+    reporter: Option<ProgressReporter>,
+) -> Result<PathBuf> {
+    if !source.exists() {
+        return Err(OewnError::DataFileNotFound(format!("{:?}", source)));
+    }
+
+    let file = if source.is_dir() {
+        let xml_path = source.join(release.filename_xml);
+        let gz_path = source.join(release.filename_gz);
+        if xml_path.exists() {
+            xml_path
+        } else if gz_path.exists() {
+            gz_path
+        } else {
+            return Err(OewnError::DataFileNotFound(format!(
+                "Neither {} nor {} found in {:?}",
+                release.filename_xml, release.filename_gz, source
+            )));
+        }
+    } else {
+        source.to_path_buf()
+    };
+
+    let file_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if file_name.ends_with(".xml") || file_name.ends_with(".xml.gz") {
+        info!("Using local OEWN data file: {:?}", file);
+        Ok(file)
+    } else if let Some(stem) = file_name.strip_suffix(".zip") {
+        let local_dir = get_data_dir(data_dir)?.join("local");
+        fs::create_dir_all(&local_dir)?;
+        let xml_path = local_dir.join(format!("{}.xml", stem.trim_end_matches(".xml")));
+        extract_zip(&file, &xml_path, reporter).await?;
+        Ok(xml_path)
+    } else {
+        Err(OewnError::InvalidArgument(format!(
+            "Unsupported LMF source {:?}: expected a .xml, .xml.gz or .zip file",
+            file
+        )))
+    }
+}
+
+/// Ensures the OEWN LMF data is available and returns the path of an XML or
+/// gzipped XML file that can be passed to [`crate::parse::open_lmf`].
+///
+/// If `options.source` is set, the data is read from that local file or directory
+/// and the network is never used. Otherwise a previously decompressed XML file in the
+/// data directory is used if present, falling back to the edition's GZ archive, which
+/// is downloaded if necessary. Each edition has its own files, so several editions
+/// can be cached side by side.
+///
+/// With `options.offline` set, a missing archive fails with
+/// [`OewnError::OfflineDataUnavailable`] instead of being downloaded, and cached files
+/// that fail verification are kept. A download with no pinned or expected SHA-256
+/// fails with [`OewnError::UnverifiedDownload`] unless `options.allow_unverified` is set. Otherwise such files are deleted, and the archive
+/// is fetched from [`resolve_download_url`] with `options.fetcher`. Without a fetcher,
+/// `file://` URLs are read from disk and anything else is fetched over HTTP.
+pub async fn ensure_data(
+    options: &LoadOptions,
+    reporter: Option<ProgressReporter>,
+) -> Result<PathBuf> {
+    ensure_release_data(options, options.edition.release()?, reporter).await
+}
+
+/// Ensures the data of `release` is available, as [`ensure_data`] does.
+async fn ensure_release_data(
+    options: &LoadOptions,
+    release: &Release,
+    reporter: Option<ProgressReporter>,
+) -> Result<PathBuf> {
+    if let Some(source) = &options.source {
+        info!("Using explicit OEWN data source: {:?}", source);
+        return resolve_local_source(source, release, options.data_dir.as_deref(), reporter).await;
+    }
+
+    let data_dir = get_data_dir(options.data_dir.as_deref())?;
+    let xml_path = data_dir.join(release.filename_xml);
+    let gz_path = data_dir.join(release.filename_gz);
+    let pinned_sha256 = options.expected_sha256.as_deref().or(release.sha256_gz);
 
     if xml_path.exists() {
-        info!("Found existing OEWN XML data file: {:?}", xml_path);
-        return Ok(xml_path);
-    } else {
-        info!("OEWN XML data file not found at {:?}.", xml_path);
+        match verify_cached_file(&xml_path, None).await {
+            Ok(true) => {
+                info!("Found existing OEWN XML data file: {:?}", xml_path);
+                return Ok(xml_path);
+            }
+            Ok(false) if options.offline => {
+                warn!("Ignoring unverified cached XML file: {:?}", xml_path);
+            }
+            Ok(false) => {
+                warn!("Discarding unverified cached XML file: {:?}", xml_path);
+                remove_cached_file(&xml_path);
+            }
+            Err(e @ OewnError::ChecksumMismatch { .. }) if options.offline => {
+                warn!("Ignoring cached XML file: {}", e);
+            }
+            Err(e @ OewnError::ChecksumMismatch { .. }) => {
+                warn!("Discarding cached XML file: {}", e);
+                remove_cached_file(&xml_path);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if gz_path.exists() {
+        match verify_cached_file(&gz_path, pinned_sha256).await {
+            Ok(true) => info!("Found existing OEWN GZ archive: {:?}", gz_path),
+            Ok(false) => warn!(
+                "No known SHA-256 for cached OEWN GZ archive {:?}; using it unverified.",
+                gz_path
+            ),
+            // It could not be fetched again
+            Err(e @ OewnError::ChecksumMismatch { .. }) if options.offline => return Err(e),
+            Err(e @ OewnError::ChecksumMismatch { .. }) => {
+                warn!(
+                    "Discarding cached GZ archive, it will be fetched again: {}",
+                    e
+                );
+                remove_cached_file(&gz_path);
+            }
+            Err(e) => return Err(e),
+        }
     }
 
     if !gz_path.exists() {
+        if options.offline {
+            return Err(OewnError::OfflineDataUnavailable(format!(
+                "OEWN {} data not found in {:?}",
+                release.edition, data_dir
+            )));
+        }
+        if pinned_sha256.is_none() {
+            if !options.allow_unverified {
+                return Err(OewnError::UnverifiedDownload(format!(
+                    "no SHA-256 is known for OEWN {}; give the expected one or allow unverified downloads",
+                    release.edition
+                )));
+            }
+            warn!(
+                "No known SHA-256 for OEWN {}; the download cannot be verified.",
+                release.edition
+            );
+        }
         info!("OEWN GZ archive not found at {:?}. Downloading...", gz_path);
-        download_file(OEWN_DOWNLOAD_URL, &gz_path, reporter.clone()).await?;
-    } else {
-        info!("Found existing OEWN GZ archive: {:?}", gz_path);
+        let url = resolve_download_url(options, release);
+        let default_fetcher: Box<dyn Fetcher>;
+        let fetcher: &dyn Fetcher = match &options.fetcher {
+            Some(fetcher) => fetcher.as_ref(),
+            None => {
+                default_fetcher = if url.starts_with("file://") {
+                    Box::new(LocalFileFetcher::default())
+                } else {
+                    Box::new(HttpFetcher::new(&options.download)?)
+                };
+                default_fetcher.as_ref()
+            }
+        };
+        download_file(
+            fetcher,
+            &url,
+            &gz_path,
+            pinned_sha256,
+            &options.download,
+            reporter.clone(),
+        )
+        .await?;
     }
 
-    decompress_gz(&gz_path, &xml_path, reporter).await?;
-
-    // 4. Return the path to the decompressed XML file
-    Ok(xml_path)
+    // The archive is streamed by the parser, no need to decompress it
+    Ok(gz_path)
 }
 
 #[cfg(test)]
@@ -202,80 +795,658 @@ mod tests {
         Ok(())
     }
 
+    /// In-process fetcher serving a fixed payload and recording requested URLs.
+    #[derive(Debug, Default)]
+    struct MemoryFetcher {
+        payload: Vec<u8>,
+        urls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Fetcher for MemoryFetcher {
+        fn fetch<'a>(
+            &'a self,
+            url: &'a str,
+            offset: u64,
+            _validator: Option<&'a str>,
+        ) -> futures::future::BoxFuture<'a, Result<crate::fetch::FetchResponse>> {
+            self.urls.lock().unwrap().push(url.to_string());
+            let body = bytes::Bytes::copy_from_slice(&self.payload[offset as usize..]);
+            Box::pin(async move {
+                Ok(crate::fetch::FetchResponse {
+                    resumed: offset > 0,
+                    content_length: Some(body.len() as u64),
+                    validator: None,
+                    body: futures::stream::iter(vec![Ok(body)]).boxed(),
+                })
+            })
+        }
+    }
+
     #[tokio::test]
-    #[ignore] // Ignored by default as it interacts with the file system and potentially network
     async fn test_ensure_data_flow() {
         let _ = env_logger::builder().is_test(true).try_init(); // Enable logging for tests
 
-        // Use a temporary directory to simulate the data directory
         let temp_dir = tempdir().expect("Failed to create temp dir");
-        let data_dir_guard = scopeguard::guard(temp_dir, |d| {
-            // Ensure temp dir is cleaned up even on panic
-            let _ = d.close();
-        });
-        let data_dir = data_dir_guard.path();
+        let data_dir = temp_dir.path().join("cache");
+        let release = Edition::Latest.release().unwrap();
+        let xml_path = data_dir.join(release.filename_xml);
+        let gz_path = data_dir.join(release.filename_gz);
 
-        let xml_path = data_dir.join(OEWN_FILENAME_XML);
-        let gz_path = data_dir.join(OEWN_FILENAME_GZ);
+        let dummy_xml_content = "<LexicalResource><Lexicon id='test'/></LexicalResource>";
+        let archive = temp_dir.path().join("archive.xml.gz");
+        create_dummy_gz(&archive, dummy_xml_content).expect("Failed to create dummy GZ");
+        let fetcher = std::sync::Arc::new(MemoryFetcher {
+            payload: fs::read(&archive).unwrap(),
+            ..Default::default()
+        });
+        let options = LoadOptions {
+            data_dir: Some(data_dir.clone()),
+            download_url: Some("https://mirror.example.com/oewn/".to_string()),
+            fetcher: Some(fetcher.clone()),
+            allow_unverified: true,
+            ..Default::default()
+        };
+        let fetch_count = || fetcher.urls.lock().unwrap().len();
 
-        // Mock the get_data_dir function to return our temp dir
-        // This requires modifying the original function or using a mocking library,
-        // which is complex. For now, we'll test the logic assuming get_data_dir works.
-        // Let's simulate the steps instead.
+        // --- Scenario 1: Nothing cached, archive is fetched from the mirror ---
+        let resolved = ensure_data(&options, None).await.unwrap();
+        assert_eq!(resolved, gz_path);
+        assert_eq!(fs::read(&gz_path).unwrap(), fetcher.payload);
+        assert_eq!(
+            fetcher.urls.lock().unwrap()[0],
+            format!("https://mirror.example.com/oewn/{}", release.filename_gz)
+        );
 
-        // --- Scenario 1: XML already exists ---
-        info!("--- Testing Scenario 1: XML exists ---");
-        fs::write(&xml_path, "<xml>dummy</xml>").expect("Failed to write dummy XML");
-        // We can't directly call ensure_data and mock get_data_dir easily,
-        // so we assert the expected outcome if it were called.
-        assert!(xml_path.exists());
-        // In a real test with mocking, we'd call ensure_data here.
-        fs::remove_file(&xml_path).unwrap(); // Clean up for next scenario
+        // --- Scenario 2: Verified archive is reused without fetching ---
+        assert_eq!(ensure_data(&options, None).await.unwrap(), gz_path);
+        assert_eq!(fetch_count(), 1);
 
-        // --- Scenario 2: GZ exists, XML does not ---
-        info!("--- Testing Scenario 2: GZ exists, XML does not ---");
-        let dummy_xml_content = "<LexicalResource><Lexicon id='test'/></LexicalResource>";
-        create_dummy_gz(&gz_path, dummy_xml_content).expect("Failed to create dummy GZ");
-        assert!(gz_path.exists());
+        // --- Scenario 3: A verified XML file takes precedence, a corrupt one is discarded ---
+        fs::write(&xml_path, dummy_xml_content).unwrap();
+        fs::write(checksum_path(&xml_path), sha256_file(&xml_path).unwrap()).unwrap();
+        assert_eq!(ensure_data(&options, None).await.unwrap(), xml_path);
+        fs::write(&xml_path, "<LexicalRes").unwrap();
+        assert_eq!(ensure_data(&options, None).await.unwrap(), gz_path);
         assert!(!xml_path.exists());
-        // Manually call decompress_gz as ensure_data would (no reporter for test)
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let decompress_result = rt.block_on(decompress_gz(&gz_path, &xml_path, None));
-        assert!(decompress_result.is_ok(), "Decompression failed");
-        assert!(xml_path.exists());
-        let decompressed_content = fs::read_to_string(&xml_path).unwrap();
-        assert_eq!(decompressed_content, dummy_xml_content);
-        fs::remove_file(&xml_path).unwrap();
-        fs::remove_file(&gz_path).unwrap(); // Clean up
-
-        // --- Scenario 3: Neither GZ nor XML exists (requires network) ---
-        // This part is hard to test reliably without actual network calls or complex mocking.
-        // We assume download_file and decompress_gz work individually.
-        info!("--- Testing Scenario 3: Network download (manual check recommended) ---");
+        assert_eq!(fetch_count(), 1);
+
+        // --- Scenario 4: Corrupt archive is deleted and fetched again ---
+        fs::write(&gz_path, b"truncated").unwrap();
+        ensure_data(&options, None).await.unwrap();
+        assert_eq!(fs::read(&gz_path).unwrap(), fetcher.payload);
+        assert_eq!(fetch_count(), 2);
+
+        // --- Scenario 5: A wrong pinned checksum rejects the download ---
+        let mismatched = LoadOptions {
+            data_dir: Some(temp_dir.path().join("mismatched")),
+            expected_sha256: Some("0".repeat(64)),
+            ..options.clone()
+        };
+        assert!(matches!(
+            ensure_data(&mismatched, None).await,
+            Err(OewnError::ChecksumMismatch { .. })
+        ));
+
+        // --- Scenario 6: Offline mode never fetches ---
+        let offline = LoadOptions {
+            data_dir: Some(temp_dir.path().join("offline")),
+            offline: true,
+            ..options.clone()
+        };
+        assert!(matches!(
+            ensure_data(&offline, None).await,
+            Err(OewnError::OfflineDataUnavailable(_))
+        ));
+        assert_eq!(fetch_count(), 3);
+    }
+
+    #[test]
+    fn test_resolve_download_url() {
+        let release = Edition::Year(2021).release().unwrap();
+        let options = LoadOptions {
+            download_url: Some("https://artifacts.example.com/oewn/".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_download_url(&options, release),
+            "https://artifacts.example.com/oewn/english-wordnet-2021.xml.gz"
+        );
+        let options = LoadOptions {
+            download_url: Some("https://artifacts.example.com/wn.xml.gz".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_download_url(&options, release),
+            "https://artifacts.example.com/wn.xml.gz"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_data_local_sources() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let content = "<LexicalResource><Lexicon id='local'/></LexicalResource>";
+
+        // Plain XML files are used in place
+        let xml_path = temp_dir.path().join("custom.xml");
+        fs::write(&xml_path, content).unwrap();
+        let options = LoadOptions {
+            source: Some(xml_path.clone()),
+            offline: true,
+            data_dir: Some(temp_dir.path().join("cache")),
+            ..Default::default()
+        };
+        assert_eq!(ensure_data(&options, None).await.unwrap(), xml_path);
+
+        // GZ archives are streamed in place
+        let gz_path = temp_dir.path().join("custom-gz.xml.gz");
+        create_dummy_gz(&gz_path, content).unwrap();
+        let options = LoadOptions {
+            source: Some(gz_path.clone()),
+            offline: true,
+            data_dir: Some(temp_dir.path().join("cache")),
+            ..Default::default()
+        };
+        assert_eq!(ensure_data(&options, None).await.unwrap(), gz_path);
+
+        // ZIP archives have their XML entry extracted
+        let zip_path = temp_dir.path().join("custom.zip");
+        {
+            let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+            writer
+                .start_file(
+                    "nested/english-wordnet.xml",
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+            writer.finish().unwrap();
+        }
+        let options = LoadOptions {
+            source: Some(zip_path),
+            offline: true,
+            data_dir: Some(temp_dir.path().join("cache")),
+            ..Default::default()
+        };
+        let resolved = ensure_data(&options, None).await.unwrap();
+        assert_eq!(fs::read_to_string(resolved).unwrap(), content);
+
+        // Directories must contain the edition's files
+        let options = LoadOptions {
+            source: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ensure_data(&options, None).await,
+            Err(OewnError::DataFileNotFound(_))
+        ));
+        let release = Edition::Latest.release().unwrap();
+        let edition_xml = temp_dir.path().join(release.filename_xml);
+        fs::write(&edition_xml, content).unwrap();
+        assert_eq!(ensure_data(&options, None).await.unwrap(), edition_xml);
+
+        // Unsupported extensions are rejected
+        let txt_path = temp_dir.path().join("custom.txt");
+        fs::write(&txt_path, content).unwrap();
+        let options = LoadOptions {
+            source: Some(txt_path),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ensure_data(&options, None).await,
+            Err(OewnError::InvalidArgument(_))
+        ));
+    }
+
+    /// Minimal HTTP/1.1 stand-in server for download tests.
+    ///
+    /// `respond` receives the request number and the requested Range start (if any)
+    /// and returns the raw response bytes; the connection is closed afterwards.
+    async fn spawn_test_server<F>(respond: F) -> String
+    where
+        F: Fn(usize, Option<u64>) -> Vec<u8> + Send + Sync + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut request_no = 0;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let range_start = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes="))
+                    .and_then(|range| range.trim().trim_end_matches('-').parse().ok());
+                let response = respond(request_no, range_start);
+                request_no += 1;
+                let _ = socket.write_all(&response).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+        format!("http://{}/english-wordnet.xml.gz", addr)
+    }
+
+    /// Builds a raw HTTP response whose Content-Length may exceed the body sent.
+    ///
+    /// Every response carries the same strong ETag, so partial downloads can be resumed.
+    fn http_response(
+        status: &str,
+        headers: &[String],
+        body: &[u8],
+        declared_len: usize,
+    ) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nETag: \"v1\"\r\nConnection: close\r\n",
+            status, declared_len
+        );
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str("\r\n");
+        let mut bytes = response.into_bytes();
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    fn test_policy() -> DownloadPolicy {
+        DownloadPolicy {
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_dropped_connection() {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let served = payload.clone();
+        let url =
+            spawn_test_server(
+                move |request_no, range_start| match (request_no, range_start) {
+                    // First attempt: the connection drops after 4000 of 10000 bytes
+                    (0, None) => http_response("200 OK", &[], &served[..4000], served.len()),
+                    (_, Some(start)) => {
+                        let start = start as usize;
+                        http_response(
+                            "206 Partial Content",
+                            &[format!(
+                                "Content-Range: bytes {}-{}/{}",
+                                start,
+                                served.len() - 1,
+                                served.len()
+                            )],
+                            &served[start..],
+                            served.len() - start,
+                        )
+                    }
+                    _ => http_response("500 Internal Server Error", &[], b"", 0),
+                },
+            )
+            .await;
+
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let dest = temp_dir.path().join("download.xml.gz");
+        let (reporter, mut updates) = crate::progress::create_progress_channel(1000);
+        let expected = {
+            let mut writer = HashingWriter::new(io::sink());
+            writer.write_all(&payload).unwrap();
+            writer.finish().unwrap()
+        };
+
+        let fetcher = HttpFetcher::new(&test_policy()).unwrap();
+        download_file(
+            &fetcher,
+            &url,
+            &dest,
+            Some(&expected),
+            &test_policy(),
+            Some(reporter),
+        )
+        .await
+        .expect("Download should succeed after resuming");
+
+        assert_eq!(fs::read(&dest).unwrap(), payload);
+        assert!(!partial_path(&dest).exists());
+        assert!(checksum_path(&dest).exists());
+
+        let mut messages = Vec::new();
+        while let Ok(update) = updates.try_recv() {
+            messages.extend(update.message);
+        }
+        assert!(messages.iter().any(|m| m.starts_with("Retry 1/3")));
+        assert!(messages.iter().any(|m| m == "Resuming at 4000 bytes"));
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_on_mismatched_range() {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let served = payload.clone();
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let url = spawn_test_server(move |_, range_start| {
+            seen.lock().unwrap().push(range_start);
+            match range_start {
+                // The returned range does not start where the partial data ends
+                Some(_) => http_response(
+                    "206 Partial Content",
+                    &[format!(
+                        "Content-Range: bytes 2000-{}/{}",
+                        served.len() - 1,
+                        served.len()
+                    )],
+                    &served[2000..],
+                    served.len() - 2000,
+                ),
+                None => http_response("200 OK", &[], &served, served.len()),
+            }
+        })
+        .await;
+
+        // A partial download left by another version of the file
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let dest = temp_dir.path().join("download.xml.gz");
+        let partial = partial_path(&dest);
+        fs::write(&partial, vec![0xffu8; 3000]).unwrap();
+        fs::write(validator_path(&partial), "\"v0\"").unwrap();
+
+        let fetcher = HttpFetcher::new(&test_policy()).unwrap();
+        download_file(&fetcher, &url, &dest, None, &test_policy(), None)
+            .await
+            .expect("Download should restart from scratch");
+
+        assert_eq!(fs::read(&dest).unwrap(), payload);
+        assert_eq!(*requests.lock().unwrap(), vec![Some(3000), None]);
+        assert!(!validator_path(&partial).exists());
+
+        // Partial data without a recorded validator is never resumed
+        let dest = temp_dir.path().join("unknown.xml.gz");
+        fs::write(partial_path(&dest), vec![0xffu8; 3000]).unwrap();
+        download_file(&fetcher, &url, &dest, None, &test_policy(), None)
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), payload);
+        assert_eq!(requests.lock().unwrap().last(), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn test_download_retry_policy() {
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        // Server errors are retried until the server recovers
+        let counter = requests.clone();
+        let url = spawn_test_server(move |request_no, _| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if request_no < 2 {
+                http_response("503 Service Unavailable", &[], b"", 0)
+            } else {
+                http_response("200 OK", &[], b"payload", 7)
+            }
+        })
+        .await;
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let dest = temp_dir.path().join("retried.xml.gz");
+        let fetcher = HttpFetcher::new(&test_policy()).unwrap();
+        download_file(&fetcher, &url, &dest, None, &test_policy(), None)
+            .await
+            .expect("Download should succeed after retries");
+        assert_eq!(fs::read(&dest).unwrap(), b"payload");
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // Client errors are not retried
+        let url = spawn_test_server(|_, _| http_response("404 Not Found", &[], b"", 0)).await;
+        let dest = temp_dir.path().join("missing.xml.gz");
+        let result = download_file(&fetcher, &url, &dest, None, &test_policy(), None).await;
+        assert!(matches!(result, Err(OewnError::Network(_))));
+
+        // Checksum mismatches discard the download
+        let url = spawn_test_server(|_, _| http_response("200 OK", &[], b"payload", 7)).await;
+        let dest = temp_dir.path().join("corrupt.xml.gz");
+        let result = download_file(
+            &fetcher,
+            &url,
+            &dest,
+            Some(&"0".repeat(64)),
+            &test_policy(),
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err(OewnError::ChecksumMismatch { .. })));
+        assert!(!dest.exists());
+        assert!(!partial_path(&dest).exists());
+    }
+
+    #[test]
+    fn test_download_policy_backoff() {
+        let policy = DownloadPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_edition_registry() {
+        assert_eq!(Edition::Latest.release().unwrap().edition, OEWN_VERSION);
+        assert_eq!("latest".parse::<Edition>().unwrap(), Edition::Latest);
+
+        let edition: Edition = "2021".parse().unwrap();
+        assert_eq!(edition, Edition::Year(2021));
+        let release = edition.release().unwrap();
+        assert_eq!(release.filename_gz, "english-wordnet-2021.xml.gz");
+        assert!(release.download_url.ends_with(release.filename_gz));
+
+        assert!(matches!(
+            "2018".parse::<Edition>(),
+            Err(OewnError::UnknownEdition(_))
+        ));
+        assert!(matches!(
+            "newest".parse::<Edition>(),
+            Err(OewnError::UnknownEdition(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_cached_file() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("data.xml");
+        fs::write(&path, "abc").unwrap();
+        let abc_sha256 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+        // Without a known checksum, the file is unverified and nothing is recorded
+        assert!(!verify_cached_file(&path, None).await.unwrap());
+        assert!(!checksum_path(&path).exists());
+
+        // A file matching its pinned checksum has it recorded
+        assert!(matches!(
+            verify_cached_file(&path, Some(&"0".repeat(64))).await,
+            Err(OewnError::ChecksumMismatch { .. })
+        ));
+        assert!(!checksum_path(&path).exists());
+        assert!(verify_cached_file(&path, Some(abc_sha256)).await.unwrap());
+        assert_eq!(
+            fs::read_to_string(checksum_path(&path)).unwrap().trim(),
+            abc_sha256
+        );
+
+        // A recorded checksum detects later truncation
+        assert!(verify_cached_file(&path, None).await.unwrap());
+        fs::write(&path, "ab").unwrap();
+        match verify_cached_file(&path, None).await {
+            Err(OewnError::ChecksumMismatch { expected, .. }) => assert_eq!(expected, abc_sha256),
+            other => panic!("Expected checksum mismatch, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_data_pinned_release_checksum() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let archive = temp_dir.path().join("archive.xml.gz");
+        create_dummy_gz(&archive, "<LexicalResource/>").unwrap();
+        let fetcher = std::sync::Arc::new(MemoryFetcher {
+            payload: fs::read(&archive).unwrap(),
+            ..Default::default()
+        });
+        let pinned = Release {
+            sha256_gz: Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            ..*Edition::Latest.release().unwrap()
+        };
+        let data_dir = temp_dir.path().join("cache");
+        let gz_path = data_dir.join(pinned.filename_gz);
+        let options = LoadOptions {
+            data_dir: Some(data_dir),
+            fetcher: Some(fetcher.clone()),
+            ..Default::default()
+        };
+
+        // A download that differs from the pinned checksum is rejected
+        assert!(matches!(
+            ensure_release_data(&options, &pinned, None).await,
+            Err(OewnError::ChecksumMismatch { .. })
+        ));
         assert!(!gz_path.exists());
-        assert!(!xml_path.exists());
-        // If we called ensure_data here, it *should* download and decompress.
-        // Manual execution and checking logs/files is the easiest way to verify this part.
 
-        // Cleanup is handled by scopeguard dropping temp_dir
+        // So is a cached archive, even with a matching sidecar
+        fs::copy(&archive, &gz_path).unwrap();
+        fs::write(checksum_path(&gz_path), sha256_file(&gz_path).unwrap()).unwrap();
+        let offline = LoadOptions {
+            offline: true,
+            ..options.clone()
+        };
+        assert!(matches!(
+            ensure_release_data(&offline, &pinned, None).await,
+            Err(OewnError::ChecksumMismatch { .. })
+        ));
+        assert!(gz_path.exists());
+
+        // The archive matching the pinned checksum is accepted
+        let matching = Release {
+            sha256_gz: Some(Box::leak(sha256_file(&archive).unwrap().into_boxed_str())),
+            ..pinned
+        };
+        assert_eq!(
+            ensure_release_data(&offline, &matching, None)
+                .await
+                .unwrap(),
+            gz_path
+        );
+        assert_eq!(fetcher.urls.lock().unwrap().len(), 1);
     }
 
     #[tokio::test]
-    async fn test_decompress_gz_basic() {
-        let _ = env_logger::builder().is_test(true).try_init();
+    async fn test_ensure_data_legacy_cache() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
-        let gz_path = temp_dir.path().join("test.xml.gz");
-        let xml_path = temp_dir.path().join("test.xml");
-        let content = "This is the test content.";
+        let release = Edition::Latest.release().unwrap();
+        let gz_path = temp_dir.path().join(release.filename_gz);
+        let xml_path = temp_dir.path().join(release.filename_xml);
+        create_dummy_gz(&gz_path, "<LexicalResource/>").unwrap();
+        fs::write(&xml_path, "<LexicalRes").unwrap();
+        let fetcher = std::sync::Arc::new(MemoryFetcher::default());
+        let offline = LoadOptions {
+            data_dir: Some(temp_dir.path().to_path_buf()),
+            offline: true,
+            ..Default::default()
+        };
+        let online = LoadOptions {
+            offline: false,
+            fetcher: Some(fetcher.clone()),
+            ..offline.clone()
+        };
 
-        create_dummy_gz(&gz_path, content).expect("Failed to create dummy GZ");
+        // An archive cached before checksums were recorded is used as it is, without
+        // recording a checksum nothing has verified; the XML file is skipped
+        assert_eq!(ensure_data(&offline, None).await.unwrap(), gz_path);
+        assert!(xml_path.exists());
+        assert!(!checksum_path(&gz_path).exists());
+
+        // Online, the archive is not fetched again, but the unverifiable XML is discarded
+        assert_eq!(ensure_data(&online, None).await.unwrap(), gz_path);
+        assert!(!xml_path.exists());
         assert!(gz_path.exists());
+        assert!(!checksum_path(&gz_path).exists());
+        assert!(fetcher.urls.lock().unwrap().is_empty());
+
+        // Once a checksum is known, the legacy archive is checked and it is recorded
+        let expected = LoadOptions {
+            expected_sha256: Some(sha256_file(&gz_path).unwrap()),
+            ..offline.clone()
+        };
+        assert_eq!(ensure_data(&expected, None).await.unwrap(), gz_path);
+        assert_eq!(
+            fs::read_to_string(checksum_path(&gz_path)).unwrap().trim(),
+            sha256_file(&gz_path).unwrap()
+        );
 
-        let result = decompress_gz(&gz_path, &xml_path, None).await;
-        assert!(result.is_ok(), "Decompression failed: {:?}", result.err());
+        // A cached file failing verification is kept when it cannot be fetched again
+        fs::write(&xml_path, "<LexicalResource/>").unwrap();
+        fs::write(checksum_path(&xml_path), "0".repeat(64)).unwrap();
+        assert_eq!(ensure_data(&offline, None).await.unwrap(), gz_path);
         assert!(xml_path.exists());
-        let decompressed_content =
-            fs::read_to_string(&xml_path).expect("Failed to read decompressed file");
-        assert_eq!(decompressed_content, content);
+        fs::write(checksum_path(&gz_path), "0".repeat(64)).unwrap();
+        assert!(matches!(
+            ensure_data(&offline, None).await,
+            Err(OewnError::ChecksumMismatch { .. })
+        ));
+        assert!(gz_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_data_requires_known_checksum() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let archive = temp_dir.path().join("archive.xml.gz");
+        create_dummy_gz(&archive, "<LexicalResource/>").unwrap();
+        let fetcher = std::sync::Arc::new(MemoryFetcher {
+            payload: fs::read(&archive).unwrap(),
+            ..Default::default()
+        });
+        let unpinned = Release {
+            sha256_gz: None,
+            ..*Edition::Latest.release().unwrap()
+        };
+        let options = LoadOptions {
+            data_dir: Some(temp_dir.path().join("cache")),
+            fetcher: Some(fetcher.clone()),
+            ..Default::default()
+        };
+
+        // Nothing is fetched without a checksum to verify it against
+        assert!(matches!(
+            ensure_release_data(&options, &unpinned, None).await,
+            Err(OewnError::UnverifiedDownload(_))
+        ));
+        assert!(fetcher.urls.lock().unwrap().is_empty());
+
+        // An expected checksum or an explicit opt-in allows the download
+        let expected = LoadOptions {
+            expected_sha256: Some(sha256_file(&archive).unwrap()),
+            ..options.clone()
+        };
+        ensure_release_data(&expected, &unpinned, None)
+            .await
+            .unwrap();
+        let allowed = LoadOptions {
+            data_dir: Some(temp_dir.path().join("allowed")),
+            allow_unverified: true,
+            ..options.clone()
+        };
+        ensure_release_data(&allowed, &unpinned, None)
+            .await
+            .unwrap();
+        assert_eq!(fetcher.urls.lock().unwrap().len(), 2);
     }
 }
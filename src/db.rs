@@ -9,13 +9,26 @@
 //!
 //! - `lexicons` - WordNet lexicon metadata
 //! - `lexical_entries` - Word entries with lemmas and part-of-speech information
-//! - `synsets` - Synonym sets (concepts) with ILI (Inter-Lingual Index) mappings
+//! - `lemma_tokens` - Later words of multi-word lemmas, for completing any word
+//! - `lemma_trigrams` - Character trigrams of lemmas, for finding similarly spelled ones
+//! - `taxonomy_depths` - Maximum hypernym depth of each part of speech, computed after loading
+//! - `information_content` - Propagated frequency and information content of each synset
+//! - `forms` - Variant written forms of lexical entries
+//! - `tags` - Categorized tags of lemmas and forms
+//! - `synsets` - Synonym sets (concepts) with ILI (Inter-Lingual Index) mappings, lexicographer files and metadata
 //! - `senses` - Links between lexical entries and synsets
+//! - `synset_members` - Member senses of synsets, in their listed order
+//! - `counts` - Corpus frequency counts of senses
+//! - `syntactic_behaviours` - Syntactic (subcategorization) frames of entries and senses
 //! - `definitions` - Textual definitions for synsets
 //! - `examples` - Usage examples for synsets
-//! - `pronunciations` - Pronunciation data for lexical entries
+//! - `pronunciations` - Pronunciation data for lemmas and forms
 //! - `sense_relations` - Semantic relationships between senses
 //! - `synset_relations` - Semantic relationships between synsets
+//! - `gloss_fts` - FTS5 full-text index over the definitions and examples of synsets
+//!
+//! Entries and senses carry a `position` column, and the remaining child rows are
+//! inserted in document order, so queries can reproduce the order of the source file.
 //!
 //! ## Usage
 //!
@@ -36,10 +49,19 @@
 //! ```
 
 use crate::error::{OewnError, Result};
-use crate::models::{LexicalResource, PartOfSpeech, SenseRelType, SynsetRelType};
+use crate::information_content::{FrequencySource, store_information_content};
+use crate::models::{
+    AdjPosition, LexicalEntry, LexicalResource, Lexicon, PartOfSpeech, Pronunciation, SenseRelType,
+    Synset, SynsetRelType, SyntacticBehaviour, Tag, parse_members,
+};
+use crate::parse::{LmfItem, LmfReader};
 use crate::progress::{ProgressReporter, ProgressUpdate, report_progress_non_blocking};
+use crate::taxonomy::store_taxonomy_depths;
 use log::{debug, info, warn};
-use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use rusqlite::{
+    Connection, OptionalExtension, Row, Statement, Transaction, params, params_from_iter,
+};
+use std::io::BufRead;
 use std::time::Instant;
 
 fn report_progress(reporter: &Option<ProgressReporter>, update: ProgressUpdate) {
@@ -48,7 +70,7 @@ fn report_progress(reporter: &Option<ProgressReporter>, update: ProgressUpdate)
     }
 }
 
-const SCHEMA_VERSION: u32 = 1;
+const SCHEMA_VERSION: u32 = 10;
 
 const CREATE_METADATA_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS metadata (
@@ -80,9 +102,65 @@ CREATE TABLE IF NOT EXISTS lexical_entries (
     lemma_written_form TEXT NOT NULL,
     lemma_written_form_lower TEXT NOT NULL, -- For case-insensitive search
     part_of_speech TEXT NOT NULL, -- Stored as TEXT (e.g., 'n', 'v')
+    lemma_script TEXT,
+    position INTEGER NOT NULL, -- Order of the entry in the source document
+    sense_count INTEGER NOT NULL DEFAULT 0,
+    tag_count INTEGER NOT NULL DEFAULT 0, -- Sum of the corpus counts of the entry's senses
     FOREIGN KEY (lexicon_id) REFERENCES lexicons(id)
 );";
 
+const CREATE_LEMMA_TOKENS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS lemma_tokens (
+    token TEXT NOT NULL, -- Lowercase non-initial word of a multi-word lemma
+    entry_id TEXT NOT NULL,
+    PRIMARY KEY (token, entry_id),
+    FOREIGN KEY (entry_id) REFERENCES lexical_entries(id)
+) WITHOUT ROWID;";
+
+const CREATE_LEMMA_TRIGRAMS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS lemma_trigrams (
+    trigram TEXT NOT NULL,
+    lemma_length INTEGER NOT NULL, -- In characters, so candidates can be narrowed by length
+    lemma TEXT NOT NULL, -- Lowercase lemma, shared by entries differing in case or POS
+    trigram_count INTEGER NOT NULL, -- Distinct trigrams of the lemma
+    PRIMARY KEY (trigram, lemma_length, lemma)
+) WITHOUT ROWID;";
+
+const CREATE_TAXONOMY_DEPTHS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS taxonomy_depths (
+    part_of_speech TEXT PRIMARY KEY,
+    max_depth INTEGER NOT NULL -- Longest hypernym path from a synset to a root, in edges
+);";
+
+const CREATE_INFORMATION_CONTENT_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS information_content (
+    synset_id TEXT PRIMARY KEY,
+    frequency REAL NOT NULL, -- Occurrences of the synset and its hyponyms
+    information_content REAL NOT NULL,
+    FOREIGN KEY (synset_id) REFERENCES synsets(id)
+);";
+
+const CREATE_FORMS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS forms (
+    entry_id TEXT NOT NULL,
+    form_index INTEGER NOT NULL, -- Position among the entry's forms
+    id TEXT,
+    written_form TEXT NOT NULL,
+    written_form_lower TEXT NOT NULL,
+    script TEXT,
+    PRIMARY KEY (entry_id, form_index),
+    FOREIGN KEY (entry_id) REFERENCES lexical_entries(id)
+);";
+
+const CREATE_TAGS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS tags (
+    entry_id TEXT NOT NULL,
+    form_index INTEGER, -- NULL for tags of the lemma
+    category TEXT NOT NULL,
+    text TEXT NOT NULL,
+    FOREIGN KEY (entry_id) REFERENCES lexical_entries(id)
+);";
+
 const CREATE_PRONUNCIATIONS_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS pronunciations (
     entry_id TEXT NOT NULL,
@@ -91,6 +169,7 @@ CREATE TABLE IF NOT EXISTS pronunciations (
     phonemic INTEGER NOT NULL, -- 0 for false, 1 for true
     audio TEXT,
     text TEXT NOT NULL,
+    form_index INTEGER, -- NULL for pronunciations of the lemma
     FOREIGN KEY (entry_id) REFERENCES lexical_entries(id)
 );";
 
@@ -100,7 +179,26 @@ CREATE TABLE IF NOT EXISTS synsets (
     lexicon_id TEXT NOT NULL,
     ili TEXT,
     part_of_speech TEXT NOT NULL,
-    -- 'members' from XML is implicitly handled by senses.entry_id + senses.synset_id
+    -- 'members' from XML is stored in order in synset_members
+    lexfile TEXT,
+    lexicalized INTEGER NOT NULL DEFAULT 1,
+    dc_contributor TEXT,
+    dc_coverage TEXT,
+    dc_creator TEXT,
+    dc_date TEXT,
+    dc_description TEXT,
+    dc_format TEXT,
+    dc_identifier TEXT,
+    dc_publisher TEXT,
+    dc_relation TEXT,
+    dc_rights TEXT,
+    dc_source TEXT,
+    dc_subject TEXT,
+    dc_title TEXT,
+    dc_type TEXT,
+    status TEXT,
+    note TEXT,
+    confidence_score TEXT,
     FOREIGN KEY (lexicon_id) REFERENCES lexicons(id)
 );";
 
@@ -109,10 +207,42 @@ CREATE TABLE IF NOT EXISTS senses (
     id TEXT PRIMARY KEY,
     entry_id TEXT NOT NULL,
     synset_id TEXT NOT NULL,
+    n INTEGER,
+    lexicalized INTEGER NOT NULL DEFAULT 1,
+    adjposition TEXT, -- 'a', 'ip' or 'p'
+    subcat TEXT, -- Space-separated syntactic behaviour IDs
+    position INTEGER NOT NULL, -- Order of the sense within its entry
     FOREIGN KEY (entry_id) REFERENCES lexical_entries(id),
     FOREIGN KEY (synset_id) REFERENCES synsets(id)
 );";
 
+const CREATE_SYNSET_MEMBERS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS synset_members (
+    synset_id TEXT NOT NULL,
+    sense_id TEXT NOT NULL,
+    position INTEGER NOT NULL, -- Order of the sense in the synset's 'members' list
+    PRIMARY KEY (synset_id, position),
+    FOREIGN KEY (synset_id) REFERENCES synsets(id)
+);";
+
+const CREATE_COUNTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS counts (
+    sense_id TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    FOREIGN KEY (sense_id) REFERENCES senses(id)
+);";
+
+const CREATE_SYNTACTIC_BEHAVIOURS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS syntactic_behaviours (
+    lexicon_id TEXT NOT NULL,
+    entry_id TEXT, -- NULL for frames declared at lexicon level
+    id TEXT,
+    subcategorization_frame TEXT NOT NULL,
+    senses TEXT, -- Space-separated sense IDs the frame is restricted to
+    FOREIGN KEY (lexicon_id) REFERENCES lexicons(id),
+    FOREIGN KEY (entry_id) REFERENCES lexical_entries(id)
+);";
+
 const CREATE_DEFINITIONS_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS definitions (
     synset_id TEXT NOT NULL,
@@ -157,6 +287,19 @@ CREATE TABLE IF NOT EXISTS synset_relations (
     FOREIGN KEY (target_synset_id) REFERENCES synsets(id)
 );";
 
+/// Full-text index over the glosses of each synset, one row per synset.
+///
+/// Definitions and examples are indexed in separate columns so that matches in
+/// definitions can be ranked above matches in examples.
+const CREATE_GLOSS_FTS_TABLE: &str = "
+CREATE VIRTUAL TABLE IF NOT EXISTS gloss_fts USING fts5(
+    synset_id UNINDEXED,
+    part_of_speech UNINDEXED,
+    definitions,
+    examples,
+    tokenize = 'porter unicode61'
+);";
+
 // Database performance optimization indices
 macro_rules! create_index {
     ($name:ident, $index_name:expr, $table:expr, $columns:expr) => {
@@ -214,6 +357,18 @@ create_index!(
     "synset_relations",
     "source_synset_id, rel_type"
 );
+create_index!(
+    CREATE_SENSE_REL_TARGET_INDEX,
+    "idx_sense_rel_target",
+    "sense_relations",
+    "target_sense_id"
+);
+create_index!(
+    CREATE_SYNSET_REL_TARGET_INDEX,
+    "idx_synset_rel_target",
+    "synset_relations",
+    "target_synset_id"
+);
 create_index!(
     CREATE_DEFINITION_SYNSET_INDEX,
     "idx_definition_synset",
@@ -232,42 +387,71 @@ create_index!(
     "pronunciations",
     "entry_id"
 );
+create_index!(
+    CREATE_FORM_WRITTEN_LOWER_INDEX,
+    "idx_form_written_lower",
+    "forms",
+    "written_form_lower"
+);
+create_index!(CREATE_TAG_ENTRY_INDEX, "idx_tag_entry", "tags", "entry_id");
+create_index!(
+    CREATE_COUNT_SENSE_INDEX,
+    "idx_count_sense",
+    "counts",
+    "sense_id"
+);
+create_index!(
+    CREATE_SYNTACTIC_BEHAVIOUR_ENTRY_INDEX,
+    "idx_syntactic_behaviour_entry",
+    "syntactic_behaviours",
+    "entry_id"
+);
+create_index!(
+    CREATE_SYNTACTIC_BEHAVIOUR_ID_INDEX,
+    "idx_syntactic_behaviour_id",
+    "syntactic_behaviours",
+    "id"
+);
+
+/// Tables holding WordNet data, in an order that respects foreign key constraints
+/// when deleting (referencing tables first).
+const DATA_TABLES: &[&str] = &[
+    "gloss_fts",
+    "lemma_tokens",
+    "lemma_trigrams",
+    "taxonomy_depths",
+    "information_content",
+    "synset_members",
+    "sense_relations",
+    "synset_relations",
+    "counts",
+    "syntactic_behaviours",
+    "definitions",
+    "ili_definitions",
+    "examples",
+    "pronunciations",
+    "tags",
+    "forms",
+    "senses",
+    "synsets",
+    "lexical_entries",
+    "lexicons",
+];
 
 /// Creates all necessary tables and indices in the database if they don't exist.
 /// Also checks and sets the schema version.
+///
+/// Databases created with an older schema version have their data tables dropped
+/// and recreated empty, so that callers repopulate them from the source data.
 pub fn initialize_database(conn: &mut Connection) -> Result<()> {
     info!(
         "Initializing database schema (version {})...",
         SCHEMA_VERSION
     );
     let tx = conn.transaction()?;
-
-    // Create tables
     tx.execute(CREATE_METADATA_TABLE, [])?;
-    tx.execute(CREATE_LEXICONS_TABLE, [])?;
-    tx.execute(CREATE_LEXICAL_ENTRIES_TABLE, [])?;
-    tx.execute(CREATE_PRONUNCIATIONS_TABLE, [])?;
-    tx.execute(CREATE_SYNSETS_TABLE, [])?;
-    tx.execute(CREATE_SENSES_TABLE, [])?;
-    tx.execute(CREATE_DEFINITIONS_TABLE, [])?;
-    tx.execute(CREATE_ILI_DEFINITIONS_TABLE, [])?;
-    tx.execute(CREATE_EXAMPLES_TABLE, [])?;
-    tx.execute(CREATE_SENSE_RELATIONS_TABLE, [])?;
-    tx.execute(CREATE_SYNSET_RELATIONS_TABLE, [])?;
-
-    // Create indices
-    tx.execute(CREATE_ENTRY_LEMMA_LOWER_INDEX, [])?;
-    tx.execute(CREATE_ENTRY_POS_INDEX, [])?;
-    tx.execute(CREATE_ENTRY_LEMMA_POS_INDEX, [])?;
-    tx.execute(CREATE_SENSE_SYNSET_INDEX, [])?;
-    tx.execute(CREATE_SENSE_ENTRY_INDEX, [])?;
-    tx.execute(CREATE_SENSE_REL_SOURCE_TYPE_INDEX, [])?;
-    tx.execute(CREATE_SYNSET_REL_SOURCE_TYPE_INDEX, [])?;
-    tx.execute(CREATE_DEFINITION_SYNSET_INDEX, [])?;
-    tx.execute(CREATE_EXAMPLE_SYNSET_INDEX, [])?;
-    tx.execute(CREATE_PRONUNCIATION_ENTRY_INDEX, [])?;
 
-    // Check schema version
+    // Check schema version before creating tables, as older tables may need rebuilding
     let existing_version_str: Option<String> = tx
         .query_row(
             "SELECT value FROM metadata WHERE key = 'schema_version'",
@@ -287,10 +471,12 @@ pub fn initialize_database(conn: &mut Connection) -> Result<()> {
             match existing_version.cmp(&SCHEMA_VERSION) {
                 std::cmp::Ordering::Less => {
                     warn!(
-                        "Database schema version ({}) is older than expected ({}). Migration needed.",
+                        "Database schema version ({}) is older than expected ({}). Rebuilding tables; the data will be reloaded.",
                         existing_version, SCHEMA_VERSION
                     );
-                    // For now, just update the version
+                    for table in DATA_TABLES {
+                        tx.execute(&format!("DROP TABLE IF EXISTS {}", table), [])?;
+                    }
                     tx.execute(
                         "UPDATE metadata SET value = ?1 WHERE key = 'schema_version'",
                         params![SCHEMA_VERSION.to_string()],
@@ -321,24 +507,441 @@ pub fn initialize_database(conn: &mut Connection) -> Result<()> {
         }
     }
 
+    // Create tables
+    tx.execute(CREATE_LEXICONS_TABLE, [])?;
+    tx.execute(CREATE_LEXICAL_ENTRIES_TABLE, [])?;
+    tx.execute(CREATE_LEMMA_TOKENS_TABLE, [])?;
+    tx.execute(CREATE_LEMMA_TRIGRAMS_TABLE, [])?;
+    tx.execute(CREATE_TAXONOMY_DEPTHS_TABLE, [])?;
+    tx.execute(CREATE_INFORMATION_CONTENT_TABLE, [])?;
+    tx.execute(CREATE_FORMS_TABLE, [])?;
+    tx.execute(CREATE_TAGS_TABLE, [])?;
+    tx.execute(CREATE_PRONUNCIATIONS_TABLE, [])?;
+    tx.execute(CREATE_SYNSETS_TABLE, [])?;
+    tx.execute(CREATE_SENSES_TABLE, [])?;
+    tx.execute(CREATE_SYNSET_MEMBERS_TABLE, [])?;
+    tx.execute(CREATE_COUNTS_TABLE, [])?;
+    tx.execute(CREATE_SYNTACTIC_BEHAVIOURS_TABLE, [])?;
+    tx.execute(CREATE_DEFINITIONS_TABLE, [])?;
+    tx.execute(CREATE_ILI_DEFINITIONS_TABLE, [])?;
+    tx.execute(CREATE_EXAMPLES_TABLE, [])?;
+    tx.execute(CREATE_SENSE_RELATIONS_TABLE, [])?;
+    tx.execute(CREATE_SYNSET_RELATIONS_TABLE, [])?;
+    tx.execute(CREATE_GLOSS_FTS_TABLE, [])?;
+
+    // Create indices
+    tx.execute(CREATE_ENTRY_LEMMA_LOWER_INDEX, [])?;
+    tx.execute(CREATE_ENTRY_POS_INDEX, [])?;
+    tx.execute(CREATE_ENTRY_LEMMA_POS_INDEX, [])?;
+    tx.execute(CREATE_SENSE_SYNSET_INDEX, [])?;
+    tx.execute(CREATE_SENSE_ENTRY_INDEX, [])?;
+    tx.execute(CREATE_SENSE_REL_SOURCE_TYPE_INDEX, [])?;
+    tx.execute(CREATE_SYNSET_REL_SOURCE_TYPE_INDEX, [])?;
+    tx.execute(CREATE_SENSE_REL_TARGET_INDEX, [])?;
+    tx.execute(CREATE_SYNSET_REL_TARGET_INDEX, [])?;
+    tx.execute(CREATE_DEFINITION_SYNSET_INDEX, [])?;
+    tx.execute(CREATE_EXAMPLE_SYNSET_INDEX, [])?;
+    tx.execute(CREATE_PRONUNCIATION_ENTRY_INDEX, [])?;
+    tx.execute(CREATE_FORM_WRITTEN_LOWER_INDEX, [])?;
+    tx.execute(CREATE_TAG_ENTRY_INDEX, [])?;
+    tx.execute(CREATE_COUNT_SENSE_INDEX, [])?;
+    tx.execute(CREATE_SYNTACTIC_BEHAVIOUR_ENTRY_INDEX, [])?;
+    tx.execute(CREATE_SYNTACTIC_BEHAVIOUR_ID_INDEX, [])?;
+
     tx.commit()?;
     info!("Database schema initialization complete.");
     Ok(())
 }
 
-// --- Data Population Function ---
+// --- Data Population Functions ---
 
-/// Populates the database with WordNet data from a parsed LexicalResource.
+/// Number of items inserted between progress reports.
+const PROGRESS_INTERVAL: u64 = 1000;
+
+/// Prepared insert statements for every table populated from LMF data.
 ///
-/// This function performs a comprehensive three-pass insertion strategy to handle
-/// foreign key dependencies correctly:
+/// Items are inserted in a single pass in document order, so e.g. senses are inserted
+/// before the synsets they refer to. Foreign key checks are therefore deferred until
+/// the transaction commits.
+struct Inserter<'conn> {
+    lexicon_stmt: Statement<'conn>,
+    entry_stmt: Statement<'conn>,
+    token_stmt: Statement<'conn>,
+    trigram_stmt: Statement<'conn>,
+    form_stmt: Statement<'conn>,
+    tag_stmt: Statement<'conn>,
+    synset_stmt: Statement<'conn>,
+    pron_stmt: Statement<'conn>,
+    sense_stmt: Statement<'conn>,
+    member_stmt: Statement<'conn>,
+    count_stmt: Statement<'conn>,
+    behaviour_stmt: Statement<'conn>,
+    def_stmt: Statement<'conn>,
+    ili_def_stmt: Statement<'conn>,
+    example_stmt: Statement<'conn>,
+    sense_rel_stmt: Statement<'conn>,
+    synset_rel_stmt: Statement<'conn>,
+    gloss_stmt: Statement<'conn>,
+    /// Lexicon that subsequent entries and synsets belong to
+    lexicon_id: Option<String>,
+    /// Entries inserted so far, which doubles as the document position of the next one
+    entries: u64,
+    synsets: u64,
+}
+
+impl<'conn> Inserter<'conn> {
+    fn new(tx: &'conn Transaction) -> Result<Self> {
+        tx.pragma_update(None, "defer_foreign_keys", "ON")?;
+        Ok(Inserter {
+            lexicon_stmt: tx.prepare(
+                "INSERT INTO lexicons (id, label, language, email, license, version, url, citation, logo, status, confidence_score, dc_publisher, dc_contributor)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            )?,
+            entry_stmt: tx.prepare(
+                "INSERT INTO lexical_entries (id, lexicon_id, lemma_written_form, lemma_written_form_lower, part_of_speech, lemma_script, position, sense_count, tag_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?,
+            token_stmt: tx.prepare(
+                "INSERT OR IGNORE INTO lemma_tokens (token, entry_id)
+                 VALUES (?1, ?2)",
+            )?,
+            trigram_stmt: tx.prepare(
+                "INSERT OR IGNORE INTO lemma_trigrams (trigram, lemma_length, lemma, trigram_count)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?,
+            form_stmt: tx.prepare(
+                "INSERT INTO forms (entry_id, form_index, id, written_form, written_form_lower, script)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?,
+            tag_stmt: tx.prepare(
+                "INSERT INTO tags (entry_id, form_index, category, text)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?,
+            synset_stmt: tx.prepare(
+                "INSERT INTO synsets (id, lexicon_id, ili, part_of_speech, lexfile, lexicalized,
+                    dc_contributor, dc_coverage, dc_creator, dc_date, dc_description, dc_format,
+                    dc_identifier, dc_publisher, dc_relation, dc_rights, dc_source, dc_subject,
+                    dc_title, dc_type, status, note, confidence_score)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+            )?,
+            pron_stmt: tx.prepare(
+                "INSERT INTO pronunciations (entry_id, variety, notation, phonemic, audio, text, form_index)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?,
+            sense_stmt: tx.prepare(
+                "INSERT INTO senses (id, entry_id, synset_id, n, lexicalized, adjposition, subcat, position)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?,
+            member_stmt: tx.prepare(
+                "INSERT INTO synset_members (synset_id, sense_id, position)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            count_stmt: tx.prepare(
+                "INSERT INTO counts (sense_id, value)
+                 VALUES (?1, ?2)",
+            )?,
+            behaviour_stmt: tx.prepare(
+                "INSERT INTO syntactic_behaviours (lexicon_id, entry_id, id, subcategorization_frame, senses)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?,
+            def_stmt: tx.prepare(
+                "INSERT INTO definitions (synset_id, text, dc_source)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            ili_def_stmt: tx.prepare(
+                "INSERT INTO ili_definitions (synset_id, text, dc_source)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            example_stmt: tx.prepare(
+                "INSERT INTO examples (synset_id, text, dc_source)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            sense_rel_stmt: tx.prepare(
+                "INSERT OR IGNORE INTO sense_relations (source_sense_id, target_sense_id, rel_type)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            synset_rel_stmt: tx.prepare(
+                "INSERT OR IGNORE INTO synset_relations (source_synset_id, target_synset_id, rel_type)
+                 VALUES (?1, ?2, ?3)",
+            )?,
+            gloss_stmt: tx.prepare(
+                "INSERT INTO gloss_fts (synset_id, part_of_speech, definitions, examples)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?,
+            lexicon_id: None,
+            entries: 0,
+            synsets: 0,
+        })
+    }
+
+    /// Inserts a streamed item, attributing entries and synsets to the last lexicon seen.
+    fn insert_item(&mut self, item: &LmfItem) -> Result<()> {
+        match item {
+            LmfItem::Lexicon(lexicon) => self.insert_lexicon(lexicon),
+            LmfItem::LexicalEntry(entry) => self.insert_entry(entry),
+            LmfItem::Synset(synset) => self.insert_synset(synset),
+            LmfItem::SyntacticBehaviour(behaviour) => {
+                self.insert_syntactic_behaviour(None, behaviour)
+            }
+        }
+    }
+
+    fn current_lexicon(lexicon_id: &Option<String>) -> Result<&str> {
+        lexicon_id.as_deref().ok_or_else(|| {
+            OewnError::ParseError("LMF entry found outside of a <Lexicon>".to_string())
+        })
+    }
+
+    fn insert_lexicon(&mut self, lexicon: &Lexicon) -> Result<()> {
+        debug!("Inserting lexicon: {}", lexicon.id);
+        self.lexicon_stmt.execute(params![
+            lexicon.id,
+            lexicon.label,
+            lexicon.language,
+            lexicon.email,
+            lexicon.license,
+            lexicon.version,
+            lexicon.url,
+            lexicon.citation,
+            lexicon.logo,
+            lexicon.status,
+            lexicon.confidence_score,
+            lexicon.dc_publisher,
+            lexicon.dc_contributor,
+        ])?;
+        self.lexicon_id = Some(lexicon.id.clone());
+        Ok(())
+    }
+
+    fn insert_entry(&mut self, entry: &LexicalEntry) -> Result<()> {
+        let lexicon_id = Self::current_lexicon(&self.lexicon_id)?;
+        let lemma_lower = entry.lemma.written_form.to_lowercase();
+        let tag_count: u64 = entry
+            .senses
+            .iter()
+            .flat_map(|sense| &sense.counts)
+            .map(|count| u64::from(count.value))
+            .sum();
+        self.entry_stmt.execute(params![
+            entry.id,
+            lexicon_id, // Foreign key
+            entry.lemma.written_form,
+            lemma_lower, // Store lowercase version
+            part_of_speech_to_string(entry.lemma.part_of_speech), // Store POS as string
+            entry.lemma.script,
+            self.entries,
+            entry.senses.len(),
+            tag_count,
+        ])?;
+
+        // The first word is already covered by the index on the whole lemma
+        for token in lemma_tokens(&lemma_lower).skip(1) {
+            self.token_stmt.execute(params![token, entry.id])?;
+        }
+        let lemma_trigrams = trigrams(&lemma_lower);
+        let lemma_length = lemma_lower.chars().count();
+        for trigram in &lemma_trigrams {
+            self.trigram_stmt.execute(params![
+                trigram,
+                lemma_length,
+                lemma_lower,
+                lemma_trigrams.len()
+            ])?;
+        }
+
+        // Pronunciations and tags of the lemma have no form index
+        for pron in entry
+            .pronunciations
+            .iter()
+            .chain(&entry.lemma.pronunciations)
+        {
+            self.insert_pronunciation(&entry.id, None, pron)?;
+        }
+        for tag in &entry.lemma.tags {
+            self.insert_tag(&entry.id, None, tag)?;
+        }
+
+        for (index, form) in entry.forms.iter().enumerate() {
+            self.form_stmt.execute(params![
+                entry.id, // Foreign key
+                index,
+                form.id,
+                form.written_form,
+                form.written_form.to_lowercase(),
+                form.script,
+            ])?;
+            for pron in &form.pronunciations {
+                self.insert_pronunciation(&entry.id, Some(index), pron)?;
+            }
+            for tag in &form.tags {
+                self.insert_tag(&entry.id, Some(index), tag)?;
+            }
+        }
+
+        for (position, sense) in entry.senses.iter().enumerate() {
+            self.sense_stmt.execute(params![
+                sense.id,
+                entry.id,     // Foreign key
+                sense.synset, // Foreign key (references synset.id)
+                sense.n,
+                sense.lexicalized,
+                sense.adjposition.map(adj_position_to_string),
+                sense.subcat,
+                position,
+            ])?;
+            for relation in &sense.sense_relations {
+                self.sense_rel_stmt.execute(params![
+                    sense.id,                                            // Source sense
+                    relation.target,                                     // Target sense ID
+                    sense_rel_type_to_string(&relation.resolved_type()), // Store type as string
+                ])?;
+            }
+            for count in &sense.counts {
+                self.count_stmt.execute(params![sense.id, count.value])?;
+            }
+        }
+
+        for behaviour in &entry.syntactic_behaviours {
+            self.insert_syntactic_behaviour(Some(&entry.id), behaviour)?;
+        }
+
+        self.entries += 1;
+        Ok(())
+    }
+
+    fn insert_pronunciation(
+        &mut self,
+        entry_id: &str,
+        form_index: Option<usize>,
+        pron: &Pronunciation,
+    ) -> Result<()> {
+        self.pron_stmt.execute(params![
+            entry_id, // Foreign key
+            pron.variety,
+            pron.notation,
+            pron.phonemic, // Store bool as integer
+            pron.audio,
+            pron.text,
+            form_index,
+        ])?;
+        Ok(())
+    }
+
+    /// Inserts a syntactic frame declared by an entry, or by the lexicon if `entry_id` is `None`.
+    fn insert_syntactic_behaviour(
+        &mut self,
+        entry_id: Option<&str>,
+        behaviour: &SyntacticBehaviour,
+    ) -> Result<()> {
+        let lexicon_id = Self::current_lexicon(&self.lexicon_id)?;
+        self.behaviour_stmt.execute(params![
+            lexicon_id,
+            entry_id,
+            behaviour.id,
+            behaviour.subcategorization_frame,
+            behaviour.senses,
+        ])?;
+        Ok(())
+    }
+
+    fn insert_tag(&mut self, entry_id: &str, form_index: Option<usize>, tag: &Tag) -> Result<()> {
+        self.tag_stmt
+            .execute(params![entry_id, form_index, tag.category, tag.text])?;
+        Ok(())
+    }
+
+    fn insert_synset(&mut self, synset: &Synset) -> Result<()> {
+        let lexicon_id = Self::current_lexicon(&self.lexicon_id)?;
+        let meta = &synset.metadata;
+        self.synset_stmt.execute(params![
+            synset.id,
+            lexicon_id, // Foreign key
+            synset.ili,
+            part_of_speech_to_string(synset.part_of_speech), // Store POS as string
+            synset.lexfile,
+            synset.lexicalized,
+            meta.dc_contributor,
+            meta.dc_coverage,
+            meta.dc_creator,
+            meta.dc_date,
+            meta.dc_description,
+            meta.dc_format,
+            meta.dc_identifier,
+            meta.dc_publisher,
+            meta.dc_relation,
+            meta.dc_rights,
+            meta.dc_source,
+            meta.dc_subject,
+            meta.dc_title,
+            meta.dc_type,
+            meta.status,
+            meta.note,
+            meta.confidence_score,
+        ])?;
+
+        for (position, sense_id) in parse_members(&synset.members).iter().enumerate() {
+            self.member_stmt
+                .execute(params![synset.id, sense_id, position])?;
+        }
+
+        for definition in &synset.definitions {
+            self.def_stmt.execute(params![
+                synset.id, // Foreign key
+                definition.text,
+                definition.dc_source,
+            ])?;
+        }
+
+        if let Some(ili_def) = &synset.ili_definition {
+            self.ili_def_stmt.execute(params![
+                synset.id, // Primary key
+                ili_def.text,
+                ili_def.dc_source,
+            ])?;
+        }
+
+        for example in &synset.examples {
+            self.example_stmt.execute(params![
+                synset.id, // Foreign key
+                example.text,
+                example.dc_source,
+            ])?;
+        }
+
+        for relation in &synset.synset_relations {
+            self.synset_rel_stmt.execute(params![
+                synset.id,                                     // Source synset
+                relation.target,                               // Target synset ID
+                synset_rel_type_to_string(&relation.rel_type), // Store type as string
+            ])?;
+        }
+
+        // Index the glosses for full-text search
+        let definitions: Vec<&str> = synset.definitions.iter().map(|d| d.text.trim()).collect();
+        let examples: Vec<&str> = synset.examples.iter().map(|e| e.text.trim()).collect();
+        self.gloss_stmt.execute(params![
+            synset.id,
+            part_of_speech_to_string(synset.part_of_speech),
+            definitions.join("; "),
+            examples.join("; "),
+        ])?;
+
+        self.synsets += 1;
+        Ok(())
+    }
+
+    fn summary(&self) -> String {
+        format!("{} entries, {} synsets", self.entries, self.synsets)
+    }
+}
+
+/// Populates the database with WordNet data from a parsed LexicalResource.
 ///
-/// 1. **Pass 1**: Insert core entities (lexicons, lexical entries, synsets)
-/// 2. **Pass 2**: Insert detail entities (pronunciations, senses, definitions, examples)
-/// 3. **Pass 3**: Insert relationship entities (sense relations, synset relations)
+/// Each lexicon, lexical entry and synset is inserted together with its details
+/// and relations using prepared statements, within a single transaction.
 ///
-/// The operation is performed within a single transaction for data integrity and
-/// uses prepared statements for optimal performance.
+/// Prefer [`populate_database_from_reader`] for full OEWN releases, which avoids
+/// holding the whole resource in memory.
 ///
 /// # Arguments
 ///
@@ -373,349 +976,144 @@ pub fn populate_database(
 ) -> Result<()> {
     info!("Populating database from parsed LexicalResource using prepared statements...");
     let start_time = Instant::now();
+    let stage = "Inserting WordNet data".to_string();
 
-    // --- Calculate Totals for Progress Reporting ---
-    let total_lexicons = resource.lexicons.len() as u64;
-    let total_entries = resource
-        .lexicons
-        .iter()
-        .map(|l| l.lexical_entries.len())
-        .sum::<usize>() as u64;
-    let total_synsets = resource
-        .lexicons
-        .iter()
-        .map(|l| l.synsets.len())
-        .sum::<usize>() as u64;
-    let total_pronunciations = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.lexical_entries)
-        .map(|e| e.pronunciations.len())
-        .sum::<usize>() as u64;
-    let total_senses = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.lexical_entries)
-        .map(|e| e.senses.len())
-        .sum::<usize>() as u64;
-    let total_definitions = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.synsets)
-        .map(|s| s.definitions.len())
-        .sum::<usize>() as u64;
-    let total_ili_definitions = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.synsets)
-        .filter(|s| s.ili_definition.is_some())
-        .count() as u64;
-    let total_examples = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.synsets)
-        .map(|s| s.examples.len())
-        .sum::<usize>() as u64;
-    let total_sense_relations = resource
+    let total_items = resource
         .lexicons
         .iter()
-        .flat_map(|l| &l.lexical_entries)
-        .flat_map(|e| &e.senses)
-        .map(|s| s.sense_relations.len())
+        .map(|l| 1 + l.lexical_entries.len() + l.synsets.len())
         .sum::<usize>() as u64;
-    let total_synset_relations = resource
-        .lexicons
-        .iter()
-        .flat_map(|l| &l.synsets)
-        .map(|s| s.synset_relations.len())
-        .sum::<usize>() as u64;
-
-    let pass1_total = total_lexicons + total_entries + total_synsets;
-    let pass2_total = total_pronunciations
-        + total_senses
-        + total_definitions
-        + total_ili_definitions
-        + total_examples;
-    let pass3_total = total_sense_relations + total_synset_relations;
-
-    // Helper closure to report progress
-    let maybe_report = |update: ProgressUpdate| {
-        report_progress(&reporter, update);
-    };
+    report_progress(
+        &reporter,
+        ProgressUpdate::new(stage.clone(), 0, Some(total_items), None),
+    );
 
     let tx = conn.transaction()?;
-
-    // --- Prepare Statements ---
-    let mut lexicon_stmt = tx.prepare(
-        "INSERT INTO lexicons (id, label, language, email, license, version, url, citation, logo, status, confidence_score, dc_publisher, dc_contributor)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-    )?;
-    let mut entry_stmt = tx.prepare(
-        "INSERT INTO lexical_entries (id, lexicon_id, lemma_written_form, lemma_written_form_lower, part_of_speech)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-    )?;
-    let mut synset_stmt = tx.prepare(
-        "INSERT INTO synsets (id, lexicon_id, ili, part_of_speech)
-         VALUES (?1, ?2, ?3, ?4)",
-    )?;
-    let mut pron_stmt = tx.prepare(
-        "INSERT INTO pronunciations (entry_id, variety, notation, phonemic, audio, text)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-    )?;
-    let mut sense_stmt = tx.prepare(
-        "INSERT INTO senses (id, entry_id, synset_id)
-         VALUES (?1, ?2, ?3)",
-    )?;
-    let mut def_stmt = tx.prepare(
-        "INSERT INTO definitions (synset_id, text, dc_source)
-         VALUES (?1, ?2, ?3)",
-    )?;
-    let mut ili_def_stmt = tx.prepare(
-        "INSERT INTO ili_definitions (synset_id, text, dc_source)
-         VALUES (?1, ?2, ?3)",
-    )?;
-    let mut example_stmt = tx.prepare(
-        "INSERT INTO examples (synset_id, text, dc_source)
-         VALUES (?1, ?2, ?3)",
-    )?;
-    let mut sense_rel_stmt = tx.prepare(
-        "INSERT OR IGNORE INTO sense_relations (source_sense_id, target_sense_id, rel_type)
-         VALUES (?1, ?2, ?3)",
-    )?;
-    let mut synset_rel_stmt = tx.prepare(
-        "INSERT OR IGNORE INTO synset_relations (source_synset_id, target_synset_id, rel_type)
-         VALUES (?1, ?2, ?3)",
-    )?;
-
-    // --- Pass 1: Insert core entities (Lexicons, Entries, Synsets) ---
-    info!("Population Pass 1: Inserting Lexicons, LexicalEntries, Synsets...");
-    maybe_report(ProgressUpdate::new(
-        "Pass 1/3: Inserting Core Entities".to_string(),
-        0,
-        Some(pass1_total),
-        None,
-    ));
-    let mut pass1_current = 0;
-
-    for lexicon in &resource.lexicons {
-        debug!("Pass 1: Inserting lexicon: {}", lexicon.id);
-        lexicon_stmt.execute(params![
-            lexicon.id,
-            lexicon.label,
-            lexicon.language,
-            lexicon.email,
-            lexicon.license,
-            lexicon.version,
-            lexicon.url,
-            lexicon.citation,
-            lexicon.logo,
-            lexicon.status,
-            lexicon.confidence_score,
-            lexicon.dc_publisher,
-            lexicon.dc_contributor,
-        ])?;
-        pass1_current += 1;
-        maybe_report(ProgressUpdate {
-            stage_description: "Pass 1/3: Inserting Core Entities".to_string(),
-            current_item: pass1_current,
-            total_items: Some(pass1_total),
-            message: Some(format!("Lexicon: {}", lexicon.id)),
-        });
-
-        for entry in &lexicon.lexical_entries {
-            entry_stmt.execute(params![
-                entry.id,
-                lexicon.id, // Foreign key
-                entry.lemma.written_form,
-                entry.lemma.written_form.to_lowercase(), // Store lowercase version
-                part_of_speech_to_string(entry.lemma.part_of_speech), // Store POS as string
-            ])?;
-            pass1_current += 1;
-            maybe_report(ProgressUpdate {
-                stage_description: "Pass 1/3: Inserting Core Entities".to_string(),
-                current_item: pass1_current,
-                total_items: Some(pass1_total),
-                message: Some(format!("Entry: {}", entry.id)),
-            });
-        }
-
-        for synset in &lexicon.synsets {
-            synset_stmt.execute(params![
-                synset.id,
-                lexicon.id, // Foreign key
-                synset.ili,
-                part_of_speech_to_string(synset.part_of_speech), // Store POS as string
-            ])?;
-            pass1_current += 1;
-            maybe_report(ProgressUpdate {
-                stage_description: "Pass 1/3: Inserting Core Entities".to_string(),
-                current_item: pass1_current,
-                total_items: Some(pass1_total),
-                message: Some(format!("Synset: {}", synset.id)),
-            });
-        }
-    }
-    info!("Pass 1 complete.");
-
-    // --- Pass 2: Insert entities referencing core entities (Senses, Definitions, Examples, Pronunciations) ---
-    info!("Population Pass 2: Inserting Senses, Definitions, Examples, Pronunciations...");
-    maybe_report(ProgressUpdate::new(
-        "Pass 2/3: Inserting Details".to_string(),
-        0,
-        Some(pass2_total),
-        None,
-    ));
-    let mut pass2_current = 0;
-
+    let mut inserter = Inserter::new(&tx)?;
+    let mut current_item = 0u64;
     for lexicon in &resource.lexicons {
+        inserter.insert_lexicon(lexicon)?;
+        current_item += 1;
         for entry in &lexicon.lexical_entries {
-            for pron in &entry.pronunciations {
-                pron_stmt.execute(params![
-                    entry.id, // Foreign key
-                    pron.variety,
-                    pron.notation,
-                    pron.phonemic, // Store bool as integer
-                    pron.audio,
-                    pron.text,
-                ])?;
-                pass2_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 2/3: Inserting Details".to_string(),
-                    current_item: pass2_current,
-                    total_items: Some(pass2_total),
-                    message: Some(format!("Pronunciation for Entry: {}", entry.id)),
-                });
-            }
-
-            for sense in &entry.senses {
-                sense_stmt.execute(params![
-                    sense.id,
-                    entry.id,     // Foreign key
-                    sense.synset, // Foreign key (references synset.id)
-                ])?;
-                pass2_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 2/3: Inserting Details".to_string(),
-                    current_item: pass2_current,
-                    total_items: Some(pass2_total),
-                    message: Some(format!("Sense: {}", sense.id)),
-                });
+            inserter.insert_entry(entry)?;
+            current_item += 1;
+            if current_item.is_multiple_of(PROGRESS_INTERVAL) {
+                report_progress(
+                    &reporter,
+                    ProgressUpdate::new(
+                        stage.clone(),
+                        current_item,
+                        Some(total_items),
+                        Some(inserter.summary()),
+                    ),
+                );
             }
         }
-
+        for behaviour in &lexicon.syntactic_behaviours {
+            inserter.insert_syntactic_behaviour(None, behaviour)?;
+        }
         for synset in &lexicon.synsets {
-            for definition in &synset.definitions {
-                def_stmt.execute(params![
-                    synset.id, // Foreign key
-                    definition.text,
-                    definition.dc_source,
-                ])?;
-                pass2_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 2/3: Inserting Details".to_string(),
-                    current_item: pass2_current,
-                    total_items: Some(pass2_total),
-                    message: Some(format!("Definition for Synset: {}", synset.id)),
-                });
-            }
-
-            if let Some(ili_def) = &synset.ili_definition {
-                ili_def_stmt.execute(params![
-                    synset.id, // Primary key
-                    ili_def.text,
-                    ili_def.dc_source,
-                ])?;
-                pass2_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 2/3: Inserting Details".to_string(),
-                    current_item: pass2_current,
-                    total_items: Some(pass2_total),
-                    message: Some(format!("ILI Definition for Synset: {}", synset.id)),
-                });
-            }
-
-            for example in &synset.examples {
-                example_stmt.execute(params![
-                    synset.id, // Foreign key
-                    example.text,
-                    example.dc_source,
-                ])?;
-                pass2_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 2/3: Inserting Details".to_string(),
-                    current_item: pass2_current,
-                    total_items: Some(pass2_total),
-                    message: Some(format!("Example for Synset: {}", synset.id)),
-                });
+            inserter.insert_synset(synset)?;
+            current_item += 1;
+            if current_item.is_multiple_of(PROGRESS_INTERVAL) {
+                report_progress(
+                    &reporter,
+                    ProgressUpdate::new(
+                        stage.clone(),
+                        current_item,
+                        Some(total_items),
+                        Some(inserter.summary()),
+                    ),
+                );
             }
         }
     }
-    info!("Pass 2 complete.");
-
-    // --- Pass 3: Insert relations (SenseRelations, SynsetRelations) ---
-    info!("Population Pass 3: Inserting SenseRelations, SynsetRelations...");
-    maybe_report(ProgressUpdate::new(
-        "Pass 3/3: Inserting Relations".to_string(),
-        0,
-        Some(pass3_total),
-        None,
-    ));
-    let mut pass3_current = 0;
+    let summary = inserter.summary();
+    drop(inserter);
+    store_taxonomy_depths(&tx)?;
+    store_information_content(&tx, &FrequencySource::SenseCounts)?;
+    tx.commit()?;
 
-    for lexicon in &resource.lexicons {
-        for entry in &lexicon.lexical_entries {
-            for sense in &entry.senses {
-                for relation in &sense.sense_relations {
-                    sense_rel_stmt.execute(params![
-                        sense.id,                                    // Source sense
-                        relation.target,                             // Target sense ID
-                        sense_rel_type_to_string(relation.rel_type), // Store type as string
-                    ])?;
-                    pass3_current += 1;
-                    maybe_report(ProgressUpdate {
-                        stage_description: "Pass 3/3: Inserting Relations".to_string(),
-                        current_item: pass3_current,
-                        total_items: Some(pass3_total),
-                        message: Some(format!("Sense Relation from: {}", sense.id)),
-                    });
-                }
-            }
-        }
-        for synset in &lexicon.synsets {
-            for relation in &synset.synset_relations {
-                synset_rel_stmt.execute(params![
-                    synset.id,                                    // Source synset
-                    relation.target,                              // Target synset ID
-                    synset_rel_type_to_string(relation.rel_type), // Store type as string
-                ])?;
-                pass3_current += 1;
-                maybe_report(ProgressUpdate {
-                    stage_description: "Pass 3/3: Inserting Relations".to_string(),
-                    current_item: pass3_current,
-                    total_items: Some(pass3_total),
-                    message: Some(format!("Synset Relation from: {}", synset.id)),
-                });
-            }
+    report_progress(
+        &reporter,
+        ProgressUpdate::new(stage, total_items, Some(total_items), Some(summary)),
+    );
+    info!(
+        "Database population complete. Took {:.2?}",
+        start_time.elapsed()
+    );
+    Ok(())
+}
+
+/// Populates the database with WordNet data streamed from an [`LmfReader`].
+///
+/// Items are inserted as soon as they are parsed, so memory use does not grow with
+/// the size of the document. Progress is reported in bytes consumed from the source
+/// file (see [`LmfReader::bytes_read`]), within a single transaction.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use oewn_rs::db::{initialize_database, populate_database_from_reader};
+/// use oewn_rs::parse::open_lmf;
+/// use rusqlite::Connection;
+/// use std::path::Path;
+///
+/// let mut conn = Connection::open("wordnet.db")?;
+/// initialize_database(&mut conn)?;
+/// let reader = open_lmf(Path::new("english-wordnet-2024.xml.gz"))?;
+/// populate_database_from_reader(&mut conn, reader, None)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn populate_database_from_reader<R: BufRead>(
+    conn: &mut Connection,
+    mut reader: LmfReader<R>,
+    reporter: Option<ProgressReporter>,
+) -> Result<()> {
+    info!("Populating database from streamed LMF data...");
+    let start_time = Instant::now();
+    let stage = "Loading WordNet data".to_string();
+    report_progress(
+        &reporter,
+        ProgressUpdate::new(stage.clone(), 0, reader.total_bytes(), None),
+    );
+
+    let tx = conn.transaction()?;
+    let mut inserter = Inserter::new(&tx)?;
+    let mut items = 0u64;
+    while let Some(item) = reader.next_item()? {
+        inserter.insert_item(&item)?;
+        items += 1;
+        if items.is_multiple_of(PROGRESS_INTERVAL) {
+            report_progress(
+                &reporter,
+                ProgressUpdate::new(
+                    stage.clone(),
+                    reader.bytes_read(),
+                    reader.total_bytes(),
+                    Some(inserter.summary()),
+                ),
+            );
         }
     }
-    info!("Pass 3 complete.");
-
-    // Drop statements explicitly before committing (optional, but good practice)
-    drop(lexicon_stmt);
-    drop(entry_stmt);
-    drop(synset_stmt);
-    drop(pron_stmt);
-    drop(sense_stmt);
-    drop(def_stmt);
-    drop(ili_def_stmt);
-    drop(example_stmt);
-    drop(sense_rel_stmt);
-    drop(synset_rel_stmt);
-
-    tx.commit()?; // Commit the transaction
+    let summary = inserter.summary();
+    drop(inserter);
+    store_taxonomy_depths(&tx)?;
+    store_information_content(&tx, &FrequencySource::SenseCounts)?;
+    tx.commit()?;
 
+    report_progress(
+        &reporter,
+        ProgressUpdate::new(
+            stage,
+            reader.bytes_read(),
+            reader.total_bytes(),
+            Some(summary.clone()),
+        ),
+    );
     info!(
-        "Database population complete. Took {:.2?}",
+        "Database population complete ({}). Took {:.2?}",
+        summary,
         start_time.elapsed()
     );
     Ok(())
@@ -742,21 +1140,81 @@ pub fn populate_database(
 pub fn clear_database_data(tx: &Transaction) -> Result<()> {
     info!("Clearing existing data from database tables...");
     // Order matters due to foreign key constraints (delete from referencing tables first)
-    tx.execute("DELETE FROM sense_relations", [])?;
-    tx.execute("DELETE FROM synset_relations", [])?;
-    tx.execute("DELETE FROM definitions", [])?;
-    tx.execute("DELETE FROM ili_definitions", [])?;
-    tx.execute("DELETE FROM examples", [])?;
-    tx.execute("DELETE FROM pronunciations", [])?;
-    tx.execute("DELETE FROM senses", [])?;
-    tx.execute("DELETE FROM synsets", [])?;
-    tx.execute("DELETE FROM lexical_entries", [])?;
-    tx.execute("DELETE FROM lexicons", [])?;
+    for table in DATA_TABLES {
+        tx.execute(&format!("DELETE FROM {}", table), [])?;
+    }
     // Don't delete from metadata table
     info!("Finished clearing data.");
     Ok(())
 }
 
+/// Splits a lemma into the words it can be completed from ("ice cream", "well-known").
+pub(crate) fn lemma_tokens(lemma: &str) -> impl Iterator<Item = &str> {
+    lemma
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|token| !token.is_empty())
+}
+
+/// Pads words on both sides before splitting them into trigrams, so that the first and
+/// last letters appear in as many trigrams as the others.
+const TRIGRAM_PADDING: &str = "$$";
+
+/// Splits a word into its distinct character trigrams, including padded ones at the
+/// start and end ("cat" gives "$$c", "$ca", "cat", "at$" and "t$$").
+pub(crate) fn trigrams(word: &str) -> Vec<String> {
+    let padded: Vec<char> = format!("{TRIGRAM_PADDING}{word}{TRIGRAM_PADDING}")
+        .chars()
+        .collect();
+    let mut trigrams: Vec<String> = Vec::new();
+    for window in padded.windows(3) {
+        let trigram: String = window.iter().collect();
+        if !trigrams.contains(&trigram) {
+            trigrams.push(trigram);
+        }
+    }
+    trigrams
+}
+
+/// Maximum number of values bound to one `IN` list by [`query_in_batches`].
+pub(crate) const IN_BATCH_SIZE: usize = 500;
+
+/// Runs a query over `values` in batches of [`IN_BATCH_SIZE`], with `{values}` in `sql`
+/// replaced by each batch's placeholders, and collects the mapped rows of all batches.
+///
+/// Rows are only ordered within a batch, so queries whose rows must be grouped should
+/// batch by the grouping key.
+pub(crate) fn query_in_batches<T>(
+    conn: &Connection,
+    sql: &str,
+    values: &[String],
+    mut map_row: impl FnMut(&Row) -> rusqlite::Result<T>,
+) -> Result<Vec<T>> {
+    let mut rows = Vec::new();
+    for batch in values.chunks(IN_BATCH_SIZE) {
+        let placeholders: Vec<String> = (1..=batch.len()).map(|i| format!("?{}", i)).collect();
+        let mut stmt = conn.prepare_cached(&sql.replace("{values}", &placeholders.join(", ")))?;
+        let batch_rows = stmt.query_map(params_from_iter(batch), &mut map_row)?;
+        for row in batch_rows {
+            rows.push(row?);
+        }
+    }
+    Ok(rows)
+}
+
+/// Returns the stored parts of speech that a part-of-speech filter matches.
+///
+/// Adjectives match adjective satellites as well; for other parts of speech both
+/// values are the same, so the pair can be bound to `part_of_speech IN (?, ?)`.
+pub(crate) fn stored_parts_of_speech(pos: PartOfSpeech) -> (&'static str, &'static str) {
+    match pos {
+        PartOfSpeech::A => ("a", "s"),
+        other => {
+            let pos_str = part_of_speech_to_string(other);
+            (pos_str, pos_str)
+        }
+    }
+}
+
 /// Converts a PartOfSpeech enum to its string representation for database storage.
 ///
 /// # Arguments
@@ -780,6 +1238,28 @@ pub(crate) fn part_of_speech_to_string(pos: PartOfSpeech) -> &'static str {
     }
 }
 
+/// Converts an AdjPosition enum to its string representation for database storage.
+pub(crate) fn adj_position_to_string(position: AdjPosition) -> &'static str {
+    match position {
+        AdjPosition::A => "a",
+        AdjPosition::Ip => "ip",
+        AdjPosition::P => "p",
+    }
+}
+
+/// Converts a string representation back to an AdjPosition enum.
+pub fn string_to_adj_position(s: &str) -> Result<AdjPosition> {
+    match s {
+        "a" => Ok(AdjPosition::A),
+        "ip" => Ok(AdjPosition::Ip),
+        "p" => Ok(AdjPosition::P),
+        _ => Err(OewnError::ParseError(format!(
+            "Invalid AdjPosition string in DB: {}",
+            s
+        ))),
+    }
+}
+
 /// Converts a string representation back to a PartOfSpeech enum.
 ///
 /// # Arguments
@@ -815,11 +1295,9 @@ pub fn string_to_part_of_speech(s: &str) -> Result<PartOfSpeech> {
 ///
 /// # Returns
 ///
-/// * `String` - Lowercase string representation of the relation type
-pub(crate) fn sense_rel_type_to_string(rel_type: SenseRelType) -> String {
-    // Use serde_plain for simple enum-string mapping if preferred,
-    // or implement manually like this:
-    format!("{:?}", rel_type).to_lowercase() // Simple debug representation to lowercase
+/// * `&str` - The WN-LMF `relType` string, verbatim for unrecognized types
+pub(crate) fn sense_rel_type_to_string(rel_type: &SenseRelType) -> &str {
+    rel_type.as_str()
 }
 
 /// Converts a string representation back to a SenseRelType enum.
@@ -830,23 +1308,9 @@ pub(crate) fn sense_rel_type_to_string(rel_type: SenseRelType) -> String {
 ///
 /// # Returns
 ///
-/// * `Result<SenseRelType>` - Parsed enum value, defaulting to `Other` for unknown types
+/// * `Result<SenseRelType>` - Parsed enum value, `Unrecognized` for types outside the vocabulary
 pub fn string_to_sense_rel_type(s: &str) -> Result<SenseRelType> {
-    // This requires a more robust mapping, potentially using serde or match
-    match s {
-        "antonym" => Ok(SenseRelType::Antonym),
-        "also" => Ok(SenseRelType::Also),
-        "participle" => Ok(SenseRelType::Participle),
-        "pertainym" => Ok(SenseRelType::Pertainym),
-        "derivation" => Ok(SenseRelType::Derivation),
-        "domain_topic" => Ok(SenseRelType::DomainTopic),
-        "domain_member_topic" => Ok(SenseRelType::DomainMemberTopic),
-        "domain_region" => Ok(SenseRelType::DomainRegion),
-        "domain_member_region" => Ok(SenseRelType::DomainMemberRegion),
-        "exemplifies" => Ok(SenseRelType::Exemplifies),
-        "is_exemplified_by" => Ok(SenseRelType::IsExemplifiedBy),
-        _ => Ok(SenseRelType::Other), // Default to Other for unknown/new types
-    }
+    Ok(SenseRelType::from(s))
 }
 
 /// Converts a SynsetRelType enum to its string representation for database storage.
@@ -857,9 +1321,9 @@ pub fn string_to_sense_rel_type(s: &str) -> Result<SenseRelType> {
 ///
 /// # Returns
 ///
-/// * `String` - Lowercase string representation of the relation type
-pub(crate) fn synset_rel_type_to_string(rel_type: SynsetRelType) -> String {
-    format!("{:?}", rel_type).to_lowercase() // Simple debug representation to lowercase
+/// * `&str` - The WN-LMF `relType` string, verbatim for unrecognized types
+pub(crate) fn synset_rel_type_to_string(rel_type: &SynsetRelType) -> &str {
+    rel_type.as_str()
 }
 
 /// Converts a string representation back to a SynsetRelType enum.
@@ -870,30 +1334,7 @@ pub(crate) fn synset_rel_type_to_string(rel_type: SynsetRelType) -> String {
 ///
 /// # Returns
 ///
-/// * `Result<SynsetRelType>` - Parsed enum value, defaulting to `Unknown` for unrecognized types
+/// * `Result<SynsetRelType>` - Parsed enum value, `Unrecognized` for types outside the vocabulary
 pub fn string_to_synset_rel_type(s: &str) -> Result<SynsetRelType> {
-    // This needs a comprehensive match or serde mapping
-    match s {
-        "hypernym" => Ok(SynsetRelType::Hypernym),
-        "hyponym" => Ok(SynsetRelType::Hyponym),
-        "instance_hypernym" => Ok(SynsetRelType::InstanceHypernym),
-        "instance_hyponym" => Ok(SynsetRelType::InstanceHyponym),
-        "mero_member" => Ok(SynsetRelType::MeroMember),
-        "mero_part" => Ok(SynsetRelType::MeroPart),
-        "mero_substance" => Ok(SynsetRelType::MeroSubstance),
-        "holo_member" => Ok(SynsetRelType::HoloMember),
-        "holo_part" => Ok(SynsetRelType::HoloPart),
-        "holo_substance" => Ok(SynsetRelType::HoloSubstance),
-        "entails" => Ok(SynsetRelType::Entails),
-        "causes" => Ok(SynsetRelType::Causes),
-        "similar" => Ok(SynsetRelType::Similar),
-        "attribute" => Ok(SynsetRelType::Attribute),
-        "domain_region" => Ok(SynsetRelType::DomainRegion),
-        "domain_topic" => Ok(SynsetRelType::DomainTopic),
-        "has_domain_region" => Ok(SynsetRelType::HasDomainRegion),
-        "has_domain_topic" => Ok(SynsetRelType::HasDomainTopic),
-        "exemplifies" => Ok(SynsetRelType::Exemplifies),
-        "is_exemplified_by" => Ok(SynsetRelType::IsExemplifiedBy),
-        _ => Ok(SynsetRelType::Unknown), // Default to Unknown
-    }
+    Ok(SynsetRelType::from(s))
 }
@@ -38,10 +38,30 @@ pub enum OewnError {
     #[error("Required data file not found: {0}")]
     DataFileNotFound(String),
 
+    /// Data is not available locally and offline mode forbids downloading it
+    #[error("Data unavailable in offline mode: {0}")]
+    OfflineDataUnavailable(String),
+
+    /// A download was refused because no SHA-256 is known to verify it against
+    #[error("Download cannot be verified: {0}")]
+    UnverifiedDownload(String),
+
+    /// A downloaded or cached file did not match its expected SHA-256 checksum
+    #[error("Checksum mismatch for {path}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+
     /// Generic parsing error for non-XML data
     #[error("Failed to parse data: {0}")]
     ParseError(String),
 
+    /// The requested OEWN edition is not in the release registry
+    #[error("Unknown OEWN edition: {0}")]
+    UnknownEdition(String),
+
     /// Synset lookup failed
     #[error("Synset not found: {0}")]
     SynsetNotFound(String),
@@ -50,6 +70,10 @@ pub enum OewnError {
     #[error("Lexical entry not found: {0}")]
     LexicalEntryNotFound(String),
 
+    /// Sense lookup failed
+    #[error("Sense not found: {0}")]
+    SenseNotFound(String),
+
     /// WordNet data has not been loaded
     #[error("WordNet data not loaded")]
     NotLoaded,
@@ -77,3 +101,10 @@ impl From<quick_xml::DeError> for OewnError {
         OewnError::XmlParse(err)
     }
 }
+
+/// Reader errors from the streaming parser are reported as XML parse errors.
+impl From<quick_xml::Error> for OewnError {
+    fn from(err: quick_xml::Error) -> Self {
+        OewnError::XmlParse(err.into())
+    }
+}
@@ -0,0 +1,325 @@
+//! Pluggable transports for fetching OEWN data.
+//!
+//! Downloads go through the [`Fetcher`] trait so the HTTP client can be swapped for a
+//! local-file or in-process implementation, e.g. to read from an artifact mirror
+//! mounted on disk or to test `data::ensure_data` without network access.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use oewn_rs::fetch::LocalFileFetcher;
+//! use oewn_rs::LoadOptions;
+//! use std::sync::Arc;
+//!
+//! // Serve release archives from a directory instead of the network
+//! let options = LoadOptions {
+//!     fetcher: Some(Arc::new(LocalFileFetcher::new(Some("/mnt/oewn-mirror".into())))),
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::data::DownloadPolicy;
+use crate::error::{OewnError, Result};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use log::{info, warn};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, ETAG, HeaderMap, IF_RANGE, LAST_MODIFIED, RANGE};
+use std::fmt;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Chunk size used when streaming local files.
+const LOCAL_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The body and metadata of a fetched resource.
+pub struct FetchResponse {
+    /// Whether the body continues from the requested offset. If `false`, the body
+    /// starts at byte 0 and any partially downloaded data must be discarded.
+    pub resumed: bool,
+    /// Number of bytes the body will yield, if known
+    pub content_length: Option<u64>,
+    /// Opaque token identifying this version of the resource (an ETag or
+    /// Last-Modified date for HTTP), passed back when resuming so that data from a
+    /// different version is never appended to
+    pub validator: Option<String>,
+    /// The response body as a stream of chunks
+    pub body: BoxStream<'static, Result<Bytes>>,
+}
+
+impl fmt::Debug for FetchResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FetchResponse")
+            .field("resumed", &self.resumed)
+            .field("content_length", &self.content_length)
+            .field("validator", &self.validator)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A transport that can fetch a resource by URL.
+pub trait Fetcher: Send + Sync + fmt::Debug {
+    /// Fetches `url`, continuing from byte `offset` if the transport supports it.
+    ///
+    /// `validator` is the [`FetchResponse::validator`] of the response the partial data
+    /// came from. Implementations must only resume if the resource is unchanged, and
+    /// should return the whole resource with `resumed: false` if it has changed or they
+    /// cannot resume.
+    fn fetch<'a>(
+        &'a self,
+        url: &'a str,
+        offset: u64,
+        validator: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<FetchResponse>>;
+}
+
+/// Fetches resources over HTTP(S) using `reqwest`, resuming with Range requests.
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpFetcher {
+    /// Creates a fetcher whose client uses the policy's connect and read timeouts.
+    pub fn new(policy: &DownloadPolicy) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(policy.connect_timeout)
+            .read_timeout(policy.read_timeout)
+            .build()?;
+        Ok(HttpFetcher { client })
+    }
+
+    /// Creates a fetcher from an existing client.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        HttpFetcher { client }
+    }
+
+    async fn fetch_http(
+        &self,
+        url: &str,
+        offset: u64,
+        validator: Option<&str>,
+    ) -> Result<FetchResponse> {
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+            if let Some(validator) = validator {
+                request = request.header(IF_RANGE, validator);
+            }
+        }
+        let response = request.send().await?;
+
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial data is already as long as the resource (or longer, if it is stale)
+            let complete_size = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("bytes */"))
+                .and_then(|v| v.parse::<u64>().ok());
+            if complete_size == Some(offset) && validator.is_some() {
+                info!("Partial download is already complete.");
+                return Ok(FetchResponse {
+                    resumed: true,
+                    content_length: Some(0),
+                    validator: validator.map(str::to_string),
+                    body: stream::empty().boxed(),
+                });
+            }
+            warn!("Server rejected resume request; restarting download from scratch.");
+            return Box::pin(self.fetch_http(url, 0, None)).await;
+        }
+
+        let response = response.error_for_status()?;
+        let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        if resumed && content_range_start(response.headers()) != Some(offset) {
+            warn!(
+                "Server returned a range not starting at byte {}; restarting download from scratch.",
+                offset
+            );
+            return Box::pin(self.fetch_http(url, 0, None)).await;
+        }
+        if offset > 0 && !resumed {
+            info!("Server does not support resuming; restarting download from scratch.");
+        }
+        Ok(FetchResponse {
+            resumed,
+            content_length: response.content_length(),
+            validator: http_validator(response.headers()),
+            body: response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(OewnError::from))
+                .boxed(),
+        })
+    }
+}
+
+/// Start of the byte range in a `Content-Range: bytes <start>-<end>/<size>` header.
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .trim()
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The validator to send in `If-Range`: a strong ETag, or the Last-Modified date.
+///
+/// Weak ETags cannot be used for range requests.
+fn http_validator(headers: &HeaderMap) -> Option<String> {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(str::to_string)
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch<'a>(
+        &'a self,
+        url: &'a str,
+        offset: u64,
+        validator: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<FetchResponse>> {
+        Box::pin(self.fetch_http(url, offset, validator))
+    }
+}
+
+/// Fetches resources from the local filesystem.
+///
+/// URLs may be `file://` URLs or plain paths. If a root directory is set, only the
+/// last path segment of the URL is used and resolved inside that directory, so a
+/// directory of release archives can stand in for a remote mirror.
+#[derive(Debug, Clone, Default)]
+pub struct LocalFileFetcher {
+    root: Option<PathBuf>,
+}
+
+impl LocalFileFetcher {
+    /// Creates a fetcher, optionally resolving file names inside `root`.
+    pub fn new(root: Option<PathBuf>) -> Self {
+        LocalFileFetcher { root }
+    }
+
+    /// Maps a URL to the local path it refers to.
+    fn resolve(&self, url: &str) -> PathBuf {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        match &self.root {
+            Some(root) => {
+                let file_name = path.rsplit('/').next().unwrap_or(path);
+                root.join(file_name)
+            }
+            None => PathBuf::from(path),
+        }
+    }
+
+    async fn fetch_file(
+        &self,
+        url: &str,
+        offset: u64,
+        validator: Option<&str>,
+    ) -> Result<FetchResponse> {
+        let path = self.resolve(url);
+        let mut file = tokio::fs::File::open(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                OewnError::DataFileNotFound(format!("{:?}", path))
+            } else {
+                OewnError::Io(e)
+            }
+        })?;
+        let metadata = file.metadata().await?;
+        let len = metadata.len();
+        let current = file_validator(&metadata);
+        let offset = if validator.is_some_and(|v| Some(v) == current.as_deref()) {
+            offset.min(len)
+        } else {
+            0
+        };
+        file.seek(SeekFrom::Start(offset)).await?;
+
+        let body = stream::try_unfold(file, |mut file| async move {
+            let mut buf = vec![0u8; LOCAL_CHUNK_SIZE];
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+            buf.truncate(n);
+            Ok(Some((Bytes::from(buf), file)))
+        });
+
+        Ok(FetchResponse {
+            resumed: offset > 0,
+            content_length: Some(len - offset),
+            validator: current,
+            body: body.boxed(),
+        })
+    }
+}
+
+/// Identifies a version of a local file by its size and modification time.
+fn file_validator(metadata: &std::fs::Metadata) -> Option<String> {
+    let modified = metadata.modified().ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(format!("{}-{}", metadata.len(), since_epoch.as_nanos()))
+}
+
+impl Fetcher for LocalFileFetcher {
+    fn fetch<'a>(
+        &'a self,
+        url: &'a str,
+        offset: u64,
+        validator: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<FetchResponse>> {
+        Box::pin(self.fetch_file(url, offset, validator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_local_file_fetcher_resumes() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let payload: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(temp_dir.path().join("archive.xml.gz"), &payload).unwrap();
+
+        let fetcher = LocalFileFetcher::new(Some(temp_dir.path().to_path_buf()));
+        let url = "https://mirror.example.com/oewn/archive.xml.gz";
+
+        let response = fetcher.fetch(url, 0, None).await.unwrap();
+        assert!(!response.resumed);
+        assert_eq!(response.content_length, Some(payload.len() as u64));
+        let validator = response
+            .validator
+            .clone()
+            .expect("Local files have a validator");
+        let chunks: Vec<Bytes> = response.body.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), payload);
+
+        let response = fetcher.fetch(url, 150_000, Some(&validator)).await.unwrap();
+        assert!(response.resumed);
+        let chunks: Vec<Bytes> = response.body.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), &payload[150_000..]);
+
+        // Partial data from another version of the file is never resumed
+        let response = fetcher.fetch(url, 150_000, Some("stale")).await.unwrap();
+        assert!(!response.resumed);
+        assert_eq!(response.content_length, Some(payload.len() as u64));
+
+        let missing = fetcher
+            .fetch("file:///nowhere/missing.xml.gz", 0, None)
+            .await;
+        assert!(matches!(missing, Err(OewnError::DataFileNotFound(_))));
+    }
+}
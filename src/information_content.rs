@@ -0,0 +1,323 @@
+//! Information content (IC) of synsets, for IC-based similarity measures.
+//!
+//! The IC of a synset is `-ln(freq(s) / total)`, where `freq(s)` counts the occurrences
+//! of the synset and of all of its hyponyms, and `total` the occurrences counted for all
+//! synsets with the same part of speech. Occurrences come from a [`FrequencySource`], are
+//! propagated up the hypernym hierarchy (once to each distinct ancestor) and are stored
+//! with the resulting IC in the `information_content` table.
+//!
+//! As in NLTK, every synset starts with [`SMOOTHING`] occurrences, but each total only
+//! starts with it once rather than once per synset, and adjective satellites share the
+//! total of adjectives.
+//!
+//! When data is loaded, the table is filled from the `Count`s of senses; it can be
+//! replaced with [`crate::WordNet::load_information_content`].
+
+use crate::error::{OewnError, Result};
+use crate::taxonomy::{all_hypernym_edges, hypernym_map};
+use log::{info, warn};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+/// Occurrences added to every synset, so that unseen synsets have a finite IC.
+pub const SMOOTHING: f64 = 1.0;
+
+/// Where synset occurrence counts come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrequencySource {
+    /// The `Count` (sense-tagged corpus frequency) data of the loaded senses
+    SenseCounts,
+    /// A word frequency list with a word and its count on each line, separated by
+    /// whitespace (e.g. `ice cream 120`). A word's count is shared equally among the
+    /// synsets of all of its senses. Empty lines and lines starting with `#` are skipped.
+    WordFrequencies(PathBuf),
+    /// An NLTK-style IC file such as `ic-brown.dat`, with lines `<offset><pos> <count>`
+    /// followed by `ROOT` for roots. Its counts are already propagated. Offsets are
+    /// matched to synsets with IDs of the form `<lexicon>-<offset>-<pos>`, as in OEWN.
+    NltkIc(PathBuf),
+}
+
+/// Computes the information content of synsets from `source` and stores it, replacing
+/// the previous values. Returns the number of synsets with an information content.
+pub(crate) fn store_information_content(
+    conn: &Connection,
+    source: &FrequencySource,
+) -> Result<usize> {
+    let rows = match source {
+        FrequencySource::SenseCounts => {
+            let mut stmt = conn.prepare(
+                "SELECT s.synset_id, SUM(c.value)
+                 FROM counts c
+                 JOIN senses s ON s.id = c.sense_id
+                 GROUP BY s.synset_id",
+            )?;
+            let counts = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            propagate_counts(conn, &counts)?
+        }
+        FrequencySource::WordFrequencies(path) => {
+            let counts = read_word_frequencies(conn, path)?;
+            propagate_counts(conn, &counts)?
+        }
+        FrequencySource::NltkIc(path) => read_nltk_ic(conn, path)?,
+    };
+
+    conn.execute("DELETE FROM information_content", [])?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO information_content (synset_id, frequency, information_content)
+         VALUES (?1, ?2, ?3)",
+    )?;
+    for (synset_id, frequency, ic) in &rows {
+        stmt.execute(params![synset_id, frequency, ic])?;
+    }
+    info!("Stored information content for {} synsets", rows.len());
+    Ok(rows.len())
+}
+
+/// Returns the stored information content of a synset.
+pub(crate) fn information_content(conn: &Connection, synset_id: &str) -> Result<Option<f64>> {
+    let ic = conn
+        .prepare_cached("SELECT information_content FROM information_content WHERE synset_id = ?1")?
+        .query_row(params![synset_id], |row| row.get(0))
+        .optional()?;
+    Ok(ic)
+}
+
+/// Propagates per-synset counts to all hypernyms and computes the IC of every synset,
+/// as `(synset, frequency, information content)`.
+fn propagate_counts(
+    conn: &Connection,
+    counts: &[(String, f64)],
+) -> Result<Vec<(String, f64, f64)>> {
+    let mut stmt = conn.prepare("SELECT id, part_of_speech FROM synsets ORDER BY rowid")?;
+    let synsets = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let edges = all_hypernym_edges(conn)?;
+    let hypernyms = hypernym_map(&edges);
+
+    let mut frequencies: HashMap<&str, f64> = HashMap::new();
+    let mut parts_of_speech: HashMap<&str, &str> = HashMap::new();
+    let mut totals: HashMap<&str, f64> = HashMap::new();
+    for (id, pos) in &synsets {
+        frequencies.insert(id, SMOOTHING);
+        parts_of_speech.insert(id, total_part_of_speech(pos));
+        totals.entry(total_part_of_speech(pos)).or_insert(SMOOTHING);
+    }
+
+    for (synset_id, count) in counts {
+        let Some(pos) = parts_of_speech.get(synset_id.as_str()) else {
+            warn!("Ignoring count for unknown synset {}", synset_id);
+            continue;
+        };
+        *totals.entry(pos).or_default() += count;
+        // The synset and each distinct ancestor receive the count once
+        let mut visited: Vec<&str> = vec![synset_id];
+        let mut index = 0;
+        while let Some(current) = visited.get(index).copied() {
+            if let Some(frequency) = frequencies.get_mut(current) {
+                *frequency += count;
+            }
+            for hypernym in hypernyms.get(current).into_iter().flatten() {
+                if !visited.contains(hypernym) {
+                    visited.push(hypernym);
+                }
+            }
+            index += 1;
+        }
+    }
+
+    Ok(synsets
+        .iter()
+        .map(|(id, pos)| {
+            let frequency = frequencies[id.as_str()];
+            (
+                id.clone(),
+                frequency,
+                -(frequency / totals[total_part_of_speech(pos)]).ln(),
+            )
+        })
+        .collect())
+}
+
+/// The part of speech whose total a synset's occurrences count towards: adjective
+/// satellites count as adjectives.
+fn total_part_of_speech(pos: &str) -> &str {
+    if pos == "s" { "a" } else { pos }
+}
+
+/// Parses one line of a word frequency list into a lowercase word and its count.
+///
+/// Underscores in words are read as spaces, as in WordNet's own multi-word lemmas.
+fn parse_word_frequency(line: &str) -> Result<Option<(String, f64)>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let parsed = line
+        .rsplit_once(char::is_whitespace)
+        .and_then(|(word, count)| Some((word.trim(), count.parse::<f64>().ok()?)))
+        .filter(|(word, count)| !word.is_empty() && *count >= 0.0);
+    match parsed {
+        Some((word, count)) => Ok(Some((word.replace('_', " ").to_lowercase(), count))),
+        None => Err(OewnError::ParseError(format!(
+            "Invalid word frequency line: '{}'",
+            line
+        ))),
+    }
+}
+
+/// Reads a word frequency list and shares each word's count among its synsets.
+fn read_word_frequencies(conn: &Connection, path: &PathBuf) -> Result<Vec<(String, f64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT s.synset_id
+         FROM lexical_entries le
+         JOIN senses s ON s.entry_id = le.id
+         WHERE le.lemma_written_form_lower = ?1",
+    )?;
+    let mut counts: Vec<(String, f64)> = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let Some((word, count)) = parse_word_frequency(&line?)? else {
+            continue;
+        };
+        let synset_ids = stmt
+            .query_map(params![word], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let share = count / synset_ids.len().max(1) as f64;
+        counts.extend(synset_ids.into_iter().map(|id| (id, share)));
+    }
+    Ok(counts)
+}
+
+/// A parsed line of an NLTK IC file.
+#[derive(Debug, PartialEq)]
+struct NltkIcLine {
+    offset: u64,
+    pos: char,
+    count: f64,
+    root: bool,
+}
+
+/// Parses one line of an NLTK IC file; the `wnver::` header yields `None`.
+fn parse_nltk_ic_line(line: &str) -> Result<Option<NltkIcLine>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with("wnver") {
+        return Ok(None);
+    }
+    let invalid = || OewnError::ParseError(format!("Invalid IC file line: '{}'", line));
+    let mut fields = line.split_whitespace();
+    let synset = fields.next().ok_or_else(invalid)?;
+    let pos = synset
+        .chars()
+        .last()
+        .filter(char::is_ascii_alphabetic)
+        .ok_or_else(invalid)?;
+    let offset = synset[..synset.len() - 1].parse().map_err(|_| invalid())?;
+    let count = fields
+        .next()
+        .and_then(|count| count.parse().ok())
+        .ok_or_else(invalid)?;
+    let root = fields.next() == Some("ROOT");
+    Ok(Some(NltkIcLine {
+        offset,
+        pos,
+        count,
+        root,
+    }))
+}
+
+/// Reads an NLTK IC file, computing each synset's IC against the total of its roots.
+fn read_nltk_ic(conn: &Connection, path: &PathBuf) -> Result<Vec<(String, f64, f64)>> {
+    let mut stmt = conn.prepare("SELECT id FROM lexicons ORDER BY rowid")?;
+    let lexicon_ids = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let mut exists_stmt = conn.prepare("SELECT 1 FROM synsets WHERE id = ?1")?;
+
+    let mut frequencies: Vec<(String, char, f64)> = Vec::new();
+    let mut totals: HashMap<char, f64> = HashMap::new();
+    let mut unmatched = 0;
+    for line in BufReader::new(File::open(path)?).lines() {
+        let Some(parsed) = parse_nltk_ic_line(&line?)? else {
+            continue;
+        };
+        if parsed.root {
+            *totals.entry(parsed.pos).or_default() += parsed.count;
+        }
+        let mut synset_id = None;
+        for lexicon_id in &lexicon_ids {
+            let id = format!("{}-{:08}-{}", lexicon_id, parsed.offset, parsed.pos);
+            if exists_stmt.exists(params![id])? {
+                synset_id = Some(id);
+                break;
+            }
+        }
+        match synset_id {
+            Some(id) => frequencies.push((id, parsed.pos, parsed.count)),
+            None => unmatched += 1,
+        }
+    }
+    if unmatched > 0 {
+        warn!("{} synsets of the IC file were not found", unmatched);
+    }
+
+    Ok(frequencies
+        .into_iter()
+        .filter_map(|(id, pos, frequency)| {
+            let total = totals.get(&pos).copied().filter(|total| *total > 0.0)?;
+            (frequency > 0.0).then(|| (id, frequency, -(frequency / total).ln()))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_word_frequency() {
+        assert_eq!(
+            parse_word_frequency("Ice_cream 12").unwrap(),
+            Some(("ice cream".to_string(), 12.0))
+        );
+        assert_eq!(
+            parse_word_frequency("ice cream\t3.5").unwrap(),
+            Some(("ice cream".to_string(), 3.5))
+        );
+        assert_eq!(parse_word_frequency("  ").unwrap(), None);
+        assert_eq!(parse_word_frequency("# comment").unwrap(), None);
+        assert!(parse_word_frequency("dog").is_err());
+        assert!(parse_word_frequency("dog many").is_err());
+    }
+
+    #[test]
+    fn test_parse_nltk_ic_line() {
+        assert_eq!(
+            parse_nltk_ic_line("wnver::eOS9lXC6GvMWznF1wkZofDdtbBU").unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_nltk_ic_line("1740n 1915712.0 ROOT").unwrap(),
+            Some(NltkIcLine {
+                offset: 1740,
+                pos: 'n',
+                count: 1915712.0,
+                root: true
+            })
+        );
+        assert_eq!(
+            parse_nltk_ic_line("2084071n 4.0")
+                .unwrap()
+                .map(|line| line.root),
+            Some(false)
+        );
+        assert!(parse_nltk_ic_line("n 4.0").is_err());
+        assert!(parse_nltk_ic_line("1740n").is_err());
+    }
+}
@@ -18,30 +18,48 @@
 //! }
 //! ```
 
+pub mod batch;
 pub mod data;
 pub mod db;
 pub mod error;
+pub mod fetch;
+pub mod information_content;
 pub mod models;
+pub mod morphy;
 pub mod parse;
 pub mod progress;
-
-use crate::db::{string_to_part_of_speech, string_to_sense_rel_type, string_to_synset_rel_type};
-use crate::progress::{
-    ProgressCallback, ProgressUpdate, create_progress_channel, report_progress_non_blocking,
+pub mod search;
+pub mod semantic_path;
+pub mod similarity;
+pub mod taxonomy;
+pub mod traversal;
+pub mod wsd;
+
+use crate::db::{
+    stored_parts_of_speech, string_to_part_of_speech, string_to_sense_rel_type,
+    string_to_synset_rel_type,
 };
+use crate::progress::{ProgressCallback, create_progress_channel};
+pub use batch::WordLookup;
+pub use data::Edition;
 use directories_next::ProjectDirs;
 pub use error::{OewnError, Result};
+pub use information_content::FrequencySource;
 use log::{debug, error, info, warn};
 pub use models::{
-    Definition, Example, ILIDefinition, Lemma, LexicalEntry, LexicalResource, Lexicon,
-    PartOfSpeech, Pronunciation, Sense, SenseRelType, SenseRelation, Synset, SynsetRelType,
-    SynsetRelation,
+    AdjPosition, Count, Definition, Example, Form, ILIDefinition, Lemma, LexicalEntry,
+    LexicalResource, Lexicon, Metadata, PartOfSpeech, Pronunciation, Sense, SenseRelType,
+    SenseRelation, Synset, SynsetRelType, SynsetRelation, SyntacticBehaviour, Tag,
 };
-use parse::parse_lmf;
 use rusqlite::{Connection, OpenFlags, OptionalExtension, Row, params};
+pub use search::{Completion, GlossMatch, LemmaMatch, LemmaMatches, PatternMode, Suggestion};
+pub use semantic_path::{PathEdge, RelationType, SemanticPath};
+pub use similarity::SimilarityMeasure;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+pub use traversal::{Traversal, TraversalOrder, TraversalStep};
+pub use wsd::{LeskOptions, ScoredSynset};
 
 const DB_CACHE_SIZE: i32 = -64000; // 64MB
 
@@ -64,6 +82,7 @@ fn open_db_connection(path: &Path) -> Result<Connection> {
     conn.pragma_update(None, "journal_mode", "WAL")?;
     conn.pragma_update(None, "cache_size", DB_CACHE_SIZE)?;
     conn.pragma_update(None, "synchronous", "NORMAL")?;
+    search::register_functions(&conn)?;
 
     Ok(conn)
 }
@@ -77,6 +96,51 @@ pub struct LoadOptions {
     /// Force reloading data from XML and repopulating the database,
     /// ignoring any existing database content.
     pub force_reload: bool,
+    /// OEWN edition to load. Defaults to the latest known edition.
+    /// Each edition is cached in its own archive and database file.
+    pub edition: data::Edition,
+    /// Optional explicit LMF source: a `.xml`, `.xml.gz` or `.zip` file, or a
+    /// directory containing the edition's cached files. The network is never
+    /// used when this is set.
+    pub source: Option<PathBuf>,
+    /// Never access the network. Loading fails with
+    /// `OewnError::OfflineDataUnavailable` if the data is not available locally.
+    pub offline: bool,
+    /// Expected SHA-256 of the downloaded GZ archive, overriding the checksum
+    /// pinned in the release registry.
+    pub expected_sha256: Option<String>,
+    /// Download the archive even if neither `expected_sha256` nor the release registry
+    /// gives a SHA-256 to verify it against. Without this, such downloads fail with
+    /// `OewnError::UnverifiedDownload`.
+    pub allow_unverified: bool,
+    /// Connect/read timeouts and retry policy used when downloading data.
+    pub download: data::DownloadPolicy,
+    /// Overrides the download URL (or, if it ends with `/`, the mirror base URL)
+    /// of the release archive. Falls back to the `OEWN_DOWNLOAD_URL` environment variable.
+    pub download_url: Option<String>,
+    /// Transport used to fetch data. Defaults to an HTTP fetcher built from `download`.
+    pub fetcher: Option<Arc<dyn fetch::Fetcher>>,
+    /// Directory for cached archives, XML files and the default database.
+    /// Defaults to the user's data directory.
+    pub data_dir: Option<PathBuf>,
+}
+
+impl LoadOptions {
+    /// Resolves the database file these options refer to: `db_path` if set,
+    /// otherwise the edition's database inside `data_dir` or the default data directory.
+    pub fn resolve_db_path(&self) -> Result<PathBuf> {
+        match (&self.db_path, &self.data_dir) {
+            (Some(path), _) => {
+                info!("Using provided database path: {:?}", path);
+                Ok(path.clone())
+            }
+            (None, Some(dir)) => {
+                let release = self.edition.release()?;
+                Ok(data::get_data_dir(Some(dir))?.join(format!("oewn-{}.db", release.edition)))
+            }
+            (None, None) => WordNet::get_default_db_path_for(self.edition),
+        }
+    }
 }
 
 /// The main WordNet interface providing access to lexical data.
@@ -87,6 +151,8 @@ pub struct LoadOptions {
 #[derive(Clone)]
 pub struct WordNet {
     conn: Arc<Mutex<Connection>>,
+    /// Whether `lookup_entries` also returns entries of the word's base forms
+    morphy_fallback: bool,
 }
 impl WordNet {
     /// Loads the WordNet data using default options.
@@ -144,18 +210,11 @@ impl WordNet {
             })
         });
 
-        let report = |update: ProgressUpdate| {
-            report_progress_non_blocking(&progress_reporter, update);
-        };
-
-        let db_path = match options.db_path {
-            Some(path) => {
-                info!("Using provided database path: {:?}", path);
-                path
-            }
-            None => Self::get_default_db_path()?,
-        };
-        info!("Using database path: {:?}", db_path);
+        let db_path = options.resolve_db_path()?;
+        info!(
+            "Using database path: {:?} (edition {})",
+            db_path, options.edition
+        );
 
         let db_exists = db_path.exists();
         let mut needs_population = !db_exists || options.force_reload;
@@ -186,56 +245,55 @@ impl WordNet {
                 info!("Database needs population (first run or empty).");
             }
 
-            let xml_path = data::ensure_data(Some(progress_reporter.clone())).await?;
-            info!("OEWN XML data available at: {:?}", xml_path);
-
-            let read_stage = "Reading XML file".to_string();
-            info!("Reading XML file: {:?}", xml_path);
-            report(ProgressUpdate::new(read_stage.clone(), 0, None, None));
-            let xml_content = tokio::fs::read_to_string(&xml_path).await?;
-            report(ProgressUpdate {
-                stage_description: read_stage,
-                current_item: 1,
-                total_items: Some(1),
-                message: Some("Read complete.".to_string()),
-            });
-
-            let parse_stage = "Parsing XML data".to_string();
-            info!("Parsing XML data...");
-            report(ProgressUpdate::new(parse_stage.clone(), 0, None, None));
-            let resource = parse_lmf(xml_content).await?;
-            report(ProgressUpdate {
-                stage_description: parse_stage,
-                current_item: 1,
-                total_items: Some(1),
-                message: Some("Parsing complete.".to_string()),
-            });
-
-            db::populate_database(&mut conn, resource, Some(progress_reporter.clone()))?;
+            let data_path = data::ensure_data(&options, Some(progress_reporter.clone())).await?;
+            info!("OEWN data available at: {:?}", data_path);
+
+            // Parse and insert on a blocking thread, streaming straight from the file
+            let reader = parse::open_lmf(&data_path)?;
+            let reporter = progress_reporter.clone();
+            conn = tokio::task::spawn_blocking(move || -> Result<Connection> {
+                db::populate_database_from_reader(&mut conn, reader, Some(reporter))?;
+                Ok(conn)
+            })
+            .await??;
         } else {
             info!("Using existing populated database: {:?}", db_path);
         }
 
         Ok(WordNet {
             conn: Arc::new(Mutex::new(conn)),
+            morphy_fallback: false,
         })
     }
 
-    /// Gets the default path for the SQLite database file.
+    /// Gets the default path for the SQLite database file of the latest edition.
     ///
-    /// The path is constructed using the user's data directory and includes
-    /// the OEWN version in the filename for proper versioning.
+    /// This is a convenience method that calls `get_default_db_path_for(Edition::Latest)`.
     ///
     /// # Returns
     ///
     /// A `Result` containing the default database path or an error if the
     /// data directory cannot be determined.
     pub fn get_default_db_path() -> Result<PathBuf> {
+        Self::get_default_db_path_for(data::Edition::Latest)
+    }
+
+    /// Gets the default path for the SQLite database file of a specific edition.
+    ///
+    /// The path is constructed using the user's data directory and includes
+    /// the OEWN edition in the filename, so each edition gets its own database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the database path, or an error if the edition is
+    /// unknown or the data directory cannot be determined.
+    pub fn get_default_db_path_for(edition: data::Edition) -> Result<PathBuf> {
+        let release = edition.release()?;
         let project_dirs = ProjectDirs::from("org", "OewnRs", data::OEWN_SUBDIR)
             .ok_or(OewnError::DataDirNotFound)?;
         let data_dir = project_dirs.data_dir();
         fs::create_dir_all(data_dir)?;
-        let db_filename = format!("oewn-{}.db", data::OEWN_VERSION);
+        let db_filename = format!("oewn-{}.db", release.edition);
         Ok(data_dir.join(db_filename))
     }
 
@@ -314,15 +372,68 @@ impl WordNet {
         Self::clear_database(None)
     }
 
+    /// Enables or disables the morphological fallback of [`WordNet::lookup_entries`].
+    ///
+    /// The fallback is off by default, so lookups only match lemmas exactly.
+    pub fn with_morphy_fallback(mut self, enabled: bool) -> Self {
+        self.morphy_fallback = enabled;
+        self
+    }
+
+    /// Reduces an inflected word to the base forms that exist as lemmas.
+    ///
+    /// Uses WordNet's detachment rules plus the variant forms in the data as exceptions;
+    /// see the [`morphy`] module. The word itself is included if it is a lemma.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The (possibly inflected) word
+    /// * `pos` - Part of speech to analyse the word as; all are tried if `None`
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the base forms with their part of speech, or an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::{WordNet, PartOfSpeech};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for (lemma, pos) in wn.lemmatize("geese", Some(PartOfSpeech::N))? {
+    ///         println!("{} ({})", lemma, pos);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lemmatize(
+        &self,
+        word: &str,
+        pos: Option<PartOfSpeech>,
+    ) -> Result<Vec<(String, PartOfSpeech)>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        morphy::lemmatize(&conn_guard, word, pos)
+    }
+
     /// Looks up lexical entries for a given lemma with optional part-of-speech filtering.
     ///
     /// This method performs an optimized single-query lookup that includes pronunciations,
     /// senses, and sense relations. The lookup is case-insensitive.
     ///
+    /// If the morphological fallback is enabled (see [`WordNet::with_morphy_fallback`]),
+    /// entries of the word's base forms follow the exact matches, so e.g. "geese" also
+    /// finds "goose" and "running" also finds "run".
+    ///
     /// # Arguments
     ///
     /// * `lemma` - The word to look up
-    /// * `pos_filter` - Optional part-of-speech filter to narrow results
+    /// * `pos_filter` - Optional part-of-speech filter to narrow results (adjectives
+    ///   include satellites)
     ///
     /// # Returns
     ///
@@ -356,155 +467,287 @@ impl WordNet {
             .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
         let conn = &*conn_guard;
 
-        let pos_str_filter = pos_filter.map(db::part_of_speech_to_string);
+        let mut final_entries = self.fetch_entries_by_lemma(conn, lemma, pos_filter)?;
+        if self.morphy_fallback {
+            for (base_form, pos) in morphy::lemmatize(conn, lemma, pos_filter)? {
+                for entry in self.fetch_entries_by_lemma(conn, &base_form, Some(pos))? {
+                    if !final_entries.iter().any(|e| e.id == entry.id) {
+                        final_entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        if final_entries.is_empty() {
+            debug!(
+                "No entries found for lemma '{}', pos_filter: {:?}",
+                lemma, pos_filter
+            );
+        }
+        Ok(final_entries)
+    }
+
+    /// Looks up many words at once.
+    ///
+    /// Each word is looked up as in [`WordNet::lookup_entries`], including the morphological
+    /// fallback if enabled, and its result also holds the synsets of its senses. All words
+    /// are resolved together in a few set-based queries rather than several queries per
+    /// word, so this is much faster for long word lists.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to look up
+    /// * `pos_filter` - Optional part-of-speech filter applied to every word
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing one [`WordLookup`] per word, in the order of `words`, with no
+    /// entries for words that are not found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for lookup in wn.lookup_many(&["cat", "dog", "xyzzy"], None)? {
+    ///         if !lookup.is_found() {
+    ///             println!("{}: not found", lookup.word);
+    ///         }
+    ///         for synset in &lookup.synsets {
+    ///             println!("{}: {:?}", lookup.word, synset.definitions.first());
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lookup_many(
+        &self,
+        words: &[&str],
+        pos_filter: Option<PartOfSpeech>,
+    ) -> Result<Vec<WordLookup>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        batch::lookup_many(&conn_guard, words, pos_filter, self.morphy_fallback)
+    }
+
+    /// Internal helper to fetch the entries whose lemma matches exactly (ignoring case).
+    fn fetch_entries_by_lemma(
+        &self,
+        conn: &Connection,
+        lemma: &str,
+        pos_filter: Option<PartOfSpeech>,
+    ) -> Result<Vec<LexicalEntry>> {
+        let (first_pos, second_pos) = match pos_filter.map(stored_parts_of_speech) {
+            Some((first, second)) => (Some(first), Some(second)),
+            None => (None, None),
+        };
 
         // Single query joining entries, pronunciations, senses, and sense relations
         // Filtered by lemma (lowercase) and optionally POS
         let sql = "
             SELECT
-                le.id AS entry_id, le.lemma_written_form, le.part_of_speech,
+                le.id AS entry_id, le.lemma_written_form, le.part_of_speech, le.lemma_script,
                 p.variety, p.notation, p.phonemic, p.audio, p.text AS pron_text,
                 s.id AS sense_id, s.synset_id,
-                sr.target_sense_id AS sense_rel_target, sr.rel_type AS sense_rel_type
+                sr.target_sense_id AS sense_rel_target, sr.rel_type AS sense_rel_type,
+                s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+                s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
             FROM lexical_entries le
-            LEFT JOIN pronunciations p ON le.id = p.entry_id
+            LEFT JOIN pronunciations p ON le.id = p.entry_id AND p.form_index IS NULL
             LEFT JOIN senses s ON le.id = s.entry_id
             LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id -- Note: JOINING sense_relations on s.id, not le.id
-            WHERE le.lemma_written_form_lower = ?1 AND (?2 IS NULL OR le.part_of_speech = ?2)
-            ORDER BY le.id, s.id -- Order is crucial for grouping
+            WHERE le.lemma_written_form_lower = ?1 AND (?2 IS NULL OR le.part_of_speech IN (?2, ?3))
+            ORDER BY le.position, s.n IS NULL, s.n, s.position, sr.rowid, p.rowid -- Source order, grouped by entry and sense
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        // Use HashMaps to aggregate data during iteration
-        let mut entries_map: std::collections::HashMap<String, LexicalEntry> =
-            std::collections::HashMap::new();
-        // Temporary storage for multi-valued fields, keyed by entry_id
-        let mut temp_pronunciations: std::collections::HashMap<
-            String,
-            std::collections::HashSet<Pronunciation>,
-        > = std::collections::HashMap::new();
-        // Temporary storage for senses, keyed by entry_id, then sense_id
-        let mut temp_senses: std::collections::HashMap<
-            String,
-            std::collections::HashMap<String, Sense>,
-        > = std::collections::HashMap::new();
-
-        let rows_iter = stmt.query_map(params![lemma.to_lowercase(), pos_str_filter], |row| {
-            // --- Extract Core Entry Data ---
-            let entry_id: String = row.get("entry_id")?;
-            let lemma_written_form: String = row.get("lemma_written_form")?;
-            let part_of_speech_str: String = row.get("part_of_speech")?;
-            let part_of_speech = string_to_part_of_speech(&part_of_speech_str).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    2,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?;
-
-            // --- Create or Get Entry in Map ---
-            // Prefix with _ as the variable itself isn't used directly after insertion/retrieval
-            let _entry_map_entry =
-                entries_map
-                    .entry(entry_id.clone())
-                    .or_insert_with(|| LexicalEntry {
-                        id: entry_id.clone(),
-                        lemma: Lemma {
-                            written_form: lemma_written_form,
-                            part_of_speech,
-                        },
-                        pronunciations: Vec::new(),
-                        senses: Vec::new(),
-                    });
-
-            // --- Extract and Store Pronunciation ---
-            let pron_variety: Option<String> = row.get("variety")?;
-            if let Some(var) = pron_variety {
-                let pron_notation: Option<String> = row.get("notation")?;
-                let pron_phonemic_int: Option<i64> = row.get("phonemic")?;
-                let pron_audio: Option<String> = row.get("audio")?;
-                let pron_text: Option<String> = row.get("pron_text")?;
-                if let (Some(ph_int), Some(txt)) = (pron_phonemic_int, pron_text) {
-                    temp_pronunciations
-                        .entry(entry_id.clone())
-                        .or_default()
-                        .insert(Pronunciation {
-                            variety: var,
-                            notation: pron_notation,
-                            phonemic: ph_int != 0,
-                            audio: pron_audio,
-                            text: txt,
-                        });
-                } else {
-                    warn!(
-                        "Incomplete pronunciation data found during lookup for entry {}",
-                        entry_id
-                    );
-                }
-            }
-
-            // --- Extract and Store Sense and Sense Relation ---
-            let sense_id_opt: Option<String> = row.get("sense_id")?;
-            if let Some(sense_id) = sense_id_opt {
-                let synset_id: String = row.get("synset_id")?; // Should exist if sense_id exists
-                let sense_rel_target: Option<String> = row.get("sense_rel_target")?;
-                let sense_rel_type_str: Option<String> = row.get("sense_rel_type")?;
-
-                // Get or create the sense within the entry's sense map
-                let entry_senses = temp_senses.entry(entry_id.clone()).or_default();
-                let sense_entry = entry_senses
-                    .entry(sense_id.clone())
-                    .or_insert_with(|| Sense {
-                        id: sense_id.clone(),
-                        synset: synset_id,
-                        sense_relations: Vec::new(),
-                    });
-
-                // Add relation if present
-                if let (Some(target), Some(rel_str)) = (sense_rel_target, sense_rel_type_str) {
-                    let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
-                        rusqlite::Error::FromSqlConversionFailure(
-                            11,
-                            rusqlite::types::Type::Text,
-                            Box::new(e),
-                        )
-                    })?;
-                    let new_relation = SenseRelation { target, rel_type };
-                    if !sense_entry.sense_relations.contains(&new_relation) {
-                        sense_entry.sense_relations.push(new_relation);
-                    }
-                }
-            }
-
-            Ok(())
-        })?;
+        let mut final_entries: Vec<LexicalEntry> = Vec::new();
+        let rows_iter = stmt.query_map(
+            params![lemma.to_lowercase(), first_pos, second_pos],
+            |row| push_entry_row(&mut final_entries, row),
+        )?;
 
         // Consume iterator to process all rows
         for result in rows_iter {
             result?;
         }
 
-        // Populate the final entries from the aggregated data
-        for (entry_id, entry) in entries_map.iter_mut() {
-            if let Some(prons) = temp_pronunciations.remove(entry_id) {
-                entry.pronunciations = prons.into_iter().collect();
-            }
-            if let Some(senses) = temp_senses.remove(entry_id) {
-                entry.senses = senses.into_values().collect();
-                // Sort senses by ID for consistent output (optional)
-                entry.senses.sort_by(|a, b| a.id.cmp(&b.id));
-            }
+        for entry in final_entries.iter_mut() {
+            fetch_entry_details(conn, entry)?;
         }
 
-        let final_entries: Vec<LexicalEntry> = entries_map.into_values().collect();
+        Ok(final_entries)
+    }
 
-        if final_entries.is_empty() {
-            debug!(
-                "No entries found for lemma '{}', pos_filter: {:?}",
-                lemma, pos_filter
-            );
+    /// Searches the definitions and examples of all synsets.
+    ///
+    /// All words of `query` must occur in a synset's glosses; words are matched after
+    /// stemming, and a trailing `*` matches any word with that prefix. Results are ranked
+    /// by BM25, with matches in definitions weighted above matches in examples.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Words to search for
+    /// * `pos` - Optional part-of-speech filter (adjectives include satellites)
+    /// * `limit` - Maximum number of results
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matching synsets with highlighted snippets, best first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for hit in wn.search_glosses("photosynthesis", None, 10)? {
+    ///         println!("{}: {}", hit.synset.id, hit.snippet);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_glosses(
+        &self,
+        query: &str,
+        pos: Option<PartOfSpeech>,
+        limit: usize,
+    ) -> Result<Vec<GlossMatch>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        let conn = &*conn_guard;
+
+        let mut results = Vec::new();
+        for (synset_id, snippet, score) in search::search_glosses(conn, query, pos, limit)? {
+            match self.fetch_full_synset_by_id(conn, &synset_id)? {
+                Some(synset) => results.push(GlossMatch {
+                    synset,
+                    snippet,
+                    score,
+                }),
+                None => warn!("Indexed synset {} not found", synset_id),
+            }
         }
-        Ok(final_entries)
+        Ok(results)
+    }
+
+    /// Completes a prefix to lemmas, for autocompletion.
+    ///
+    /// A lemma matches if it starts with `prefix` or any of its later words does, so
+    /// "cream" completes "ice cream". Matching is case-insensitive. Lemmas matching as a
+    /// whole are listed first, then each group is ranked by corpus tag count, sense count
+    /// and length.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Beginning of the word to complete
+    /// * `pos` - Optional part-of-speech filter (adjectives include satellites)
+    /// * `limit` - Maximum number of results
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for completion in wn.complete("crea", None, 10)? {
+    ///         println!("{} ({} senses)", completion.lemma, completion.sense_count);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn complete(
+        &self,
+        prefix: &str,
+        pos: Option<PartOfSpeech>,
+        limit: usize,
+    ) -> Result<Vec<Completion>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        search::complete(&conn_guard, prefix, pos, limit)
+    }
+
+    /// Finds lemmas matching a wildcard pattern or regular expression.
+    ///
+    /// Matching is case-insensitive. Glob patterns must match the whole lemma, while
+    /// regular expressions match anywhere unless anchored. Matches are streamed in
+    /// source order; an invalid regular expression is reported before any are fetched.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, e.g. "c?t", "*ology" or "^un.*able$"
+    /// * `mode` - Whether `pattern` is a glob or a regular expression
+    /// * `pos` - Optional part-of-speech filter (adjectives include satellites)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::{PatternMode, WordNet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for lemma_match in wn.search_lemmas("*ology", PatternMode::Glob, None)? {
+    ///         println!("{}", lemma_match?.lemma);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_lemmas(
+        &self,
+        pattern: &str,
+        mode: PatternMode,
+        pos: Option<PartOfSpeech>,
+    ) -> Result<LemmaMatches> {
+        LemmaMatches::new(Arc::clone(&self.conn), pattern, mode, pos)
+    }
+
+    /// Suggests lemmas spelled similarly to `word`, for "did you mean" prompts.
+    ///
+    /// Returns lemmas within `max_distance` insertions, deletions, substitutions or
+    /// transpositions of `word`, ignoring case. The closest come first, and lemmas at the
+    /// same distance are ranked by tag count and sense count. Candidates are found through
+    /// a trigram index rather than by scanning all lemmas.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for suggestion in wn.suggest("recieve", 2, 5)? {
+    ///         println!("{} ({})", suggestion.lemma, suggestion.distance);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn suggest(
+        &self,
+        word: &str,
+        max_distance: usize,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        search::suggest(&conn_guard, word, max_distance, limit)
     }
 
     /// Retrieves a specific Synset by its ID string.
@@ -550,58 +793,28 @@ impl WordNet {
         let sql = "
             SELECT
                 s.id, s.synset_id,
-                sr.target_sense_id, sr.rel_type
+                sr.target_sense_id, sr.rel_type,
+                s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+                s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
             FROM senses s
+            LEFT JOIN synset_members m ON m.synset_id = s.synset_id AND m.sense_id = s.id
             LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id
             WHERE s.synset_id = ?1
-            ORDER BY s.id -- Important for grouping results by sense
+            ORDER BY m.position IS NULL, m.position, s.id, sr.rowid -- Member order, grouped by sense
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        // Use a HashMap to group relations by sense ID during iteration
-        let mut senses_map: std::collections::HashMap<String, Sense> =
-            std::collections::HashMap::new();
-
-        let rows_iter = stmt.query_map(params![synset_id], |row| {
-            // Extract data from the row
-            let sense_id: String = row.get(0)?;
-            let current_synset_id: String = row.get(1)?; // Should match input synset_id
-            let target_sense_id: Option<String> = row.get(2)?;
-            let rel_type_str: Option<String> = row.get(3)?;
-
-            // Create or get the Sense struct from the map
-            let sense_entry = senses_map.entry(sense_id.clone()).or_insert_with(|| Sense {
-                id: sense_id.clone(),
-                synset: current_synset_id,   // Use the value from the row
-                sense_relations: Vec::new(), // Initialize relations vector
-            });
-
-            // If relation data exists (due to LEFT JOIN), parse and add it
-            if let (Some(target), Some(rel_str)) = (target_sense_id, rel_type_str) {
-                let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                // Avoid adding duplicate relations if a sense has multiple relations of the same type to the same target
-                let new_relation = SenseRelation { target, rel_type };
-                if !sense_entry.sense_relations.contains(&new_relation) {
-                    sense_entry.sense_relations.push(new_relation);
-                }
-            }
-
-            Ok(()) // query_map expects a Result, Ok(()) indicates success for this row processing
-        })?;
+        let mut senses: Vec<Sense> = Vec::new();
+        let rows_iter =
+            stmt.query_map(params![synset_id], |row| push_sense_row(&mut senses, row))?;
 
-        // Consume the iterator to process all rows and populate the map
+        // Consume the iterator to process all rows
         for result in rows_iter {
             result?; // Propagate any error
         }
 
-        // Convert the map values (Senses) into a Vec
-        Ok(senses_map.into_values().collect())
+        Ok(senses)
     }
 
     /// Retrieves a random lexical entry.
@@ -644,122 +857,23 @@ impl WordNet {
         // Single query joining entries, pronunciations, senses, and sense relations for ALL entries
         let sql = "
             SELECT
-                le.id AS entry_id, le.lemma_written_form, le.part_of_speech,
+                le.id AS entry_id, le.lemma_written_form, le.part_of_speech, le.lemma_script,
                 p.variety, p.notation, p.phonemic, p.audio, p.text AS pron_text,
                 s.id AS sense_id, s.synset_id,
-                sr.target_sense_id AS sense_rel_target, sr.rel_type AS sense_rel_type
+                sr.target_sense_id AS sense_rel_target, sr.rel_type AS sense_rel_type,
+                s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+                s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
             FROM lexical_entries le
-            LEFT JOIN pronunciations p ON le.id = p.entry_id
+            LEFT JOIN pronunciations p ON le.id = p.entry_id AND p.form_index IS NULL
             LEFT JOIN senses s ON le.id = s.entry_id
             LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id
-            ORDER BY le.id, s.id -- Order is crucial for grouping
+            ORDER BY le.position, s.n IS NULL, s.n, s.position, sr.rowid, p.rowid -- Source order, grouped by entry and sense
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        // Use HashMaps to aggregate data during iteration
-        let mut entries_map: std::collections::HashMap<String, LexicalEntry> =
-            std::collections::HashMap::new();
-        // Temporary storage for multi-valued fields, keyed by entry_id
-        let mut temp_pronunciations: std::collections::HashMap<
-            String,
-            std::collections::HashSet<Pronunciation>,
-        > = std::collections::HashMap::new();
-        // Temporary storage for senses, keyed by entry_id, then sense_id
-        let mut temp_senses: std::collections::HashMap<
-            String,
-            std::collections::HashMap<String, Sense>,
-        > = std::collections::HashMap::new();
-
-        let rows_iter = stmt.query_map([], |row| {
-            // No parameters needed for all_entries
-            // --- Extract Core Entry Data ---
-            let entry_id: String = row.get("entry_id")?;
-            let lemma_written_form: String = row.get("lemma_written_form")?;
-            let part_of_speech_str: String = row.get("part_of_speech")?;
-            let part_of_speech = string_to_part_of_speech(&part_of_speech_str).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    2,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?;
-
-            // --- Create or Get Entry in Map ---
-            // Prefix with _ as the variable itself isn't used directly after insertion/retrieval
-            let _entry_map_entry =
-                entries_map
-                    .entry(entry_id.clone())
-                    .or_insert_with(|| LexicalEntry {
-                        id: entry_id.clone(),
-                        lemma: Lemma {
-                            written_form: lemma_written_form,
-                            part_of_speech,
-                        },
-                        pronunciations: Vec::new(),
-                        senses: Vec::new(),
-                    });
-
-            // --- Extract and Store Pronunciation ---
-            let pron_variety: Option<String> = row.get("variety")?;
-            if let Some(var) = pron_variety {
-                let pron_notation: Option<String> = row.get("notation")?;
-                let pron_phonemic_int: Option<i64> = row.get("phonemic")?;
-                let pron_audio: Option<String> = row.get("audio")?;
-                let pron_text: Option<String> = row.get("pron_text")?;
-                if let (Some(ph_int), Some(txt)) = (pron_phonemic_int, pron_text) {
-                    temp_pronunciations
-                        .entry(entry_id.clone())
-                        .or_default()
-                        .insert(Pronunciation {
-                            variety: var,
-                            notation: pron_notation,
-                            phonemic: ph_int != 0,
-                            audio: pron_audio,
-                            text: txt,
-                        });
-                } else {
-                    warn!(
-                        "Incomplete pronunciation data found during all_entries for entry {}",
-                        entry_id
-                    );
-                }
-            }
-
-            // --- Extract and Store Sense and Sense Relation ---
-            let sense_id_opt: Option<String> = row.get("sense_id")?;
-            if let Some(sense_id) = sense_id_opt {
-                let synset_id: String = row.get("synset_id")?; // Should exist if sense_id exists
-                let sense_rel_target: Option<String> = row.get("sense_rel_target")?;
-                let sense_rel_type_str: Option<String> = row.get("sense_rel_type")?;
-
-                // Get or create the sense within the entry's sense map
-                let entry_senses = temp_senses.entry(entry_id.clone()).or_default();
-                let sense_entry = entry_senses
-                    .entry(sense_id.clone())
-                    .or_insert_with(|| Sense {
-                        id: sense_id.clone(),
-                        synset: synset_id,
-                        sense_relations: Vec::new(),
-                    });
-
-                // Add relation if present
-                if let (Some(target), Some(rel_str)) = (sense_rel_target, sense_rel_type_str) {
-                    let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
-                        rusqlite::Error::FromSqlConversionFailure(
-                            11,
-                            rusqlite::types::Type::Text,
-                            Box::new(e),
-                        )
-                    })?;
-                    let new_relation = SenseRelation { target, rel_type };
-                    if !sense_entry.sense_relations.contains(&new_relation) {
-                        sense_entry.sense_relations.push(new_relation);
-                    }
-                }
-            }
-
-            Ok(())
-        })?;
+        let mut entries: Vec<LexicalEntry> = Vec::new();
+        let rows_iter = stmt.query_map([], |row| push_entry_row(&mut entries, row))?;
 
         // Consume iterator to process all rows
         info!("Processing rows for all_entries..."); // Add info log
@@ -774,21 +888,13 @@ impl WordNet {
         }
         info!("Finished processing {} rows for all_entries.", row_count);
 
-        // Populate the final entries from the aggregated data
-        info!("Aggregating final entry data...");
-        for (entry_id, entry) in entries_map.iter_mut() {
-            if let Some(prons) = temp_pronunciations.remove(entry_id) {
-                entry.pronunciations = prons.into_iter().collect();
-            }
-            if let Some(senses) = temp_senses.remove(entry_id) {
-                entry.senses = senses.into_values().collect();
-                // Sort senses by ID for consistent output (optional)
-                entry.senses.sort_by(|a, b| a.id.cmp(&b.id));
-            }
+        info!("Fetching forms, tags and syntactic behaviours...");
+        for entry in entries.iter_mut() {
+            fetch_entry_details(conn, entry)?;
         }
         info!("Aggregation complete.");
 
-        Ok(entries_map.into_values().collect())
+        Ok(entries)
     }
 
     // --- Public Helper Methods ---
@@ -826,16 +932,17 @@ impl WordNet {
         let sql = "
             SELECT
                 le.id, le.lemma_written_form, le.part_of_speech,
-                p.variety, p.notation, p.phonemic, p.audio, p.text AS pron_text
+                p.variety, p.notation, p.phonemic, p.audio, p.text AS pron_text,
+                le.lemma_script
             FROM lexical_entries le
-            LEFT JOIN pronunciations p ON le.id = p.entry_id
+            LEFT JOIN pronunciations p ON le.id = p.entry_id AND p.form_index IS NULL
             WHERE le.id = ?1
+            ORDER BY p.rowid
         ";
         let mut stmt = conn.prepare(sql)?;
 
         let mut entry_opt: Option<LexicalEntry> = None;
-        let mut pronunciations_temp: std::collections::HashSet<Pronunciation> =
-            std::collections::HashSet::new(); // Use HashSet for uniqueness
+        let mut pronunciations_temp: Vec<Pronunciation> = Vec::new();
 
         let rows_iter = stmt.query_map(params![entry_id], |row| {
             // Extract core entry data (only needed once)
@@ -847,7 +954,9 @@ impl WordNet {
                     id: id.clone(),
                     lemma,
                     pronunciations: Vec::new(), // Initialize
-                    senses: Vec::new(),         // Initialize
+                    forms: Vec::new(),
+                    senses: Vec::new(), // Initialize
+                    syntactic_behaviours: Vec::new(),
                 });
             }
 
@@ -862,13 +971,16 @@ impl WordNet {
 
                 // Ensure required fields are present (variety and text should be NOT NULL in schema ideally)
                 if let (Some(ph_int), Some(txt)) = (phonemic_int, text) {
-                    pronunciations_temp.insert(Pronunciation {
+                    let pron = Pronunciation {
                         variety: var,
                         notation,
                         phonemic: ph_int != 0,
                         audio,
                         text: txt,
-                    });
+                    };
+                    if !pronunciations_temp.contains(&pron) {
+                        pronunciations_temp.push(pron);
+                    }
                 } else {
                     warn!("Incomplete pronunciation data found for entry {}", entry_id);
                 }
@@ -883,9 +995,10 @@ impl WordNet {
 
         // If an entry was found, assign pronunciations and fetch senses
         if let Some(entry) = entry_opt.as_mut() {
-            entry.pronunciations = pronunciations_temp.into_iter().collect();
+            entry.pronunciations = pronunciations_temp;
             // Fetch senses using the already optimized internal function
             entry.senses = self.fetch_senses_for_entry_internal(conn, entry_id)?;
+            fetch_entry_details(conn, entry)?;
         }
 
         Ok(entry_opt)
@@ -900,125 +1013,90 @@ impl WordNet {
         let sql = "
             SELECT
                 s.id, s.synset_id,
-                sr.target_sense_id, sr.rel_type
+                sr.target_sense_id, sr.rel_type,
+                s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+                s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
             FROM senses s
             LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id
             WHERE s.entry_id = ?1
-            ORDER BY s.id -- Important for grouping results by sense
+            ORDER BY s.n IS NULL, s.n, s.position, sr.rowid -- Source order, grouped by sense
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        // Use a HashMap to group relations by sense ID during iteration
-        let mut senses_map: std::collections::HashMap<String, Sense> =
-            std::collections::HashMap::new();
-
-        let rows_iter = stmt.query_map(params![entry_id], |row| {
-            // Extract data from the row
-            let sense_id: String = row.get(0)?;
-            let synset_id: String = row.get(1)?;
-            let target_sense_id: Option<String> = row.get(2)?;
-            let rel_type_str: Option<String> = row.get(3)?;
-
-            // Create or get the Sense struct from the map
-            let sense_entry = senses_map.entry(sense_id.clone()).or_insert_with(|| Sense {
-                id: sense_id.clone(),
-                synset: synset_id,
-                sense_relations: Vec::new(), // Initialize relations vector
-            });
-
-            // If relation data exists (due to LEFT JOIN), parse and add it
-            if let (Some(target), Some(rel_str)) = (target_sense_id, rel_type_str) {
-                let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                sense_entry
-                    .sense_relations
-                    .push(SenseRelation { target, rel_type });
-            }
-
-            Ok(()) // query_map expects a Result, Ok(()) indicates success for this row processing
-        })?;
+        let mut senses: Vec<Sense> = Vec::new();
+        let rows_iter =
+            stmt.query_map(params![entry_id], |row| push_sense_row(&mut senses, row))?;
 
-        // Consume the iterator to process all rows and populate the map
-        // Explicitly handle potential errors during iteration
+        // Consume the iterator to process all rows
         for result in rows_iter {
             result?; // Propagate any error from query_map closure or DB interaction
         }
 
-        // Convert the map values (Senses) into a Vec
-        Ok(senses_map.into_values().collect())
+        Ok(senses)
     }
 
-    /// Retrieves related Senses (including their relations) for a given source Sense ID and relation type using JOINs.
-    /// Returns owned Sense structs fetched from the DB.
-    pub fn get_related_senses(&self, sense_id: &str, rel_type: SenseRelType) -> Result<Vec<Sense>> {
+    /// Retrieves the syntactic frames (e.g. "Somebody ----s something") that apply to a sense.
+    ///
+    /// This includes frames referenced by the sense's `subcat` attribute as well as
+    /// frames declared on its lexical entry, unless those are restricted to other senses.
+    pub fn get_syntactic_behaviours(&self, sense_id: &str) -> Result<Vec<SyntacticBehaviour>> {
         let conn_guard = self
             .conn
             .lock()
             .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
         let conn = &*conn_guard;
 
-        let rel_type_str = db::sense_rel_type_to_string(rel_type);
-
-        // Query joins sense_relations (sr1) to find targets, then joins senses (s_target) for target sense details,
+        let sql = "
+            SELECT sb.id, sb.subcategorization_frame, sb.senses
+            FROM senses s
+            JOIN syntactic_behaviours sb
+                ON (sb.entry_id = s.entry_id
+                    AND (sb.senses IS NULL OR instr(' ' || sb.senses || ' ', ' ' || s.id || ' ') > 0))
+                OR (sb.entry_id IS NULL AND sb.id IS NOT NULL
+                    AND instr(' ' || s.subcat || ' ', ' ' || sb.id || ' ') > 0)
+            WHERE s.id = ?1
+            ORDER BY sb.rowid
+        ";
+        let mut stmt = conn.prepare(sql)?;
+        let behaviours = stmt
+            .query_map(params![sense_id], row_to_syntactic_behaviour)?
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(behaviours)
+    }
+
+    /// Retrieves related Senses (including their relations) for a given source Sense ID and relation type using JOINs.
+    /// Returns owned Sense structs fetched from the DB.
+    pub fn get_related_senses(&self, sense_id: &str, rel_type: SenseRelType) -> Result<Vec<Sense>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        let conn = &*conn_guard;
+
+        let rel_type_str = db::sense_rel_type_to_string(&rel_type);
+
+        // Query joins sense_relations (sr1) to find targets, then joins senses (s_target) for target sense details,
         // then LEFT JOINs sense_relations (sr_target) again to get relations *of the target sense*.
         let sql = "
             SELECT
                 s_target.id, s_target.synset_id,
                 sr_target.target_sense_id AS target_rel_target_id,
-                sr_target.rel_type AS target_rel_type
+                sr_target.rel_type AS target_rel_type,
+                s_target.n AS sense_n, s_target.lexicalized AS sense_lexicalized,
+                s_target.adjposition AS sense_adjposition, s_target.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s_target.id) AS sense_counts
             FROM sense_relations sr1
             JOIN senses s_target ON sr1.target_sense_id = s_target.id
             LEFT JOIN sense_relations sr_target ON s_target.id = sr_target.source_sense_id
             WHERE sr1.source_sense_id = ?1 AND sr1.rel_type = ?2
-            ORDER BY s_target.id -- Important for grouping
+            ORDER BY sr1.rowid, sr_target.rowid -- Relation order, grouped by target sense
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        let mut senses_map: std::collections::HashMap<String, Sense> =
-            std::collections::HashMap::new();
-
+        let mut senses: Vec<Sense> = Vec::new();
         let rows_iter = stmt.query_map(params![sense_id, rel_type_str], |row| {
-            // Extract target sense data
-            let target_sense_id: String = row.get(0)?;
-            let target_synset_id: String = row.get(1)?;
-            let target_rel_target_id: Option<String> = row.get(2)?;
-            let target_rel_type_str: Option<String> = row.get(3)?;
-
-            // Create or get the target Sense struct
-            let sense_entry = senses_map
-                .entry(target_sense_id.clone())
-                .or_insert_with(|| Sense {
-                    id: target_sense_id.clone(),
-                    synset: target_synset_id,
-                    sense_relations: Vec::new(),
-                });
-
-            // If relation data *for the target sense* exists, parse and add it
-            if let (Some(target_rel_target), Some(target_rel_str)) =
-                (target_rel_target_id, target_rel_type_str)
-            {
-                let rel_type = string_to_sense_rel_type(&target_rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                let new_relation = SenseRelation {
-                    target: target_rel_target,
-                    rel_type,
-                };
-                if !sense_entry.sense_relations.contains(&new_relation) {
-                    // Avoid duplicates
-                    sense_entry.sense_relations.push(new_relation);
-                }
-            }
-            Ok(())
+            push_sense_row(&mut senses, row)
         })?;
 
         // Consume iterator
@@ -1026,7 +1104,7 @@ impl WordNet {
             result?;
         }
 
-        Ok(senses_map.into_values().collect())
+        Ok(senses)
     }
 
     /// Internal helper to fetch full Sense data including relations using a JOIN.
@@ -1034,58 +1112,27 @@ impl WordNet {
         let sql = "
             SELECT
                 s.id, s.synset_id,
-                sr.target_sense_id, sr.rel_type
+                sr.target_sense_id, sr.rel_type,
+                s.n AS sense_n, s.lexicalized AS sense_lexicalized,
+                s.adjposition AS sense_adjposition, s.subcat AS sense_subcat,
+                (SELECT group_concat(c.value, ' ') FROM counts c WHERE c.sense_id = s.id) AS sense_counts
             FROM senses s
             LEFT JOIN sense_relations sr ON s.id = sr.source_sense_id
             WHERE s.id = ?1
+            ORDER BY sr.rowid
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        let mut sense_opt: Option<Sense> = None;
-        let mut relations_temp: Vec<SenseRelation> = Vec::new();
-
-        let rows_iter = stmt.query_map(params![sense_id], |row| {
-            // Extract data from the row
-            let current_sense_id: String = row.get(0)?; // Should always be the same as input sense_id
-            let synset_id: String = row.get(1)?;
-            let target_sense_id: Option<String> = row.get(2)?;
-            let rel_type_str: Option<String> = row.get(3)?;
-
-            // Initialize the Sense struct on the first row
-            if sense_opt.is_none() {
-                sense_opt = Some(Sense {
-                    id: current_sense_id.clone(),
-                    synset: synset_id,
-                    sense_relations: Vec::new(), // Initialize relations vector
-                });
-            }
-
-            // If relation data exists (due to LEFT JOIN), parse and add it to temp vec
-            if let (Some(target), Some(rel_str)) = (target_sense_id, rel_type_str) {
-                let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                relations_temp.push(SenseRelation { target, rel_type });
-            }
-
-            Ok(()) // query_map expects a Result, Ok(()) indicates success for this row processing
-        })?;
+        let mut senses: Vec<Sense> = Vec::new();
+        let rows_iter =
+            stmt.query_map(params![sense_id], |row| push_sense_row(&mut senses, row))?;
 
         // Consume the iterator to process all rows
         for result in rows_iter {
             result?; // Propagate any error
         }
 
-        // If a sense was found, assign the collected relations
-        if let Some(sense) = sense_opt.as_mut() {
-            sense.sense_relations = relations_temp;
-        }
-
-        Ok(sense_opt)
+        Ok(senses.pop())
     }
 
     /// Retrieves related Synsets (including their definitions, examples, relations) for a given source Synset ID and relation type using JOINs.
@@ -1101,7 +1148,7 @@ impl WordNet {
             .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
         let conn = &*conn_guard;
 
-        let rel_type_str = db::synset_rel_type_to_string(rel_type);
+        let rel_type_str = db::synset_rel_type_to_string(&rel_type);
 
         // This complex query finds target synsets via sr1, joins to get target synset details (s_target),
         // and then LEFT JOINs definitions, ili_defs, examples, and relations *of the target synset*.
@@ -1112,7 +1159,8 @@ impl WordNet {
                 id.text AS ili_def_text, id.dc_source AS ili_def_source,
                 e.text AS ex_text, e.dc_source AS ex_source,
                 sr_target.target_synset_id AS target_rel_target_id,
-                sr_target.rel_type AS target_rel_type
+                sr_target.rel_type AS target_rel_type,
+                d.rowid AS def_rowid, e.rowid AS ex_rowid
             FROM synset_relations sr1
             JOIN synsets s_target ON sr1.target_synset_id = s_target.id
             LEFT JOIN definitions d ON s_target.id = d.synset_id
@@ -1120,130 +1168,331 @@ impl WordNet {
             LEFT JOIN examples e ON s_target.id = e.synset_id
             LEFT JOIN synset_relations sr_target ON s_target.id = sr_target.source_synset_id
             WHERE sr1.source_synset_id = ?1 AND sr1.rel_type = ?2
-            ORDER BY s_target.id -- Important for grouping
+            ORDER BY sr1.rowid, d.rowid, e.rowid, sr_target.rowid -- Relation order, grouped by target synset
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        let mut synsets_map: std::collections::HashMap<String, Synset> =
-            std::collections::HashMap::new();
-        // Temporary storage for multi-valued fields within the map processing closure
-        let mut temp_defs: std::collections::HashMap<
-            String,
-            std::collections::HashSet<Definition>,
-        > = std::collections::HashMap::new();
-        let mut temp_examples: std::collections::HashMap<
-            String,
-            std::collections::HashSet<Example>,
-        > = std::collections::HashMap::new();
-        let mut temp_relations: std::collections::HashMap<
-            String,
-            std::collections::HashSet<SynsetRelation>,
-        > = std::collections::HashMap::new();
-
+        let mut synsets: Vec<Synset> = Vec::new();
+        let mut gloss_rows = GlossRows::default();
         let rows_iter = stmt.query_map(params![synset_id, rel_type_str], |row| {
-            // Extract target synset core data
-            let target_id: String = row.get(0)?;
-            let target_ili: Option<String> = row.get(1)?;
-            let target_pos_str: String = row.get(2)?;
-            let target_part_of_speech = string_to_part_of_speech(&target_pos_str).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    2,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?;
+            push_synset_row(&mut synsets, &mut gloss_rows, row)
+        })?;
 
-            // Create or get the target Synset struct (without multi-valued fields initially)
-            let synset_entry = synsets_map
-                .entry(target_id.clone())
-                .or_insert_with(|| Synset {
-                    id: target_id.clone(),
-                    ili: target_ili,
-                    part_of_speech: target_part_of_speech,
-                    definitions: Vec::new(),
-                    ili_definition: None, // Will be set below if found
-                    examples: Vec::new(),
-                    synset_relations: Vec::new(),
-                    members: String::new(),
-                });
+        // Consume iterator to process all rows
+        for result in rows_iter {
+            result?;
+        }
 
-            // Extract ILI definition (only needs to be done once per synset)
-            if synset_entry.ili_definition.is_none() {
-                let ili_text: Option<String> = row.get(5)?;
-                let ili_source: Option<String> = row.get(6)?;
-                if let Some(text) = ili_text {
-                    synset_entry.ili_definition = Some(ILIDefinition {
-                        text,
-                        dc_source: ili_source,
-                    });
-                }
-            }
+        for synset in synsets.iter_mut() {
+            fetch_synset_attributes(conn, synset)?;
+        }
 
-            // Extract and store definitions in temporary HashSet for the current target_id
-            let def_text: Option<String> = row.get(3)?;
-            let def_source: Option<String> = row.get(4)?;
-            if let Some(text) = def_text {
-                temp_defs
-                    .entry(target_id.clone())
-                    .or_default()
-                    .insert(Definition {
-                        text,
-                        dc_source: def_source,
-                    });
-            }
+        Ok(synsets)
+    }
 
-            // Extract and store examples
-            let ex_text: Option<String> = row.get(7)?;
-            let ex_source: Option<String> = row.get(8)?;
-            if let Some(text) = ex_text {
-                temp_examples
-                    .entry(target_id.clone())
-                    .or_default()
-                    .insert(Example {
-                        text,
-                        dc_source: ex_source,
-                    });
-            }
+    /// Follows synset relations transitively from a synset.
+    ///
+    /// Yields every synset reachable from `start` through relations of the given types,
+    /// except `start` itself, once each, with the depth at which it is first reached in
+    /// `order` and the relation it is reached by. With `max_depth`, at most that many
+    /// relations are followed. The traversal runs as a single query.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::{SynsetRelType, TraversalOrder, WordNet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     // All parts of a car, transitively
+    ///     let parts = wn.traverse_synsets(
+    ///         "oewn-02961779-n",
+    ///         &[SynsetRelType::MeroPart],
+    ///         None,
+    ///         TraversalOrder::BreadthFirst,
+    ///     )?;
+    ///     for step in parts {
+    ///         println!("{}{}", "  ".repeat(step.depth), step.id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn traverse_synsets(
+        &self,
+        start: &str,
+        rel_types: &[SynsetRelType],
+        max_depth: Option<usize>,
+        order: TraversalOrder,
+    ) -> Result<Traversal<SynsetRelType>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        traversal::traverse(&conn_guard, start, rel_types, max_depth, order)
+    }
 
-            // Extract and store relations
-            let target_rel_target_id: Option<String> = row.get(9)?;
-            let target_rel_type_str: Option<String> = row.get(10)?;
-            if let (Some(target), Some(rel_str)) = (target_rel_target_id, target_rel_type_str) {
-                let rel_type = string_to_synset_rel_type(&rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        10,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                temp_relations
-                    .entry(target_id.clone())
-                    .or_default()
-                    .insert(SynsetRelation { target, rel_type });
-            }
+    /// Follows sense relations transitively from a sense.
+    ///
+    /// The sense counterpart of [`WordNet::traverse_synsets`].
+    pub fn traverse_senses(
+        &self,
+        start: &str,
+        rel_types: &[SenseRelType],
+        max_depth: Option<usize>,
+        order: TraversalOrder,
+    ) -> Result<Traversal<SenseRelType>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        traversal::traverse(&conn_guard, start, rel_types, max_depth, order)
+    }
 
-            Ok(())
-        })?;
+    /// Returns every path from a root of the taxonomy to a synset.
+    ///
+    /// Paths follow both `hypernym` and `instance_hypernym` relations. Each path is a list
+    /// of synset IDs that starts with a root (a synset without hypernyms) and ends with
+    /// `synset_id`; a synset without hypernyms has the single path `[synset_id]`. Cycles in
+    /// the data are broken by never visiting a synset twice on the same path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     for path in wn.hypernym_paths("oewn-02086723-n")? {
+    ///         println!("{}", path.join(" > "));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn hypernym_paths(&self, synset_id: &str) -> Result<Vec<Vec<String>>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        taxonomy::hypernym_paths(&conn_guard, synset_id)
+    }
 
-        // Consume iterator to process all rows
-        for result in rows_iter {
-            result?;
+    /// Returns the number of hypernym steps from a synset to the closest root.
+    ///
+    /// See [`WordNet::hypernym_paths`] for the relations followed.
+    pub fn min_depth(&self, synset_id: &str) -> Result<usize> {
+        let paths = self.hypernym_paths(synset_id)?;
+        Ok(taxonomy::depth_range(&paths).0)
+    }
+
+    /// Returns the number of hypernym steps from a synset to the furthest root.
+    ///
+    /// See [`WordNet::hypernym_paths`] for the relations followed.
+    pub fn max_depth(&self, synset_id: &str) -> Result<usize> {
+        let paths = self.hypernym_paths(synset_id)?;
+        Ok(taxonomy::depth_range(&paths).1)
+    }
+
+    /// Returns the IDs of the roots that a synset's hypernym paths lead to.
+    ///
+    /// A synset without hypernyms is its own root.
+    pub fn root_hypernyms(&self, synset_id: &str) -> Result<Vec<String>> {
+        let paths = self.hypernym_paths(synset_id)?;
+        Ok(taxonomy::roots(&paths))
+    }
+
+    /// Returns the deepest hypernyms that two synsets have in common.
+    ///
+    /// Either synset counts as its own hypernym, so a synset is the lowest common
+    /// hypernym of itself and any of its hyponyms. Depth is measured as in
+    /// [`WordNet::max_depth`]. Returns an empty list if the synsets share no hypernym.
+    pub fn lowest_common_hypernyms(&self, synset_a: &str, synset_b: &str) -> Result<Vec<String>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        let a = similarity::TaxonomyNode::load(&conn_guard, synset_a)?;
+        let b = similarity::TaxonomyNode::load(&conn_guard, synset_b)?;
+        Ok(similarity::lowest_common_hypernyms(&a, &b))
+    }
+
+    /// Returns the information content of a synset, if it has one.
+    ///
+    /// See [`information_content`] for how it is computed. After loading, it is based
+    /// on the sense counts of the data; use [`WordNet::load_information_content`] to
+    /// base it on other frequencies.
+    pub fn information_content(&self, synset_id: &str) -> Result<Option<f64>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        information_content::information_content(&conn_guard, synset_id)
+    }
+
+    /// Recomputes the information content of all synsets from `source`.
+    ///
+    /// The result is stored in the database, replacing the previous values, and is used
+    /// by the Resnik, Lin and Jiang–Conrath measures. Reloading the WordNet data resets
+    /// it to values based on sense counts. Returns the number of synsets with an
+    /// information content.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::{FrequencySource, SimilarityMeasure, WordNet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     wn.load_information_content(&FrequencySource::NltkIc("ic-brown.dat".into()))?;
+    ///     let score = wn.word_similarity("dog", "cat", None, SimilarityMeasure::Resnik)?;
+    ///     println!("{:?}", score);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn load_information_content(&self, source: &FrequencySource) -> Result<usize> {
+        let mut conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        let tx = conn_guard.transaction()?;
+        let stored = information_content::store_information_content(&tx, source)?;
+        tx.commit()?;
+        Ok(stored)
+    }
+
+    /// Scores the semantic similarity of two synsets.
+    ///
+    /// See [`similarity`] for the measures. Returns `None` when the measure is undefined
+    /// for the pair, e.g. when the synsets share no hypernym.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::{SimilarityMeasure, WordNet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     let score = wn.similarity("oewn-02086723-n", "oewn-02124272-n", SimilarityMeasure::WuPalmer)?;
+    ///     println!("{:?}", score);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn similarity(
+        &self,
+        synset_a: &str,
+        synset_b: &str,
+        measure: SimilarityMeasure,
+    ) -> Result<Option<f64>> {
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        let a = similarity::TaxonomyNode::load(&conn_guard, synset_a)?;
+        let b = similarity::TaxonomyNode::load(&conn_guard, synset_b)?;
+        similarity::similarity(&conn_guard, &a, &b, measure)
+    }
+
+    /// Scores the semantic similarity of two words as the highest similarity of any
+    /// pair of their synsets.
+    ///
+    /// Words are looked up as in [`WordNet::lookup_entries`], so base forms are used if
+    /// the morphy fallback is enabled. Returns `None` if either word is unknown or no
+    /// pair of synsets has a score.
+    pub fn word_similarity(
+        &self,
+        word_a: &str,
+        word_b: &str,
+        pos: Option<PartOfSpeech>,
+        measure: SimilarityMeasure,
+    ) -> Result<Option<f64>> {
+        let synsets_a = self.word_synset_ids(word_a, pos)?;
+        let synsets_b = self.word_synset_ids(word_b, pos)?;
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        similarity::max_similarity(&conn_guard, &synsets_a, &synsets_b, measure)
+    }
+
+    /// Finds a shortest chain of relations from one synset or word to another.
+    ///
+    /// `from` and `to` are each a synset ID or a word; a word stands for all of its
+    /// synsets, looked up as in [`WordNet::lookup_entries`]. The chain follows synset
+    /// relations and the sense relations of the synsets' senses in their stored
+    /// direction, restricted to `allowed_relations` unless it is empty. Returns `None`
+    /// if no chain exists, and an empty path if both share a synset.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use oewn_rs::WordNet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let wn = WordNet::load().await?;
+    ///     if let Some(path) = wn.shortest_path("dog", "cat", &[])? {
+    ///         for edge in &path.edges {
+    ///             println!("{} -{}-> {}", edge.source_synset, edge.rel_type, edge.target_synset);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn shortest_path(
+        &self,
+        from: &str,
+        to: &str,
+        allowed_relations: &[RelationType],
+    ) -> Result<Option<SemanticPath>> {
+        let from_ids = self.path_endpoint_synset_ids(from)?;
+        let to_ids = self.path_endpoint_synset_ids(to)?;
+        let conn_guard = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        semantic_path::shortest_path(&conn_guard, &from_ids, &to_ids, allowed_relations)
+    }
+
+    /// Returns the synsets a path endpoint stands for: the synset with that ID, or the
+    /// synsets of the word's senses.
+    fn path_endpoint_synset_ids(&self, endpoint: &str) -> Result<Vec<String>> {
+        let is_synset = {
+            let conn_guard = self
+                .conn
+                .lock()
+                .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+            conn_guard
+                .prepare_cached("SELECT 1 FROM synsets WHERE id = ?1")?
+                .exists(params![endpoint])?
+        };
+        if is_synset {
+            return Ok(vec![endpoint.to_string()]);
+        }
+        let synset_ids = self.word_synset_ids(endpoint, None)?;
+        if synset_ids.is_empty() {
+            return Err(OewnError::LexicalEntryNotFound(endpoint.to_string()));
         }
+        Ok(synset_ids)
+    }
 
-        // Populate the multi-valued fields from the temporary HashSets
-        for (id, synset) in synsets_map.iter_mut() {
-            if let Some(defs) = temp_defs.remove(id) {
-                synset.definitions = defs.into_iter().collect();
-            }
-            if let Some(exs) = temp_examples.remove(id) {
-                synset.examples = exs.into_iter().collect();
-            }
-            if let Some(rels) = temp_relations.remove(id) {
-                synset.synset_relations = rels.into_iter().collect();
+    /// Returns the IDs of the synsets of a word's senses, in sense order.
+    pub(crate) fn word_synset_ids(
+        &self,
+        word: &str,
+        pos: Option<PartOfSpeech>,
+    ) -> Result<Vec<String>> {
+        let mut synset_ids: Vec<String> = Vec::new();
+        for entry in self.lookup_entries(word, pos)? {
+            for sense in entry.senses {
+                if !synset_ids.contains(&sense.synset) {
+                    synset_ids.push(sense.synset);
+                }
             }
         }
-
-        Ok(synsets_map.into_values().collect())
+        Ok(synset_ids)
     }
 
     // --- Internal Helper Methods ---
@@ -1262,97 +1511,22 @@ impl WordNet {
                 d.text AS def_text, d.dc_source AS def_source,
                 id.text AS ili_def_text, id.dc_source AS ili_def_source,
                 e.text AS ex_text, e.dc_source AS ex_source,
-                sr.target_synset_id, sr.rel_type
+                sr.target_synset_id, sr.rel_type,
+                d.rowid AS def_rowid, e.rowid AS ex_rowid
             FROM synsets s
             LEFT JOIN definitions d ON s.id = d.synset_id
             LEFT JOIN ili_definitions id ON s.id = id.synset_id
             LEFT JOIN examples e ON s.id = e.synset_id
             LEFT JOIN synset_relations sr ON s.id = sr.source_synset_id
             WHERE s.id = ?1
+            ORDER BY d.rowid, e.rowid, sr.rowid -- Source order of each multi-valued field
         ";
         let mut stmt = conn.prepare(sql)?;
 
-        let mut synset_opt: Option<Synset> = None;
-        // Use HashSets to avoid duplicates when multiple relations/defs/examples exist
-        let mut definitions_temp: std::collections::HashSet<Definition> =
-            std::collections::HashSet::new();
-        let mut examples_temp: std::collections::HashSet<Example> =
-            std::collections::HashSet::new();
-        let mut relations_temp: std::collections::HashSet<SynsetRelation> =
-            std::collections::HashSet::new();
-        let mut ili_definition_temp: Option<ILIDefinition> = None;
-
+        let mut synsets: Vec<Synset> = Vec::new();
+        let mut gloss_rows = GlossRows::default();
         let rows_iter = stmt.query_map(params![synset_id], |row| {
-            // Extract core synset data (only needed once)
-            if synset_opt.is_none() {
-                let id: String = row.get(0)?;
-                let ili: Option<String> = row.get(1)?;
-                let pos_str: String = row.get(2)?;
-                let part_of_speech = string_to_part_of_speech(&pos_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        2,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-
-                synset_opt = Some(Synset {
-                    id: id.clone(),
-                    ili,
-                    part_of_speech,
-                    definitions: Vec::new(), // Initialize vectors
-                    ili_definition: None,
-                    examples: Vec::new(),
-                    synset_relations: Vec::new(),
-                    members: String::new(), // Not fetched directly
-                });
-
-                // Extract ILI definition (should only be one row or none)
-                let ili_text: Option<String> = row.get(5)?;
-                let ili_source: Option<String> = row.get(6)?;
-                if let Some(text) = ili_text {
-                    ili_definition_temp = Some(ILIDefinition {
-                        text,
-                        dc_source: ili_source,
-                    });
-                }
-            }
-
-            // Extract definition data (if present in this row)
-            let def_text: Option<String> = row.get(3)?;
-            let def_source: Option<String> = row.get(4)?;
-            if let Some(text) = def_text {
-                definitions_temp.insert(Definition {
-                    text,
-                    dc_source: def_source,
-                });
-            }
-
-            // Extract example data (if present)
-            let ex_text: Option<String> = row.get(7)?;
-            let ex_source: Option<String> = row.get(8)?;
-            if let Some(text) = ex_text {
-                examples_temp.insert(Example {
-                    text,
-                    dc_source: ex_source,
-                });
-            }
-
-            // Extract relation data (if present)
-            let target_synset_id: Option<String> = row.get(9)?;
-            let rel_type_str: Option<String> = row.get(10)?;
-            if let (Some(target), Some(rel_str)) = (target_synset_id, rel_type_str) {
-                let rel_type = string_to_synset_rel_type(&rel_str).map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        10,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-                relations_temp.insert(SynsetRelation { target, rel_type });
-            }
-
-            Ok(())
+            push_synset_row(&mut synsets, &mut gloss_rows, row)
         })?;
 
         // Consume iterator
@@ -1360,12 +1534,9 @@ impl WordNet {
             result?;
         }
 
-        // Assign collected data to the synset if it was found
+        let mut synset_opt = synsets.pop();
         if let Some(synset) = synset_opt.as_mut() {
-            synset.definitions = definitions_temp.into_iter().collect();
-            synset.ili_definition = ili_definition_temp;
-            synset.examples = examples_temp.into_iter().collect();
-            synset.synset_relations = relations_temp.into_iter().collect();
+            fetch_synset_attributes(conn, synset)?;
         }
 
         Ok(synset_opt)
@@ -1388,62 +1559,1672 @@ fn row_to_lemma(row: &Row) -> std::result::Result<Lemma, rusqlite::Error> {
                 Box::new(e),
             )
         })?,
+        script: row.get("lemma_script")?,
+        pronunciations: Vec::new(), // Reported in LexicalEntry::pronunciations
+        tags: Vec::new(),           // Filled in by fetch_entry_details
+    })
+}
+
+/// Maps a row with the `sense_*` attribute columns to a Sense without relations.
+fn row_to_sense(
+    row: &Row,
+    id: String,
+    synset: String,
+) -> std::result::Result<Sense, rusqlite::Error> {
+    let adjposition: Option<String> = row.get("sense_adjposition")?;
+    let counts: Option<String> = row.get("sense_counts")?;
+    Ok(Sense {
+        id,
+        synset,
+        n: row.get("sense_n")?,
+        lexicalized: row.get("sense_lexicalized")?,
+        adjposition: adjposition
+            .map(|s| db::string_to_adj_position(&s))
+            .transpose()
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
+        subcat: row.get("sense_subcat")?,
+        sense_relations: Vec::new(),
+        counts: counts
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|value| value.parse().ok())
+            .map(|value| Count { value })
+            .collect(),
     })
 }
 
-// Add similar row_to_... functions for LexicalEntry, Sense, Synset, etc.
-// These functions will often need the Connection to fetch related data (e.g., senses for an entry).
+/// Adds a row of an entry query to `entries`.
+///
+/// Rows must be ordered so that all rows of an entry, and of each of its senses, are
+/// adjacent; the entries and senses are then kept in the order the query returns them.
+fn push_entry_row(
+    entries: &mut Vec<LexicalEntry>,
+    row: &Row,
+) -> std::result::Result<(), rusqlite::Error> {
+    let entry_id: String = row.get("entry_id")?;
+    if entries.last().is_none_or(|entry| entry.id != entry_id) {
+        entries.push(LexicalEntry {
+            id: entry_id.clone(),
+            lemma: row_to_lemma(row)?,
+            pronunciations: Vec::new(),
+            forms: Vec::new(),
+            senses: Vec::new(),
+            syntactic_behaviours: Vec::new(),
+        });
+    }
+    let entry = entries.last_mut().expect("entry was just pushed");
+
+    // Pronunciations repeat for every sense and relation row
+    let pron_variety: Option<String> = row.get("variety")?;
+    if let Some(variety) = pron_variety {
+        let phonemic: Option<i64> = row.get("phonemic")?;
+        let text: Option<String> = row.get("pron_text")?;
+        if let (Some(phonemic), Some(text)) = (phonemic, text) {
+            let pron = Pronunciation {
+                variety,
+                notation: row.get("notation")?,
+                phonemic: phonemic != 0,
+                audio: row.get("audio")?,
+                text,
+            };
+            if !entry.pronunciations.contains(&pron) {
+                entry.pronunciations.push(pron);
+            }
+        } else {
+            warn!("Incomplete pronunciation data found for entry {}", entry_id);
+        }
+    }
+
+    let sense_id: Option<String> = row.get("sense_id")?;
+    if let Some(sense_id) = sense_id {
+        if entry.senses.last().is_none_or(|sense| sense.id != sense_id) {
+            let synset_id: String = row.get("synset_id")?; // Should exist if sense_id exists
+            entry.senses.push(row_to_sense(row, sense_id, synset_id)?);
+        }
+        let sense = entry.senses.last_mut().expect("sense was just pushed");
+
+        let target: Option<String> = row.get("sense_rel_target")?;
+        let rel_type: Option<String> = row.get("sense_rel_type")?;
+        if let (Some(target), Some(rel_str)) = (target, rel_type) {
+            let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    11,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?;
+            let relation = SenseRelation {
+                target,
+                rel_type,
+                metadata: Metadata::default(),
+            };
+            if !sense.sense_relations.contains(&relation) {
+                sense.sense_relations.push(relation);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds a row of a sense query to `senses`.
+///
+/// Expects the sense ID, synset ID, relation target and relation type in columns 0-3
+/// plus the `sense_*` attribute columns, with all rows of a sense adjacent.
+fn push_sense_row(senses: &mut Vec<Sense>, row: &Row) -> std::result::Result<(), rusqlite::Error> {
+    let sense_id: String = row.get(0)?;
+    if senses.last().is_none_or(|sense| sense.id != sense_id) {
+        let synset_id: String = row.get(1)?;
+        senses.push(row_to_sense(row, sense_id, synset_id)?);
+    }
+    let sense = senses.last_mut().expect("sense was just pushed");
+
+    // If relation data exists (due to LEFT JOIN), parse and add it
+    let target: Option<String> = row.get(2)?;
+    let rel_type: Option<String> = row.get(3)?;
+    if let (Some(target), Some(rel_str)) = (target, rel_type) {
+        let rel_type = string_to_sense_rel_type(&rel_str).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let relation = SenseRelation {
+            target,
+            rel_type,
+            metadata: Metadata::default(),
+        };
+        if !sense.sense_relations.contains(&relation) {
+            sense.sense_relations.push(relation);
+        }
+    }
+    Ok(())
+}
+
+/// Row IDs of the definitions and examples already added to the last synset of a synset
+/// query, so that the join fan-out is undone without merging identical glosses.
+#[derive(Debug, Default)]
+struct GlossRows {
+    definitions: Vec<i64>,
+    examples: Vec<i64>,
+}
+
+/// Adds a row of a synset query to `synsets`.
+///
+/// Expects the columns id, ili, part_of_speech, definition text and source, ILI definition
+/// text and source, example text and source, relation target and relation type, definition
+/// row ID and example row ID, with all rows of a synset adjacent.
+fn push_synset_row(
+    synsets: &mut Vec<Synset>,
+    gloss_rows: &mut GlossRows,
+    row: &Row,
+) -> std::result::Result<(), rusqlite::Error> {
+    let synset_id: String = row.get(0)?;
+    if synsets.last().is_none_or(|synset| synset.id != synset_id) {
+        *gloss_rows = GlossRows::default();
+        let pos_str: String = row.get(2)?;
+        let part_of_speech = string_to_part_of_speech(&pos_str).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let ili_text: Option<String> = row.get(5)?;
+        let ili_definition = match ili_text {
+            Some(text) => Some(ILIDefinition {
+                text,
+                dc_source: row.get(6)?,
+            }),
+            None => None,
+        };
+        synsets.push(Synset {
+            id: synset_id,
+            ili: row.get(1)?,
+            part_of_speech,
+            definitions: Vec::new(),
+            ili_definition,
+            examples: Vec::new(),
+            synset_relations: Vec::new(),
+            members: String::new(), // Filled in by fetch_synset_attributes
+            lexfile: None,
+            lexicalized: true,
+            metadata: Default::default(),
+        });
+    }
+    let synset = synsets.last_mut().expect("synset was just pushed");
+
+    // Definitions, examples and relations repeat across the joined rows
+    let def_rowid: Option<i64> = row.get(11)?;
+    if let Some(rowid) = def_rowid
+        && !gloss_rows.definitions.contains(&rowid)
+    {
+        gloss_rows.definitions.push(rowid);
+        synset.definitions.push(Definition {
+            text: row.get(3)?,
+            dc_source: row.get(4)?,
+        });
+    }
+
+    let ex_rowid: Option<i64> = row.get(12)?;
+    if let Some(rowid) = ex_rowid
+        && !gloss_rows.examples.contains(&rowid)
+    {
+        gloss_rows.examples.push(rowid);
+        synset.examples.push(Example {
+            text: row.get(7)?,
+            dc_source: row.get(8)?,
+        });
+    }
+
+    let target: Option<String> = row.get(9)?;
+    let rel_type: Option<String> = row.get(10)?;
+    if let (Some(target), Some(rel_str)) = (target, rel_type) {
+        let rel_type = string_to_synset_rel_type(&rel_str).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let relation = SynsetRelation { target, rel_type };
+        if !synset.synset_relations.contains(&relation) {
+            synset.synset_relations.push(relation);
+        }
+    }
+    Ok(())
+}
+
+/// Fills in an entry's lemma tags, variant forms and entry-level syntactic behaviours.
+fn fetch_entry_details(conn: &Connection, entry: &mut LexicalEntry) -> Result<()> {
+    let mut form_stmt = conn.prepare_cached(
+        "SELECT id, written_form, script FROM forms WHERE entry_id = ?1 ORDER BY form_index",
+    )?;
+    entry.forms = form_stmt
+        .query_map(params![entry.id], |row| {
+            Ok(Form {
+                id: row.get(0)?,
+                written_form: row.get(1)?,
+                script: row.get(2)?,
+                pronunciations: Vec::new(),
+                tags: Vec::new(),
+            })
+        })?
+        .collect::<std::result::Result<_, _>>()?;
+
+    let mut tag_stmt = conn.prepare_cached(
+        "SELECT form_index, category, text FROM tags WHERE entry_id = ?1 ORDER BY rowid",
+    )?;
+    let tags = tag_stmt.query_map(params![entry.id], |row| {
+        let form_index: Option<usize> = row.get(0)?;
+        let tag = Tag {
+            category: row.get(1)?,
+            text: row.get(2)?,
+        };
+        Ok((form_index, tag))
+    })?;
+    for tag in tags {
+        match tag? {
+            (None, tag) => entry.lemma.tags.push(tag),
+            (Some(index), tag) => {
+                if let Some(form) = entry.forms.get_mut(index) {
+                    form.tags.push(tag);
+                }
+            }
+        }
+    }
+
+    if !entry.forms.is_empty() {
+        let mut pron_stmt = conn.prepare_cached(
+            "SELECT form_index, variety, notation, phonemic, audio, text
+             FROM pronunciations
+             WHERE entry_id = ?1 AND form_index IS NOT NULL
+             ORDER BY rowid",
+        )?;
+        let prons = pron_stmt.query_map(params![entry.id], |row| {
+            let form_index: usize = row.get(0)?;
+            let pron = Pronunciation {
+                variety: row.get(1)?,
+                notation: row.get(2)?,
+                phonemic: row.get(3)?,
+                audio: row.get(4)?,
+                text: row.get(5)?,
+            };
+            Ok((form_index, pron))
+        })?;
+        for pron in prons {
+            let (index, pron) = pron?;
+            if let Some(form) = entry.forms.get_mut(index) {
+                form.pronunciations.push(pron);
+            }
+        }
+    }
+
+    let mut behaviour_stmt = conn.prepare_cached(
+        "SELECT id, subcategorization_frame, senses FROM syntactic_behaviours
+         WHERE entry_id = ?1 ORDER BY rowid",
+    )?;
+    entry.syntactic_behaviours = behaviour_stmt
+        .query_map(params![entry.id], row_to_syntactic_behaviour)?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(())
+}
+
+/// Columns of the synset attributes read by [`read_synset_attributes`].
+const SYNSET_ATTRIBUTE_COLUMNS: &str = "lexfile, lexicalized,
+    dc_contributor, dc_coverage, dc_creator, dc_date, dc_description, dc_format,
+    dc_identifier, dc_publisher, dc_relation, dc_rights, dc_source, dc_subject,
+    dc_title, dc_type, status, note, confidence_score";
+
+/// Fills in a synset's members, lexicographer file, lexicalized flag and metadata.
+fn fetch_synset_attributes(conn: &Connection, synset: &mut Synset) -> Result<()> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {SYNSET_ATTRIBUTE_COLUMNS} FROM synsets WHERE id = ?1"
+    ))?;
+    let synset_id = synset.id.clone();
+    stmt.query_row(params![synset_id], |row| {
+        read_synset_attributes(synset, row)
+    })?;
+
+    let mut member_stmt = conn.prepare_cached(
+        "SELECT sense_id FROM synset_members WHERE synset_id = ?1 ORDER BY position",
+    )?;
+    let members = member_stmt
+        .query_map(params![synset_id], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    synset.members = members.join(" ");
+    Ok(())
+}
+
+/// Reads the [`SYNSET_ATTRIBUTE_COLUMNS`], from the first column on, into a synset.
+fn read_synset_attributes(
+    synset: &mut Synset,
+    row: &Row,
+) -> std::result::Result<(), rusqlite::Error> {
+    synset.lexfile = row.get(0)?;
+    synset.lexicalized = row.get(1)?;
+    synset.metadata = Metadata {
+        dc_contributor: row.get(2)?,
+        dc_coverage: row.get(3)?,
+        dc_creator: row.get(4)?,
+        dc_date: row.get(5)?,
+        dc_description: row.get(6)?,
+        dc_format: row.get(7)?,
+        dc_identifier: row.get(8)?,
+        dc_publisher: row.get(9)?,
+        dc_relation: row.get(10)?,
+        dc_rights: row.get(11)?,
+        dc_source: row.get(12)?,
+        dc_subject: row.get(13)?,
+        dc_title: row.get(14)?,
+        dc_type: row.get(15)?,
+        status: row.get(16)?,
+        note: row.get(17)?,
+        confidence_score: row.get(18)?,
+    };
+    Ok(())
+}
+
+fn row_to_syntactic_behaviour(
+    row: &Row,
+) -> std::result::Result<SyntacticBehaviour, rusqlite::Error> {
+    Ok(SyntacticBehaviour {
+        id: row.get(0)?,
+        subcategorization_frame: row.get(1)?,
+        senses: row.get(2)?,
+    })
+}
 
 #[cfg(test)]
 mod tests {
-    // Tests need to be rewritten for the SQLite backend.
-
     use super::*;
 
+    use std::io::Write;
     use tempfile::tempdir;
 
-    // Placeholder test function
+    const TEST_LMF_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="cat" partOfSpeech="n"/>
+      <Sense id="s1" synset="syn1"/>
+    </LexicalEntry>
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="feline" partOfSpeech="n"/>
+      <Sense id="s2" synset="syn2"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="s1">
+      <Definition>A feline animal</Definition>
+      <SynsetRelation relType="hypernym" target="syn2"/>
+    </Synset>
+    <Synset id="syn2" partOfSpeech="n" members="s2">
+      <Definition>Any animal of the cat family</Definition>
+      <SynsetRelation relType="hyponym" target="syn1"/>
+    </Synset>
+  </Lexicon>
+</LexicalResource>"#;
+
+    /// A test synset: its ID, part of speech and `(relType, target)` relations.
+    type TestSynset<'a> = (&'a str, &'a str, &'a [(&'a str, &'a str)]);
+
+    /// Builds an LMF document from synsets with no members, and raw `entries` XML placed
+    /// before them.
+    fn lmf_xml(synsets: &[TestSynset], entries: &str) -> String {
+        let mut items = String::new();
+        for (id, pos, relations) in synsets {
+            let relations: String = relations
+                .iter()
+                .map(|(rel_type, target)| {
+                    format!(r#"<SynsetRelation relType="{rel_type}" target="{target}"/>"#)
+                })
+                .collect();
+            items.push_str(&format!(
+                r#"<Synset id="{id}" partOfSpeech="{pos}" members="">{relations}</Synset>"#
+            ));
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    {entries}
+    {items}
+  </Lexicon>
+</LexicalResource>"#
+        )
+    }
+
+    /// Loads `xml` from a gzipped source file into a fresh database.
+    async fn load_test_wordnet(dir: &Path, xml: &str) -> WordNet {
+        let source = dir.join("test.xml.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&source).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(xml.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let options = LoadOptions {
+            db_path: Some(dir.join("test.db")),
+            source: Some(source),
+            offline: true,
+            ..Default::default()
+        };
+        WordNet::load_with_options(options, None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_load_from_gz_source() {
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), TEST_LMF_XML).await;
+
+        let entries = wn.lookup_entries("Cat", None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].lemma.written_form, "cat");
+        assert_eq!(entries[0].senses[0].synset, "syn1");
+
+        let synset = wn.get_synset("syn1").unwrap();
+        assert_eq!(synset.definitions[0].text, "A feline animal");
+        let hypernyms = wn
+            .get_related_synsets("syn1", SynsetRelType::Hypernym)
+            .unwrap();
+        assert_eq!(hypernyms[0].id, "syn2");
+    }
+
+    #[tokio::test]
+    async fn test_round_trips_forms_and_syntactic_behaviours() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="run" partOfSpeech="v">
+        <Pronunciation variety="en-GB-fonipa">rʌn</Pronunciation>
+        <Tag category="register">neutral</Tag>
+      </Lemma>
+      <Form writtenForm="ran">
+        <Pronunciation variety="en-GB-fonipa">ræn</Pronunciation>
+        <Tag category="tense">past</Tag>
+      </Form>
+      <Sense id="s1" synset="syn1" n="1" subcat="vframe-2"><Count>7</Count><Count>3</Count></Sense>
+      <Sense id="s2" synset="syn2" adjposition="ip" lexicalized="false"/>
+      <SyntacticBehaviour subcategorizationFrame="Somebody ----s" senses="s2"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="v" members="s1" lexfile="verb.motion" dc:source="PWN">
+      <Definition>move fast</Definition>
+    </Synset>
+    <Synset id="syn2" partOfSpeech="v" members="s2" lexicalized="false"/>
+    <SyntacticBehaviour id="vframe-1" subcategorizationFrame="Something ----s"/>
+    <SyntacticBehaviour id="vframe-2" subcategorizationFrame="Somebody ----s something"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let entry = &wn.lookup_entries("run", None).unwrap()[0];
+        assert_eq!(entry.pronunciations.len(), 1);
+        assert_eq!(entry.lemma.tags[0].text, "neutral");
+        assert_eq!(entry.forms[0].written_form, "ran");
+        assert_eq!(entry.forms[0].pronunciations[0].text, "ræn");
+        assert_eq!(entry.forms[0].tags[0].text, "past");
+        assert_eq!(entry.syntactic_behaviours.len(), 1);
+
+        let sense = entry.senses.iter().find(|s| s.id == "s1").unwrap();
+        assert_eq!(sense.n, Some(1));
+        assert_eq!(sense.counts.iter().map(|c| c.value).sum::<u32>(), 10);
+        let sense = entry.senses.iter().find(|s| s.id == "s2").unwrap();
+        assert_eq!(sense.adjposition, Some(AdjPosition::Ip));
+        assert!(!sense.lexicalized);
+
+        let synset = wn.get_synset("syn1").unwrap();
+        assert_eq!(synset.lexfile.as_deref(), Some("verb.motion"));
+        assert_eq!(synset.metadata.dc_source.as_deref(), Some("PWN"));
+        assert!(!wn.get_synset("syn2").unwrap().lexicalized);
+
+        let frames = wn.get_syntactic_behaviours("s1").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].subcategorization_frame,
+            "Somebody ----s something"
+        );
+        let frames = wn.get_syntactic_behaviours("s2").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].subcategorization_frame, "Somebody ----s");
+    }
+
+    #[tokio::test]
+    async fn test_relation_types_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="bake" partOfSpeech="v"/>
+      <Sense id="s1" synset="syn1">
+        <SenseRelation relType="other" dc:type="agent" target="s2"/>
+        <SenseRelation relType="has_domain_topic" target="s2"/>
+        <SenseRelation relType="other" dc:type="body_part" target="s3"/>
+        <SenseRelation relType="metonym" target="s3"/>
+      </Sense>
+    </LexicalEntry>
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="baker" partOfSpeech="n"/>
+      <Sense id="s2" synset="syn2"/>
+    </LexicalEntry>
+    <LexicalEntry id="w3">
+      <Lemma writtenForm="hand" partOfSpeech="n"/>
+      <Sense id="s3" synset="syn2"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="v" members="s1">
+      <SynsetRelation relType="frobnicates" target="syn2"/>
+    </Synset>
+    <Synset id="syn2" partOfSpeech="n" members="s2 s3"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let sense = &wn.get_senses_for_entry("w1").unwrap()[0];
+        let rel_types: Vec<&SenseRelType> =
+            sense.sense_relations.iter().map(|r| &r.rel_type).collect();
+        assert_eq!(
+            rel_types,
+            [
+                &SenseRelType::Agent,
+                &SenseRelType::HasDomainTopic,
+                &SenseRelType::BodyPart,
+                &SenseRelType::Metonym,
+            ]
+        );
+        assert_eq!(
+            wn.get_related_senses("s1", SenseRelType::Agent).unwrap()[0].id,
+            "s2"
+        );
+
+        let unknown = SynsetRelType::Unrecognized("frobnicates".to_string());
+        let synset = wn.get_synset("syn1").unwrap();
+        assert_eq!(synset.synset_relations[0].rel_type, unknown);
+        assert_eq!(
+            wn.get_related_synsets("syn1", unknown).unwrap()[0].id,
+            "syn2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_results_follow_source_order() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="bank" partOfSpeech="n"/>
+      <Sense id="s-z" synset="syn2"/>
+      <Sense id="s-a" synset="syn1"/>
+      <Sense id="s-m" synset="syn3"/>
+    </LexicalEntry>
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="bank" partOfSpeech="v"/>
+      <Sense id="v-2" synset="syn1" n="2"/>
+      <Sense id="v-1" synset="syn1" n="1"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="v-2 s-a v-1">
+      <Definition>second definition listed first</Definition>
+      <Definition>another definition</Definition>
+      <SynsetRelation relType="hyponym" target="syn3"/>
+      <SynsetRelation relType="hyponym" target="syn2"/>
+      <Example>banked on it</Example>
+      <Example>banked the fire</Example>
+      <Example>banked on it</Example>
+    </Synset>
+    <Synset id="syn2" partOfSpeech="n" members="s-z"/>
+    <Synset id="syn3" partOfSpeech="n" members="s-m"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let ids = |senses: &[Sense]| senses.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+
+        let entries = wn.lookup_entries("bank", None).unwrap();
+        assert_eq!(entries[0].id, "w2");
+        assert_eq!(ids(&entries[0].senses), ["s-z", "s-a", "s-m"]);
+        assert_eq!(ids(&entries[1].senses), ["v-1", "v-2"]);
+        assert_eq!(
+            ids(&wn.get_senses_for_entry("w2").unwrap()),
+            ["s-z", "s-a", "s-m"]
+        );
+        let all: Vec<String> = wn
+            .all_entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(all, ["w2", "w1"]);
+
+        assert_eq!(
+            ids(&wn.get_senses_for_synset("syn1").unwrap()),
+            ["v-2", "s-a", "v-1"]
+        );
+        let synset = wn.get_synset("syn1").unwrap();
+        assert_eq!(synset.members, "v-2 s-a v-1");
+        assert_eq!(synset.definitions[0].text, "second definition listed first");
+        assert_eq!(synset.definitions.len(), 2);
+        // Repeated glosses are kept, the join fan-out is not
+        let examples: Vec<&str> = synset.examples.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(
+            examples,
+            ["banked on it", "banked the fire", "banked on it"]
+        );
+        let hyponyms: Vec<String> = wn
+            .get_related_synsets("syn1", SynsetRelType::Hyponym)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(hyponyms, ["syn3", "syn2"]);
+    }
+
+    #[tokio::test]
+    async fn test_lemmatize_and_morphy_fallback() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="goose-n">
+      <Lemma writtenForm="goose" partOfSpeech="n"/>
+      <Form writtenForm="geese"/>
+      <Sense id="goose-n-1" synset="syn1"/>
+    </LexicalEntry>
+    <LexicalEntry id="run-v">
+      <Lemma writtenForm="run" partOfSpeech="v"/>
+      <Form writtenForm="ran"/>
+      <Sense id="run-v-1" synset="syn2"/>
+    </LexicalEntry>
+    <LexicalEntry id="running-n">
+      <Lemma writtenForm="running" partOfSpeech="n"/>
+      <Sense id="running-n-1" synset="syn3"/>
+    </LexicalEntry>
+    <LexicalEntry id="church-n">
+      <Lemma writtenForm="church" partOfSpeech="n"/>
+      <Sense id="church-n-1" synset="syn1"/>
+    </LexicalEntry>
+    <LexicalEntry id="big-s">
+      <Lemma writtenForm="big" partOfSpeech="s"/>
+      <Sense id="big-s-1" synset="syn4"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="goose-n-1 church-n-1"/>
+    <Synset id="syn2" partOfSpeech="v" members="run-v-1"/>
+    <Synset id="syn3" partOfSpeech="n" members="running-n-1"/>
+    <Synset id="syn4" partOfSpeech="s" members="big-s-1"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        assert_eq!(
+            wn.lemmatize("Geese", None).unwrap(),
+            [("goose".to_string(), PartOfSpeech::N)]
+        );
+        assert_eq!(
+            wn.lemmatize("churches", Some(PartOfSpeech::N)).unwrap(),
+            [("church".to_string(), PartOfSpeech::N)]
+        );
+        assert_eq!(
+            wn.lemmatize("running", None).unwrap(),
+            [
+                ("running".to_string(), PartOfSpeech::N),
+                ("run".to_string(), PartOfSpeech::V)
+            ]
+        );
+        assert!(
+            wn.lemmatize("ran", Some(PartOfSpeech::N))
+                .unwrap()
+                .is_empty()
+        );
+
+        // Exact matching unless the fallback is enabled
+        assert!(wn.lookup_entries("geese", None).unwrap().is_empty());
+        let wn = wn.with_morphy_fallback(true);
+        assert_eq!(wn.lookup_entries("geese", None).unwrap()[0].id, "goose-n");
+        assert_eq!(wn.lookup_entries("ran", None).unwrap()[0].id, "run-v");
+        let ids: Vec<String> = wn
+            .lookup_entries("running", None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, ["running-n", "run-v"]);
+        let ids: Vec<String> = wn
+            .lookup_entries("running", Some(PartOfSpeech::V))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, ["run-v"]);
+
+        // An adjective filter includes satellites, with or without the fallback
+        for word in ["big", "bigger"] {
+            let entries = wn.lookup_entries(word, Some(PartOfSpeech::A)).unwrap();
+            assert_eq!(entries[0].id, "big-s", "{}", word);
+        }
+        let lookups = wn
+            .lookup_many(&["big", "bigger"], Some(PartOfSpeech::A))
+            .unwrap();
+        assert!(lookups.iter().all(|l| l.entries[0].id == "big-s"));
+        let wn = wn.with_morphy_fallback(false);
+        assert_eq!(
+            wn.lookup_entries("big", Some(PartOfSpeech::A)).unwrap()[0].id,
+            "big-s"
+        );
+        assert!(
+            wn.lookup_entries("big", Some(PartOfSpeech::N))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_many() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="run-v">
+      <Lemma writtenForm="run" partOfSpeech="v">
+        <Pronunciation variety="en-GB-fonipa">rʌn</Pronunciation>
+        <Tag category="register">neutral</Tag>
+      </Lemma>
+      <Form writtenForm="ran">
+        <Pronunciation variety="en-GB-fonipa">ræn</Pronunciation>
+        <Tag category="tense">past</Tag>
+      </Form>
+      <Sense id="run-v-1" synset="syn-run" n="1"><Count>4</Count></Sense>
+      <Sense id="run-v-2" synset="syn-operate">
+        <SenseRelation relType="derivation" target="running-n-1"/>
+      </Sense>
+      <SyntacticBehaviour subcategorizationFrame="Somebody ----s"/>
+    </LexicalEntry>
+    <LexicalEntry id="run-n">
+      <Lemma writtenForm="run" partOfSpeech="n"/>
+      <Sense id="run-n-1" synset="syn-score"/>
+    </LexicalEntry>
+    <LexicalEntry id="running-n">
+      <Lemma writtenForm="running" partOfSpeech="n"/>
+      <Sense id="running-n-1" synset="syn-operate-n"/>
+    </LexicalEntry>
+    <LexicalEntry id="operate-v">
+      <Lemma writtenForm="operate" partOfSpeech="v"/>
+      <Sense id="operate-v-1" synset="syn-operate"/>
+    </LexicalEntry>
+    <Synset id="syn-run" partOfSpeech="v" members="run-v-1" lexfile="verb.motion" dc:source="PWN">
+      <Definition>move fast</Definition>
+      <Example>run home</Example>
+    </Synset>
+    <Synset id="syn-operate" partOfSpeech="v" members="operate-v-1 run-v-2">
+      <Definition>direct or control</Definition>
+      <SynsetRelation relType="hypernym" target="syn-run"/>
+    </Synset>
+    <Synset id="syn-score" partOfSpeech="n" members="run-n-1"/>
+    <Synset id="syn-operate-n" partOfSpeech="n" members="running-n-1"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let exact = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let words = ["Run", "ran", "running", "operate", "xyzzy", "run"];
+        let results = exact.lookup_many(&words, None).unwrap();
+        assert_eq!(results.len(), words.len());
+        assert_eq!(results[0].word, "Run");
+        assert!(!results[1].is_found());
+        assert!(!results[4].is_found());
+        assert!(results[4].synsets.is_empty());
+        let synset_ids: Vec<&str> = results[0].synsets.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(synset_ids, ["syn-run", "syn-operate", "syn-score"]);
+        assert_eq!(
+            results[3].synset("syn-operate").unwrap().members,
+            "operate-v-1 run-v-2"
+        );
+
+        // Same entries and synsets as looking each word up, with or without the fallback
+        let mut wn = exact;
+        for morphy_fallback in [false, true] {
+            wn = wn.with_morphy_fallback(morphy_fallback);
+            for pos in [None, Some(PartOfSpeech::V)] {
+                for (word, result) in words.iter().zip(wn.lookup_many(&words, pos).unwrap()) {
+                    let entries = wn.lookup_entries(word, pos).unwrap();
+                    assert_eq!(result.entries, entries, "entries of {}", word);
+                    let mut synsets: Vec<Synset> = Vec::new();
+                    for sense in entries.iter().flat_map(|entry| &entry.senses) {
+                        if !synsets.iter().any(|synset| synset.id == sense.synset) {
+                            synsets.push(wn.get_synset(&sense.synset).unwrap());
+                        }
+                    }
+                    assert_eq!(result.synsets, synsets, "synsets of {}", word);
+                }
+            }
+        }
+        let results = wn.lookup_many(&["ran"], None).unwrap();
+        assert_eq!(results[0].entries[0].id, "run-v");
+    }
+
+    #[tokio::test]
+    async fn test_search_glosses() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="chlorophyll" partOfSpeech="n"/>
+      <Sense id="s1" synset="syn1"/>
+    </LexicalEntry>
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="photosynthesize" partOfSpeech="v"/>
+      <Sense id="s2" synset="syn2"/>
+    </LexicalEntry>
+    <LexicalEntry id="w3">
+      <Lemma writtenForm="leaf" partOfSpeech="n"/>
+      <Sense id="s3" synset="syn3"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="s1">
+      <Definition>a green pigment that absorbs light</Definition>
+      <Example>plants need it for photosynthesis</Example>
+    </Synset>
+    <Synset id="syn2" partOfSpeech="v" members="s2">
+      <Definition>carry out photosynthesis using light energy</Definition>
+    </Synset>
+    <Synset id="syn3" partOfSpeech="n" members="s3">
+      <Definition>the main organ of photosynthesis in plants</Definition>
+    </Synset>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let hits = wn.search_glosses("photosynthesis", None, 10).unwrap();
+        let ids: Vec<&str> = hits.iter().map(|h| h.synset.id.as_str()).collect();
+        assert_eq!(ids.len(), 3);
+        // A match in an example ranks below matches in definitions
+        assert_eq!(ids[2], "syn1");
+        assert!(hits[0].score >= hits[1].score);
+        assert!(hits[0].snippet.contains("[photosynthesis]"));
+        assert_eq!(hits[0].synset.definitions.len(), 1);
+
+        let hits = wn
+            .search_glosses("photosynthesis", Some(PartOfSpeech::V), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].synset.id, "syn2");
+
+        // Words are stemmed, all must match, and a trailing '*' matches a prefix
+        assert_eq!(wn.search_glosses("absorbing", None, 10).unwrap().len(), 1);
+        assert_eq!(
+            wn.search_glosses("light plants", None, 10).unwrap().len(),
+            1
+        );
+        assert_eq!(wn.search_glosses("pigm*", None, 10).unwrap().len(), 1);
+        assert_eq!(
+            wn.search_glosses("photosynthesis", None, 1).unwrap().len(),
+            1
+        );
+        assert!(wn.search_glosses("\"-( OR", None, 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_complete() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="ice cream" partOfSpeech="n"/>
+      <Sense id="s1" synset="syn1"><Count>7</Count></Sense>
+    </LexicalEntry>
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="cream" partOfSpeech="n"/>
+      <Sense id="s2" synset="syn2"/>
+      <Sense id="s3" synset="syn3"/>
+    </LexicalEntry>
+    <LexicalEntry id="w3">
+      <Lemma writtenForm="cream" partOfSpeech="v"/>
+      <Sense id="s4" synset="syn4"/>
+    </LexicalEntry>
+    <LexicalEntry id="w4">
+      <Lemma writtenForm="creamy" partOfSpeech="a"/>
+      <Sense id="s5" synset="syn5"><Count>3</Count></Sense>
+    </LexicalEntry>
+    <LexicalEntry id="w5">
+      <Lemma writtenForm="Creation" partOfSpeech="n"/>
+      <Sense id="s6" synset="syn6"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="s1"/>
+    <Synset id="syn2" partOfSpeech="n" members="s2"/>
+    <Synset id="syn3" partOfSpeech="n" members="s3"/>
+    <Synset id="syn4" partOfSpeech="v" members="s4"/>
+    <Synset id="syn5" partOfSpeech="a" members="s5"/>
+    <Synset id="syn6" partOfSpeech="n" members="s6"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let lemmas = |completions: Vec<Completion>| -> Vec<String> {
+            completions.into_iter().map(|c| c.lemma).collect()
+        };
+
+        // Whole-lemma matches first, by tag count, then sense count; "ice cream" by its
+        // second word
+        let completions = wn.complete("Cre", None, 10).unwrap();
+        assert_eq!(
+            completions[1],
+            Completion {
+                lemma: "cream".to_string(),
+                parts_of_speech: vec![PartOfSpeech::N, PartOfSpeech::V],
+                sense_count: 3,
+                tag_count: 0,
+            }
+        );
+        assert_eq!(
+            lemmas(completions),
+            ["creamy", "cream", "Creation", "ice cream"]
+        );
+
+        assert_eq!(
+            lemmas(wn.complete("cream", Some(PartOfSpeech::N), 10).unwrap()),
+            ["cream", "ice cream"]
+        );
+        assert_eq!(
+            lemmas(wn.complete("ice c", None, 10).unwrap()),
+            ["ice cream"]
+        );
+        assert_eq!(wn.complete("cre", None, 2).unwrap().len(), 2);
+        assert!(wn.complete("  ", None, 10).unwrap().is_empty());
+        assert!(wn.complete("x", None, 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_lemmas() {
+        let mut entries = String::new();
+        for (i, (lemma, pos)) in [
+            ("cat", "n"),
+            ("cot", "n"),
+            ("coat", "n"),
+            ("Cut", "v"),
+            ("zoology", "n"),
+            ("unbelievable", "a"),
+            ("unable", "s"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            entries.push_str(&format!(
+                r#"<LexicalEntry id="w{i}"><Lemma writtenForm="{lemma}" partOfSpeech="{pos}"/><Sense id="s{i}" synset="syn{i}"/></LexicalEntry>
+                <Synset id="syn{i}" partOfSpeech="{pos}" members="s{i}"/>"#
+            ));
+        }
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    {entries}
+  </Lexicon>
+</LexicalResource>"#
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+
+        let lemmas = |pattern: &str, mode: PatternMode, pos: Option<PartOfSpeech>| {
+            wn.search_lemmas(pattern, mode, pos)
+                .unwrap()
+                .map(|m| m.unwrap().lemma)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lemmas("c?t", PatternMode::Glob, None),
+            ["cat", "cot", "Cut"]
+        );
+        assert_eq!(
+            lemmas("C?T", PatternMode::Glob, Some(PartOfSpeech::N)),
+            ["cat", "cot"]
+        );
+        assert_eq!(lemmas("*ology", PatternMode::Glob, None), ["zoology"]);
+        assert_eq!(
+            lemmas("olog", PatternMode::Glob, None),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            lemmas("^un.*able$", PatternMode::Regex, None),
+            ["unbelievable", "unable"]
+        );
+        assert_eq!(
+            lemmas("^un", PatternMode::Regex, Some(PartOfSpeech::A)),
+            ["unbelievable", "unable"]
+        );
+        assert_eq!(
+            lemmas("^c.a?t$", PatternMode::Regex, None),
+            ["cat", "cot", "coat", "Cut"]
+        );
+
+        let first = wn
+            .search_lemmas("c*", PatternMode::Glob, None)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.entry_id, "w0");
+        assert_eq!(first.part_of_speech, PartOfSpeech::N);
+        // The database is not locked between batches
+        assert!(wn.get_entry_by_id(&first.entry_id).unwrap().is_some());
+
+        assert!(matches!(
+            wn.search_lemmas("(", PatternMode::Regex, None),
+            Err(OewnError::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_suggest() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="receive" partOfSpeech="v"/>
+      <Sense id="s1" synset="syn1"/>
+    </LexicalEntry>
+    <LexicalEntry id="w2">
+      <Lemma writtenForm="relieve" partOfSpeech="v"/>
+      <Sense id="s2" synset="syn2"><Count>4</Count></Sense>
+    </LexicalEntry>
+    <LexicalEntry id="w3">
+      <Lemma writtenForm="deceive" partOfSpeech="v"/>
+      <Sense id="s3" synset="syn3"/>
+    </LexicalEntry>
+    <LexicalEntry id="w4">
+      <Lemma writtenForm="the" partOfSpeech="x"/>
+      <Sense id="s4" synset="syn4"/>
+    </LexicalEntry>
+    <LexicalEntry id="w5">
+      <Lemma writtenForm="Paris" partOfSpeech="n"/>
+      <Sense id="s5" synset="syn5"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="v" members="s1"/>
+    <Synset id="syn2" partOfSpeech="v" members="s2"/>
+    <Synset id="syn3" partOfSpeech="v" members="s3"/>
+    <Synset id="syn4" partOfSpeech="x" members="s4"/>
+    <Synset id="syn5" partOfSpeech="n" members="s5"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        // At the same distance, the lemma with the higher tag count comes first
+        let suggestions = wn.suggest("recieve", 2, 10).unwrap();
+        let lemmas: Vec<&str> = suggestions.iter().map(|s| s.lemma.as_str()).collect();
+        assert_eq!(lemmas, ["relieve", "receive", "deceive"]);
+        assert_eq!(
+            suggestions[2],
+            Suggestion {
+                lemma: "deceive".to_string(),
+                distance: 2
+            }
+        );
+
+        assert_eq!(wn.suggest("recieve", 1, 10).unwrap().len(), 2);
+        assert_eq!(wn.suggest("recieve", 2, 2).unwrap().len(), 2);
+        assert_eq!(wn.suggest("teh", 1, 10).unwrap()[0].lemma, "the");
+        assert_eq!(wn.suggest("PARSI", 1, 10).unwrap()[0].lemma, "Paris");
+        assert!(wn.suggest("xylophone", 2, 10).unwrap().is_empty());
+        assert!(wn.suggest(" ", 2, 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_suggestion_candidates_are_bounded() {
+        // Many lemmas sharing only their first trigrams with the misspelling, some of
+        // them short enough to share nothing but those
+        let alphabet = b"abdfghjklmnopqtuwxyz";
+        let mut entries = String::new();
+        let mut synsets = String::new();
+        for i in 0..300 {
+            // Distinct letters, so that every trigram of the lemma is distinct
+            let mut letters: Vec<char> = (0..8)
+                .map(|k| alphabet[(i + k) % alphabet.len()] as char)
+                .collect();
+            letters.swap(0, 1 + (i / alphabet.len()) % 7);
+            let suffix: String = letters.into_iter().collect();
+            let lemma = if i % 2 == 0 {
+                format!("re{}", suffix)
+            } else {
+                format!("re{}", &suffix[..1])
+            };
+            entries.push_str(&format!(
+                r#"<LexicalEntry id="w{i}"><Lemma writtenForm="{lemma}" partOfSpeech="n"/><Sense id="s{i}" synset="syn{i}"/></LexicalEntry>"#
+            ));
+            synsets.push_str(&format!(
+                r#"<Synset id="syn{i}" partOfSpeech="n" members="s{i}"/>"#
+            ));
+        }
+        entries.push_str(
+            r#"<LexicalEntry id="receives"><Lemma writtenForm="receives" partOfSpeech="v"/><Sense id="r1" synset="r1"/></LexicalEntry>"#,
+        );
+        entries.push_str(&synsets);
+        let xml = lmf_xml(&[("r1", "v", &[])], &entries);
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+
+        let conn = wn.conn.lock().unwrap();
+        let candidates = search::suggestion_candidates(&conn, "recieves", 2).unwrap();
+        let lemmas: Vec<&str> = candidates.iter().map(|c| c.1.as_str()).collect();
+        assert_eq!(lemmas, ["receives"]);
+        drop(conn);
+        assert_eq!(wn.suggest("recieves", 2, 10).unwrap()[0].lemma, "receives");
+    }
+
+    #[tokio::test]
+    async fn test_hypernym_paths() {
+        let mut entries = String::new();
+        for (i, lemma) in ["entity", "animal", "canine", "pet", "dog", "Lassie", "loop"]
+            .iter()
+            .enumerate()
+        {
+            entries.push_str(&format!(
+                r#"<LexicalEntry id="w{i}"><Lemma writtenForm="{lemma}" partOfSpeech="n"/><Sense id="s{i}" synset="{lemma}"/></LexicalEntry>"#
+            ));
+        }
+        let xml = lmf_xml(
+            &[
+                ("entity", "n", &[]),
+                ("animal", "n", &[("hypernym", "entity")]),
+                ("canine", "n", &[("hypernym", "animal")]),
+                ("pet", "n", &[("hypernym", "animal"), ("hypernym", "loop")]),
+                ("dog", "n", &[("hypernym", "canine"), ("hypernym", "pet")]),
+                ("Lassie", "n", &[("instance_hypernym", "dog")]),
+                // A cycle through "pet" must not be followed back round
+                ("loop", "n", &[("hypernym", "pet")]),
+            ],
+            &entries,
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+
+        assert_eq!(
+            wn.hypernym_paths("Lassie").unwrap(),
+            [
+                vec!["entity", "animal", "canine", "dog", "Lassie"],
+                vec!["entity", "animal", "pet", "dog", "Lassie"],
+                vec!["loop", "pet", "dog", "Lassie"],
+            ]
+        );
+        assert_eq!(wn.min_depth("Lassie").unwrap(), 3);
+        assert_eq!(wn.max_depth("Lassie").unwrap(), 4);
+        assert_eq!(wn.root_hypernyms("Lassie").unwrap(), ["entity", "loop"]);
+
+        assert_eq!(wn.hypernym_paths("entity").unwrap(), [["entity"]]);
+        assert_eq!(wn.min_depth("entity").unwrap(), 0);
+        assert_eq!(wn.root_hypernyms("entity").unwrap(), ["entity"]);
+        assert!(matches!(
+            wn.hypernym_paths("missing"),
+            Err(OewnError::SynsetNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_similarity() {
+        // entity > animal > canine > dog, entity > animal > feline > cat, entity > frump
+        let senses = [
+            ("dog", "n", &["dog", "frump"][..]),
+            ("cat", "n", &["cat"][..]),
+            ("canine", "n", &["canine"][..]),
+            ("run", "v", &["run"][..]),
+        ];
+        let mut entries = String::new();
+        for (lemma, pos, synsets) in senses {
+            let senses: String = synsets
+                .iter()
+                .map(|synset| format!(r#"<Sense id="{lemma}-{synset}" synset="{synset}"/>"#))
+                .collect();
+            entries.push_str(&format!(
+                r#"<LexicalEntry id="w-{lemma}"><Lemma writtenForm="{lemma}" partOfSpeech="{pos}"/>{senses}</LexicalEntry>"#
+            ));
+        }
+        let xml = lmf_xml(
+            &[
+                ("entity", "n", &[]),
+                ("animal", "n", &[("hypernym", "entity")]),
+                ("canine", "n", &[("hypernym", "animal")]),
+                ("feline", "n", &[("hypernym", "animal")]),
+                ("dog", "n", &[("hypernym", "canine")]),
+                ("cat", "n", &[("hypernym", "feline")]),
+                ("frump", "n", &[("hypernym", "entity")]),
+                ("run", "v", &[]),
+            ],
+            &entries,
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+        let score =
+            |a: &str, b: &str, measure: SimilarityMeasure| wn.similarity(a, b, measure).unwrap();
+        let close = |actual: Option<f64>, expected: f64| {
+            assert!(
+                actual.is_some_and(|actual| (actual - expected).abs() < 1e-9),
+                "{:?} != {}",
+                actual,
+                expected
+            );
+        };
+
+        assert_eq!(
+            wn.lowest_common_hypernyms("dog", "cat").unwrap(),
+            ["animal"]
+        );
+        assert_eq!(
+            wn.lowest_common_hypernyms("dog", "canine").unwrap(),
+            ["canine"]
+        );
+        assert_eq!(
+            wn.lowest_common_hypernyms("dog", "frump").unwrap(),
+            ["entity"]
+        );
+        assert!(wn.lowest_common_hypernyms("dog", "run").unwrap().is_empty());
+
+        // dog and cat are 4 steps apart through animal; the noun taxonomy is 3 deep
+        close(score("dog", "cat", SimilarityMeasure::Path), 0.2);
+        close(score("dog", "dog", SimilarityMeasure::Path), 1.0);
+        close(
+            score("dog", "cat", SimilarityMeasure::LeacockChodorow),
+            -(5.0f64 / 6.0).ln(),
+        );
+        close(score("dog", "cat", SimilarityMeasure::WuPalmer), 0.5);
+        close(score("dog", "dog", SimilarityMeasure::WuPalmer), 1.0);
+        assert_eq!(score("dog", "run", SimilarityMeasure::Path), None);
+        assert_eq!(
+            score("dog", "run", SimilarityMeasure::LeacockChodorow),
+            None
+        );
+        assert_eq!(score("dog", "run", SimilarityMeasure::WuPalmer), None);
+        assert!(matches!(
+            wn.similarity("dog", "missing", SimilarityMeasure::Path),
+            Err(OewnError::SynsetNotFound(_))
+        ));
+
+        // The best pair of senses wins: dog (not frump) is one step below canine
+        close(
+            wn.word_similarity("dog", "canine", None, SimilarityMeasure::Path)
+                .unwrap(),
+            0.5,
+        );
+        assert_eq!(
+            wn.word_similarity("dog", "unknown", None, SimilarityMeasure::Path)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wu_palmer_multiple_inheritance() {
+        // root > x1 > x2 > x3 > p and root > p, with a and b below p
+        let xml = lmf_xml(
+            &[
+                ("root", "n", &[]),
+                ("x1", "n", &[("hypernym", "root")]),
+                ("x2", "n", &[("hypernym", "x1")]),
+                ("x3", "n", &[("hypernym", "x2")]),
+                ("p", "n", &[("hypernym", "x3"), ("hypernym", "root")]),
+                ("a", "n", &[("hypernym", "p")]),
+                ("b", "n", &[("hypernym", "p")]),
+            ],
+            "",
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+        let wup = |a: &str, b: &str| {
+            wn.similarity(a, b, SimilarityMeasure::WuPalmer)
+                .unwrap()
+                .unwrap()
+        };
+
+        // p is the deepest common hypernym, but x3 has the longest shortest path to the
+        // root, so NLTK scores a and b through x3: 2 * 4 / ((2 + 4) + (2 + 4))
+        assert_eq!(wn.lowest_common_hypernyms("a", "b").unwrap(), ["p"]);
+        assert!((wup("a", "b") - 2.0 / 3.0).abs() < 1e-9);
+        // The same holds when one synset is a hypernym of the other
+        assert!((wup("p", "a") - 8.0 / 11.0).abs() < 1e-9);
+        // Even for a synset and itself, as in NLTK
+        assert!((wup("a", "a") - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_information_content_similarity() {
+        // entity > animal > {dog, cat}, entity > rock
+        let mut entries = String::new();
+        for (lemma, count) in [("dog", 3), ("cat", 1), ("rock", 5)] {
+            entries.push_str(&format!(
+                r#"<LexicalEntry id="w-{lemma}"><Lemma writtenForm="{lemma}" partOfSpeech="n"/><Sense id="s-{lemma}" synset="{lemma}"><Count>{count}</Count></Sense></LexicalEntry>"#
+            ));
+        }
+        let xml = lmf_xml(
+            &[
+                ("entity", "n", &[]),
+                ("animal", "n", &[("hypernym", "entity")]),
+                ("dog", "n", &[("hypernym", "animal")]),
+                ("cat", "n", &[("hypernym", "animal")]),
+                ("rock", "n", &[("hypernym", "entity")]),
+            ],
+            &entries,
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+        let close = |actual: Option<f64>, expected: f64| {
+            assert!(
+                actual.is_some_and(|actual| (actual - expected).abs() < 1e-9),
+                "{:?} != {}",
+                actual,
+                expected
+            );
+        };
+
+        // With one smoothing occurrence per synset: dog 4, cat 2, animal 5, entity 10
+        close(wn.information_content("entity").unwrap(), 0.0);
+        close(wn.information_content("animal").unwrap(), 2.0f64.ln());
+        close(wn.information_content("dog").unwrap(), 2.5f64.ln());
+        close(wn.information_content("cat").unwrap(), 5.0f64.ln());
+        let score =
+            |a: &str, b: &str, measure: SimilarityMeasure| wn.similarity(a, b, measure).unwrap();
+        close(score("dog", "cat", SimilarityMeasure::Resnik), 2.0f64.ln());
+        close(
+            score("dog", "cat", SimilarityMeasure::Lin),
+            2.0 * 2.0f64.ln() / 12.5f64.ln(),
+        );
+        close(
+            score("dog", "cat", SimilarityMeasure::JiangConrath),
+            1.0 / 3.125f64.ln(),
+        );
+        close(score("dog", "rock", SimilarityMeasure::Resnik), 0.0);
+        assert_eq!(
+            score("dog", "dog", SimilarityMeasure::JiangConrath),
+            Some(f64::INFINITY)
+        );
+
+        // A word frequency list replaces the sense counts: dog 4, cat 1, animal 4 and
+        // entity 4, out of a total of 4
+        let frequencies = temp_dir.path().join("frequencies.txt");
+        std::fs::write(&frequencies, "# word count\ndog 3\ncat 0\nunknown 7\n").unwrap();
+        let stored = wn
+            .load_information_content(&FrequencySource::WordFrequencies(frequencies))
+            .unwrap();
+        assert_eq!(stored, 5);
+        close(wn.information_content("animal").unwrap(), 0.0);
+        close(wn.information_content("rock").unwrap(), 4.0f64.ln());
+
+        let invalid = temp_dir.path().join("invalid.txt");
+        std::fs::write(&invalid, "dog lots\n").unwrap();
+        assert!(matches!(
+            wn.load_information_content(&FrequencySource::WordFrequencies(invalid)),
+            Err(OewnError::ParseError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_traversal() {
+        // car has parts engine and wheel, engine has parts piston and wheel, and wheel
+        // has part spoke, which has part car to close a cycle
+        let entries = r#"<LexicalEntry id="w1"><Lemma writtenForm="hot" partOfSpeech="a"/>
+                 <Sense id="hot" synset="temp"><SenseRelation relType="antonym" target="cold"/><SenseRelation relType="similar" target="warm"/></Sense></LexicalEntry>
+               <LexicalEntry id="w2"><Lemma writtenForm="cold" partOfSpeech="a"/>
+                 <Sense id="cold" synset="temp"><SenseRelation relType="antonym" target="hot"/></Sense></LexicalEntry>
+               <LexicalEntry id="w3"><Lemma writtenForm="warm" partOfSpeech="a"/>
+                 <Sense id="warm" synset="temp"/></LexicalEntry>
+               <Synset id="temp" partOfSpeech="a" members="hot cold warm"/>"#;
+        let xml = lmf_xml(
+            &[
+                (
+                    "car",
+                    "n",
+                    &[
+                        ("mero_part", "engine"),
+                        ("mero_part", "wheel"),
+                        ("hypernym", "vehicle"),
+                    ],
+                ),
+                (
+                    "engine",
+                    "n",
+                    &[("mero_part", "piston"), ("mero_part", "wheel")],
+                ),
+                ("wheel", "n", &[("mero_part", "spoke")]),
+                ("spoke", "n", &[("mero_part", "car")]),
+                ("piston", "n", &[]),
+                ("vehicle", "n", &[]),
+            ],
+            entries,
+        );
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), &xml).await;
+        let parts = |max_depth: Option<usize>, order: TraversalOrder| -> Vec<(String, usize)> {
+            wn.traverse_synsets("car", &[SynsetRelType::MeroPart], max_depth, order)
+                .unwrap()
+                .map(|step| (step.id, step.depth))
+                .collect()
+        };
+        let ids = |steps: &[(&str, usize)]| -> Vec<(String, usize)> {
+            steps
+                .iter()
+                .map(|(id, depth)| (id.to_string(), *depth))
+                .collect()
+        };
+
+        // Each part once, at the depth it is first reached; the cycle back to car ends
+        assert_eq!(
+            parts(None, TraversalOrder::BreadthFirst),
+            ids(&[("engine", 1), ("wheel", 1), ("piston", 2), ("spoke", 2)])
+        );
+        assert_eq!(
+            parts(None, TraversalOrder::DepthFirst),
+            ids(&[("engine", 1), ("piston", 2), ("wheel", 2), ("spoke", 3)])
+        );
+        assert_eq!(
+            parts(Some(1), TraversalOrder::BreadthFirst),
+            ids(&[("engine", 1), ("wheel", 1)])
+        );
+        assert_eq!(
+            parts(Some(1), TraversalOrder::DepthFirst),
+            ids(&[("engine", 1), ("wheel", 1)])
+        );
+        // Spoke is within two relations through car → wheel, though wheel is first
+        // reached at the maximum depth through engine
+        assert_eq!(
+            parts(Some(2), TraversalOrder::BreadthFirst),
+            ids(&[("engine", 1), ("wheel", 1), ("piston", 2), ("spoke", 2)])
+        );
+        assert_eq!(
+            parts(Some(2), TraversalOrder::DepthFirst),
+            ids(&[("engine", 1), ("piston", 2), ("wheel", 2), ("spoke", 2)])
+        );
+        assert_eq!(
+            parts(Some(3), TraversalOrder::DepthFirst),
+            ids(&[("engine", 1), ("piston", 2), ("wheel", 2), ("spoke", 3)])
+        );
+        assert_eq!(parts(Some(0), TraversalOrder::BreadthFirst), Vec::new());
+
+        let steps: Vec<TraversalStep<SynsetRelType>> = wn
+            .traverse_synsets(
+                "car",
+                &[SynsetRelType::Hypernym, SynsetRelType::MeroPart],
+                Some(1),
+                TraversalOrder::BreadthFirst,
+            )
+            .unwrap()
+            .collect();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            steps[2],
+            TraversalStep {
+                id: "vehicle".to_string(),
+                depth: 1,
+                via: SynsetRelType::Hypernym
+            }
+        );
+
+        let senses: Vec<(String, SenseRelType)> = wn
+            .traverse_senses(
+                "cold",
+                &[SenseRelType::Antonym, SenseRelType::Similar],
+                None,
+                TraversalOrder::BreadthFirst,
+            )
+            .unwrap()
+            .map(|step| (step.id, step.via))
+            .collect();
+        assert_eq!(
+            senses,
+            [
+                ("hot".to_string(), SenseRelType::Antonym),
+                ("warm".to_string(), SenseRelType::Similar)
+            ]
+        );
+        assert!(
+            wn.traverse_synsets("car", &[], None, TraversalOrder::BreadthFirst)
+                .unwrap()
+                .next()
+                .is_none()
+        );
+        assert!(matches!(
+            wn.traverse_senses(
+                "missing",
+                &[SenseRelType::Antonym],
+                None,
+                TraversalOrder::DepthFirst
+            ),
+            Err(OewnError::SenseNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_shortest_path() {
+        // dog > canine > carnivore < feline < cat, with hyponyms stored downwards only
+        // from carnivore, and hot and cold as antonym senses
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w-dog"><Lemma writtenForm="dog" partOfSpeech="n"/><Sense id="dog-1" synset="t-dog"/></LexicalEntry>
+    <LexicalEntry id="w-cat"><Lemma writtenForm="cat" partOfSpeech="n"/><Sense id="cat-1" synset="t-cat"/></LexicalEntry>
+    <LexicalEntry id="w-hot"><Lemma writtenForm="hot" partOfSpeech="a"/>
+      <Sense id="hot-1" synset="t-hot"><SenseRelation relType="antonym" target="cold-1"/></Sense></LexicalEntry>
+    <LexicalEntry id="w-cold"><Lemma writtenForm="cold" partOfSpeech="a"/><Sense id="cold-1" synset="t-cold"/></LexicalEntry>
+    <Synset id="t-dog" partOfSpeech="n" members="dog-1"><SynsetRelation relType="hypernym" target="t-canine"/></Synset>
+    <Synset id="t-canine" partOfSpeech="n" members=""><SynsetRelation relType="hypernym" target="t-carnivore"/></Synset>
+    <Synset id="t-carnivore" partOfSpeech="n" members=""><SynsetRelation relType="hyponym" target="t-feline"/></Synset>
+    <Synset id="t-feline" partOfSpeech="n" members=""><SynsetRelation relType="hyponym" target="t-cat"/></Synset>
+    <Synset id="t-cat" partOfSpeech="n" members="cat-1"><SynsetRelation relType="hypernym" target="t-feline"/></Synset>
+    <Synset id="t-hot" partOfSpeech="a" members="hot-1"/>
+    <Synset id="t-cold" partOfSpeech="a" members="cold-1"/>
+  </Lexicon>
+</LexicalResource>"#;
+        let temp_dir = tempdir().unwrap();
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+
+        let path = wn.shortest_path("dog", "cat", &[]).unwrap().unwrap();
+        assert_eq!(
+            path.synsets,
+            ["t-dog", "t-canine", "t-carnivore", "t-feline", "t-cat"]
+        );
+        assert_eq!(path.len(), 4);
+        let rel_types: Vec<String> = path.edges.iter().map(|e| e.rel_type.to_string()).collect();
+        assert_eq!(rel_types, ["hypernym", "hypernym", "hyponym", "hyponym"]);
+        assert_eq!(
+            path.edges[3],
+            PathEdge {
+                source_synset: "t-feline".to_string(),
+                target_synset: "t-cat".to_string(),
+                rel_type: RelationType::Synset(SynsetRelType::Hyponym),
+                senses: None,
+            }
+        );
+
+        // Relations are followed in their stored direction only
+        assert_eq!(wn.shortest_path("cat", "dog", &[]).unwrap(), None);
+        assert_eq!(
+            wn.shortest_path("dog", "cat", &[SynsetRelType::Hypernym.into()])
+                .unwrap(),
+            None
+        );
+
+        let path = wn
+            .shortest_path("hot", "t-cold", &[SenseRelType::Antonym.into()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.synsets, ["t-hot", "t-cold"]);
+        assert_eq!(
+            path.edges[0].rel_type,
+            RelationType::Sense(SenseRelType::Antonym)
+        );
+        assert_eq!(
+            path.edges[0].senses,
+            Some(("hot-1".to_string(), "cold-1".to_string()))
+        );
+
+        let path = wn.shortest_path("dog", "t-dog", &[]).unwrap().unwrap();
+        assert!(path.is_empty());
+        assert_eq!(path.synsets, ["t-dog"]);
+        assert!(matches!(
+            wn.shortest_path("dog", "unicorn", &[]),
+            Err(OewnError::LexicalEntryNotFound(_))
+        ));
+    }
+
     #[tokio::test]
-    #[ignore] // Ignore until tests are rewritten
-    async fn test_sqlite_loading_placeholder() {
-        // Setup temp dir and db path
+    async fn test_lesk() {
+        // bank has a river sense and a money sense, listed first; the money sense's
+        // hyponym mentions loans
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w-bank"><Lemma writtenForm="bank" partOfSpeech="n"/>
+      <Sense id="bank-1" synset="t-money"/><Sense id="bank-2" synset="t-river"/></LexicalEntry>
+    <Synset id="t-money" partOfSpeech="n" members="bank-1">
+      <Definition>a financial institution that accepts deposits</Definition>
+      <Example>he cashed a check at the bank</Example>
+      <SynsetRelation relType="hyponym" target="t-lender"/>
+    </Synset>
+    <Synset id="t-lender" partOfSpeech="n" members="">
+      <Definition>an institution that makes loans</Definition>
+    </Synset>
+    <Synset id="t-river" partOfSpeech="n" members="bank-2">
+      <Definition>sloping land beside a body of water</Definition>
+      <Example>they pulled the canoe up on the bank</Example>
+    </Synset>
+  </Lexicon>
+</LexicalResource>"#;
         let temp_dir = tempdir().unwrap();
-        let db_path = temp_dir.path().join("test_load.db");
-
-        // Create dummy XML data (or use a small test fixture)
-        let _dummy_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
-            <LexicalResource>
-              <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
-                <LexicalEntry id="w1">
-                  <Lemma writtenForm="cat" partOfSpeech="n"/>
-                  <Sense id="s1" synset="syn1"/>
-                </LexicalEntry>
-                <Synset id="syn1" partOfSpeech="n">
-                  <Definition>A feline animal</Definition>
-                </Synset>
-              </Lexicon>
-            </LexicalResource>"#;
-
-        // Mock ensure_data to provide the dummy XML content path (tricky without modifying data.rs)
-        // For a real test, might need to write dummy XML to a temp file and point ensure_data there,
-        // or refactor ensure_data for testability.
-
-        // Test loading (this will likely fail until ensure_data is handled and methods implemented)
-        let _load_options = LoadOptions {
-            db_path: Some(db_path.clone()),
-            force_reload: true, // Force population for the test
+        let wn = load_test_wordnet(temp_dir.path(), xml).await;
+        let ranked = |context: &str, options: &LeskOptions| -> Vec<(String, usize)> {
+            wsd::lesk_with_options(&wn, context, "bank", Some(PartOfSpeech::N), options)
+                .unwrap()
+                .into_iter()
+                .map(|scored| (scored.synset.id, scored.score))
+                .collect()
         };
-        // let wn_result = WordNet::load_with_options(load_options).await;
-        // assert!(wn_result.is_ok());
-        // let wn = wn_result.unwrap();
-
-        // Add assertions here once query methods are implemented
-        // let entries = wn.lookup_entries("cat", None).unwrap();
-        // assert_eq!(entries.len(), 1);
-        // assert_eq!(entries[0].lemma.written_form, "cat");
-
-        // Test clear_database
-        // assert!(WordNet::clear_database(Some(db_path.clone())).is_ok());
-        // assert!(!db_path.exists());
+
+        let scored = wsd::lesk(
+            &wn,
+            "The canoe drifted to the bank of the water",
+            "bank",
+            None,
+        )
+        .unwrap();
+        assert_eq!(scored[0].synset.id, "t-river");
+        assert_eq!(scored[0].overlap, ["canoe", "water"]);
+        assert_eq!(scored[1].score, 0);
+
+        // Stopwords and the word itself do not count, so ties keep sense order
+        let defaults = LeskOptions::default();
+        assert_eq!(
+            ranked("the bank is at the end of a street", &defaults),
+            [("t-money".to_string(), 0), ("t-river".to_string(), 0)]
+        );
+        let with_stopwords = LeskOptions {
+            remove_stopwords: false,
+            ..Default::default()
+        };
+        assert_eq!(ranked("on the bank", &with_stopwords)[0].0, "t-river");
+
+        assert_eq!(ranked("the bank refused the loans", &defaults)[0].1, 0);
+        let extended = LeskOptions {
+            extended: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            ranked("the bank refused the loans", &extended),
+            [("t-money".to_string(), 1), ("t-river".to_string(), 0)]
+        );
+        assert!(
+            wsd::lesk(&wn, "anything", "unicorn", None)
+                .unwrap()
+                .is_empty()
+        );
     }
 }
@@ -3,22 +3,71 @@
 //! This CLI provides commands for looking up word definitions, viewing random words,
 //! and managing the WordNet database.
 
+mod output;
+mod shell;
+mod tree;
+
 use clap::{Parser, Subcommand};
 use colored::*;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use log::{LevelFilter, debug, error, info, warn};
 use oewn_rs::{
-    LexicalEntry, LoadOptions, SenseRelType, Synset, SynsetRelType, WordNet,
-    error::Result,
+    Edition, LemmaMatches, LexicalEntry, LoadOptions, PatternMode, RelationType, Sense,
+    SenseRelType, Synset, SynsetRelType, WordLookup, WordNet,
+    data::DownloadPolicy,
+    error::{OewnError, Result},
     models::PartOfSpeech,
     progress::{ProgressCallback, ProgressUpdate},
+    search,
+};
+use output::{
+    BATCH_TSV_HEADER, BatchOutput, ClearOutput, DefineOutput, ENTRY_TSV_HEADER, GlossRecord,
+    GrepOutput, LEMMA_TSV_HEADER, OutputFormat, PathOutput, PathRecord, PathSynsetRecord,
+    SearchOutput, pos_code, write_ndjson, write_records, write_tsv,
 };
 use std::collections::HashMap;
-use std::io::Write;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Maximum edit distance of the suggestions shown when a word is not found.
+const SUGGESTION_MAX_DISTANCE: usize = 2;
+/// Maximum number of suggestions shown when a word is not found.
+const SUGGESTION_LIMIT: usize = 5;
+/// Number of words of a batch looked up together, between writes of their records.
+const BATCH_CHUNK_SIZE: usize = 5000;
+
+/// Labels of the most common relation types; others are shown by name.
+const RELATION_LABELS: &[(&str, &str)] = &[
+    ("also", "See also"),
+    ("antonym", "Antonyms"),
+    ("attribute", "Attributes"),
+    ("causes", "Causes"),
+    ("derivation", "Derivationally related"),
+    ("domain_region", "Region domain"),
+    ("domain_topic", "Topic domain"),
+    ("entails", "Entails"),
+    ("exemplifies", "Usage domain"),
+    ("has_domain_region", "Region domain of"),
+    ("has_domain_topic", "Topic domain of"),
+    ("holo_member", "Member holonyms"),
+    ("holo_part", "Part holonyms"),
+    ("holo_substance", "Substance holonyms"),
+    ("hypernym", "Hypernyms"),
+    ("hyponym", "Hyponyms"),
+    ("instance_hypernym", "Instance of"),
+    ("instance_hyponym", "Instances"),
+    ("is_exemplified_by", "Usage domain of"),
+    ("mero_member", "Member meronyms"),
+    ("mero_part", "Part meronyms"),
+    ("mero_substance", "Substance meronyms"),
+    ("participle", "Participle of"),
+    ("pertainym", "Pertains to"),
+    ("similar", "Similar to"),
+];
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Open English WordNet CLI", long_about = None)]
 struct Cli {
@@ -33,6 +82,55 @@ struct Cli {
     #[arg(long, global = true, default_value_t = false)]
     force_reload: bool,
 
+    /// OEWN edition to use (e.g., 2021, or "latest")
+    #[arg(long, global = true, default_value_t = Edition::Latest)]
+    edition: Edition,
+
+    /// Load data from a local .xml, .xml.gz or .zip file (or a directory of cached files)
+    #[arg(long, global = true)]
+    source: Option<PathBuf>,
+
+    /// Never access the network; fail if the data is not available locally
+    #[arg(long, global = true, default_value_t = false)]
+    offline: bool,
+
+    /// Expected SHA-256 of the downloaded archive (overrides the release's pinned
+    /// checksum, if it has one)
+    #[arg(long, global = true)]
+    sha256: Option<String>,
+
+    /// Download the archive even if no SHA-256 is known to verify it against
+    #[arg(long, global = true, default_value_t = false)]
+    allow_unverified: bool,
+
+    /// Connection timeout for downloads, in seconds
+    #[arg(long, global = true, default_value_t = 30)]
+    connect_timeout: u64,
+
+    /// Read timeout for downloads, in seconds
+    #[arg(long, global = true, default_value_t = 60)]
+    read_timeout: u64,
+
+    /// Number of times a failed download is retried
+    #[arg(long, global = true, default_value_t = 5)]
+    retries: u32,
+
+    /// Download URL or mirror base URL ending in '/' (also read from OEWN_DOWNLOAD_URL)
+    #[arg(long, global = true)]
+    download_url: Option<String>,
+
+    /// Directory for cached data files and the default database
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Only match words exactly, without falling back to their base forms
+    #[arg(long, global = true, default_value_t = false)]
+    exact: bool,
+
+    /// Output format: colored text, or JSON, NDJSON or TSV for scripts
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
     /// Set verbosity level (use -v, -vv, or -vvv for increasing verbosity)
     #[arg(short, long, global = true, action = clap::ArgAction::Count)]
     verbose: u8,
@@ -46,9 +144,85 @@ enum Commands {
         word: String,
         /// Optional part of speech filter (noun, verb, adj, adv)
         pos: Option<PartOfSpeech>,
+        /// Show these relation types of each sense, or "all", instead of antonyms,
+        /// hypernyms and hyponyms (e.g. mero_part,derivation)
+        #[arg(short, long, value_delimiter = ',')]
+        relations: Vec<String>,
+    },
+    /// Search definitions and examples for words
+    Search {
+        /// Words to search for (a trailing '*' matches any word with that prefix)
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Only show synsets with this part of speech (noun, verb, adj, adv)
+        #[arg(long)]
+        pos: Option<PartOfSpeech>,
+        /// Maximum number of results
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// List lemmas matching a wildcard pattern or regular expression
+    Grep {
+        /// Pattern matching whole lemmas: '*' matches any characters, '?' one character
+        /// (e.g. "c?t", "*ology")
+        pattern: String,
+        /// Interpret the pattern as a regular expression matching anywhere in the lemma
+        /// (e.g. "^un.*able$")
+        #[arg(short = 'E', long)]
+        regex: bool,
+        /// Only list lemmas with this part of speech (noun, verb, adj, adv)
+        #[arg(long)]
+        pos: Option<PartOfSpeech>,
+    },
+    /// Show the shortest chain of relations linking two words or synsets
+    Path {
+        /// Word or synset ID to start from
+        from: String,
+        /// Word or synset ID to reach
+        to: String,
+        /// Only follow these relation types (e.g. hypernym,hyponym)
+        #[arg(short, long, value_delimiter = ',')]
+        relation: Vec<String>,
+    },
+    /// Draw the hypernym tree of each sense of a word, or its hyponym or other relation tree
+    Tree {
+        /// Word or synset ID at the root of the trees
+        word: String,
+        /// Only draw trees for this part of speech (noun, verb, adj, adv)
+        #[arg(long)]
+        pos: Option<PartOfSpeech>,
+        /// Follow hypernyms up the taxonomy (the default)
+        #[arg(long, conflicts_with = "down")]
+        up: bool,
+        /// Follow hyponyms down the taxonomy
+        #[arg(long)]
+        down: bool,
+        /// Follow these synset relation types instead (e.g. meronym, holonym, mero_part)
+        #[arg(short, long, value_delimiter = ',', conflicts_with_all = ["up", "down"])]
+        relation: Vec<String>,
+        /// Maximum number of relations followed from the root [default: no limit, or 3
+        /// with --down]
+        #[arg(short, long)]
+        depth: Option<usize>,
+        /// Maximum number of children shown per synset (0 for no limit)
+        #[arg(short, long, default_value_t = 20)]
+        breadth: usize,
+        /// Indent with spaces instead of drawing branches
+        #[arg(long)]
+        indent: bool,
+    },
+    /// Look up a list of words, one per line, and print one record per word
+    Batch {
+        /// File of words, or '-' for standard input [default: standard input]
+        file: Option<PathBuf>,
+        /// Only look words up with this part of speech (noun, verb, adj, adv)
+        #[arg(long)]
+        pos: Option<PartOfSpeech>,
     },
     /// Show a random word
     Random,
+    /// Open an interactive shell with history and tab completion
+    Shell,
     /// Clear the WordNet database
     ClearDb,
 }
@@ -68,6 +242,15 @@ fn setup_logging(verbose: u8) {
         .init();
 }
 
+/// Disables colors for machine-readable formats, when `NO_COLOR` is set to a non-empty
+/// value, and when the output is not a terminal.
+fn setup_colors(format: OutputFormat) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if format != OutputFormat::Text || no_color || !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+}
+
 /// Creates a progress callback for displaying download and processing progress.
 fn create_progress_callback(
     multi_progress: MultiProgress,
@@ -101,10 +284,10 @@ fn create_progress_callback(
             if let Some(msg) = update.message {
                 pb.set_message(msg);
             }
-            if let Some(total) = update.total_items {
-                if update.current_item >= total {
-                    pb.finish_and_clear();
-                }
+            if let Some(total) = update.total_items
+                && update.current_item >= total
+            {
+                pb.finish_and_clear();
             }
         }
         true
@@ -117,6 +300,8 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     setup_logging(cli.verbose);
+    setup_colors(cli.format);
+    let format = cli.format;
 
     info!("Loading WordNet data...");
 
@@ -128,7 +313,22 @@ async fn main() -> Result<()> {
     let load_options = LoadOptions {
         db_path: cli.db_path.as_ref().map(PathBuf::from),
         force_reload: cli.force_reload,
+        edition: cli.edition,
+        source: cli.source.clone(),
+        offline: cli.offline,
+        expected_sha256: cli.sha256.clone(),
+        allow_unverified: cli.allow_unverified,
+        download: DownloadPolicy {
+            connect_timeout: Duration::from_secs(cli.connect_timeout),
+            read_timeout: Duration::from_secs(cli.read_timeout),
+            max_retries: cli.retries,
+            ..Default::default()
+        },
+        download_url: cli.download_url.clone(),
+        fetcher: None,
+        data_dir: cli.data_dir.clone(),
     };
+    let clear_options = load_options.clone();
 
     let load_handle =
         tokio::spawn(async move { WordNet::load_with_options(load_options, Some(callback)).await });
@@ -151,7 +351,7 @@ async fn main() -> Result<()> {
     let wn = match wn_result {
         Ok(wn) => {
             info!("WordNet data loaded successfully.");
-            wn
+            wn.with_morphy_fallback(!cli.exact)
         }
         Err(e) => {
             error!("Failed to load WordNet data: {}", e);
@@ -161,30 +361,151 @@ async fn main() -> Result<()> {
     };
 
     match cli.command {
-        Commands::Define { word, pos } => {
-            if let Err(e) = handle_define(&wn, &word, pos).await {
+        Commands::Define {
+            word,
+            pos,
+            relations,
+        } => {
+            let result = match RelationDisplay::parse(&relations) {
+                Ok(shown) => handle_define(&wn, &word, pos, &shown, format).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
                 error!("Error during define command: {}", e);
                 eprintln!("{}", format!("Error defining '{}': {}", word, e).red());
                 std::process::exit(1);
             }
         }
+        Commands::Search { query, pos, limit } => {
+            let query = query.join(" ");
+            if let Err(e) = handle_search(&wn, &query, pos, limit, format) {
+                error!("Error during search command: {}", e);
+                eprintln!(
+                    "{}",
+                    format!("Error searching for '{}': {}", query, e).red()
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Grep {
+            pattern,
+            regex,
+            pos,
+        } => {
+            let mode = if regex {
+                PatternMode::Regex
+            } else {
+                PatternMode::Glob
+            };
+            if let Err(e) = handle_grep(&wn, &pattern, mode, pos, format) {
+                error!("Error during grep command: {}", e);
+                eprintln!("{}", format!("Error matching '{}': {}", pattern, e).red());
+                std::process::exit(1);
+            }
+        }
+        Commands::Path { from, to, relation } => {
+            if let Err(e) = handle_path(&wn, &from, &to, &relation, format) {
+                error!("Error during path command: {}", e);
+                eprintln!(
+                    "{}",
+                    format!("Error finding a path from '{}' to '{}': {}", from, to, e).red()
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Tree {
+            word,
+            pos,
+            up,
+            down,
+            relation,
+            depth,
+            breadth,
+            indent,
+        } => {
+            // Hypernyms are followed with --up or when no direction is given
+            let direction = if up || !down {
+                tree::TaxonomyDirection::Up
+            } else {
+                tree::TaxonomyDirection::Down
+            };
+            let result = if relation.is_empty() {
+                Ok(tree::taxonomy_rel_types(direction))
+            } else {
+                tree::parse_tree_relation_types(&relation)
+            };
+            let result = result.and_then(|rel_types| {
+                let options = tree::TreeOptions {
+                    rel_types,
+                    max_depth: depth.or((direction == tree::TaxonomyDirection::Down)
+                        .then_some(tree::DEFAULT_HYPONYM_DEPTH)),
+                    max_children: (breadth > 0).then_some(breadth),
+                    style: if indent {
+                        tree::TreeStyle::Indent
+                    } else {
+                        tree::TreeStyle::Box
+                    },
+                };
+                tree::handle_tree(&wn, &word, pos, &options, format)
+            });
+            if let Err(e) = result {
+                error!("Error during tree command: {}", e);
+                eprintln!(
+                    "{}",
+                    format!("Error drawing the tree of '{}': {}", word, e).red()
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Batch { file, pos } => {
+            if let Err(e) = handle_batch(&wn, file.as_deref(), pos, format) {
+                error!("Error during batch command: {}", e);
+                eprintln!("{}", format!("Error looking up words: {}", e).red());
+                std::process::exit(1);
+            }
+        }
         Commands::Random => {
-            if let Err(e) = handle_random(&wn).await {
+            if let Err(e) = handle_random(&wn, format).await {
                 error!("Error during random command: {}", e);
                 eprintln!("{}", format!("Error getting random word: {}", e).red());
                 std::process::exit(1);
             }
         }
+        Commands::Shell => {
+            let history_path = clear_options
+                .resolve_db_path()
+                .ok()
+                .and_then(|db_path| Some(db_path.parent()?.join(shell::HISTORY_FILE_NAME)));
+            if let Err(e) = shell::run(&wn, history_path.as_deref()).await {
+                error!("Error during shell command: {}", e);
+                eprintln!("{}", format!("Error in shell: {}", e).red());
+                std::process::exit(1);
+            }
+        }
         Commands::ClearDb => {
             info!("Clearing database...");
-            let db_path_to_clear = if let Some(custom_path) = cli.db_path {
-                Some(PathBuf::from(custom_path))
-            } else {
-                WordNet::get_default_db_path().ok()
-            };
+            let db_path_to_clear = clear_options.resolve_db_path().ok();
 
+            let db_path = db_path_to_clear
+                .as_ref()
+                .map(|path| path.display().to_string());
             match WordNet::clear_database(db_path_to_clear) {
-                Ok(_) => println!("{}", "Database cleared successfully.".green()),
+                Ok(_) if format == OutputFormat::Text => {
+                    println!("{}", "Database cleared successfully.".green())
+                }
+                Ok(_) => {
+                    let cleared = ClearOutput {
+                        cleared: true,
+                        db_path,
+                    };
+                    write_records(
+                        format,
+                        vec![cleared],
+                        |records| records.into_iter().next(),
+                        &["db_path"],
+                        |out, record| write_tsv(out, &[record.db_path.as_deref().unwrap_or("")]),
+                    )?;
+                }
                 Err(e) => {
                     error!("Failed to clear database: {}", e);
                     eprintln!("{}", format!("Error clearing database: {}", e).red());
@@ -196,8 +517,46 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+/// Relations shown for each sense by `define`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RelationDisplay {
+    /// Antonyms, hypernyms and hyponyms, by lemma
+    Summary,
+    /// Every sense and synset relation
+    All,
+    /// The sense and synset relations of these types
+    Only(Vec<RelationType>),
+}
+
+impl RelationDisplay {
+    /// Parses the `--relations` names: none for the summary, `all`, or relation types.
+    fn parse(names: &[String]) -> Result<Self> {
+        if names.is_empty() {
+            Ok(RelationDisplay::Summary)
+        } else if names.iter().any(|name| name == "all") {
+            Ok(RelationDisplay::All)
+        } else {
+            Ok(RelationDisplay::Only(parse_relation_types(names)?))
+        }
+    }
+
+    fn shows(&self, rel_type: &RelationType) -> bool {
+        match self {
+            RelationDisplay::Summary => false,
+            RelationDisplay::All => true,
+            RelationDisplay::Only(rel_types) => rel_types.contains(rel_type),
+        }
+    }
+}
+
 /// Handles the define command by looking up and displaying word definitions.
-async fn handle_define(wn: &WordNet, word: &str, pos_filter: Option<PartOfSpeech>) -> Result<()> {
+async fn handle_define(
+    wn: &WordNet,
+    word: &str,
+    pos_filter: Option<PartOfSpeech>,
+    relations: &RelationDisplay,
+    format: OutputFormat,
+) -> Result<()> {
     info!("Defining word: '{}', PoS filter: {:?}", word, pos_filter);
     let start_lookup = Instant::now();
     let entries = wn.lookup_entries(word, pos_filter)?;
@@ -207,8 +566,39 @@ async fn handle_define(wn: &WordNet, word: &str, pos_filter: Option<PartOfSpeech
         start_lookup.elapsed()
     );
 
+    if format != OutputFormat::Text {
+        let records = entries
+            .iter()
+            .map(|entry| output::entry_record(wn, entry))
+            .collect::<Result<Vec<_>>>()?;
+        let suggestions = if records.is_empty() {
+            wn.suggest(word, SUGGESTION_MAX_DISTANCE, SUGGESTION_LIMIT)?
+                .into_iter()
+                .map(|suggestion| suggestion.lemma)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        return write_records(
+            format,
+            records,
+            |entries| DefineOutput {
+                word: word.to_string(),
+                entries,
+                suggestions,
+            },
+            ENTRY_TSV_HEADER,
+            output::write_entry_tsv,
+        );
+    }
+
     if entries.is_empty() {
         println!("No definitions found for '{}'.", word.yellow());
+        let suggestions = wn.suggest(word, SUGGESTION_MAX_DISTANCE, SUGGESTION_LIMIT)?;
+        if !suggestions.is_empty() {
+            let lemmas: Vec<&str> = suggestions.iter().map(|s| s.lemma.as_str()).collect();
+            println!("Did you mean: {}?", lemmas.join(", ").green());
+        }
         return Ok(());
     }
 
@@ -231,17 +621,28 @@ async fn handle_define(wn: &WordNet, word: &str, pos_filter: Option<PartOfSpeech
         );
 
         // Print pronunciations
-        if let Some(first_entry) = entries_for_group.first() {
-            if !first_entry.pronunciations.is_empty() {
-                print!("  Pronunciations: ");
-                let pron_strings: Vec<String> = first_entry
-                    .pronunciations
-                    .iter()
-                    .map(|p| format!("{}[{}]", p.text.green(), p.variety.dimmed()))
-                    .collect();
-                println!("{}", pron_strings.join(", "));
+        if let Some(first_entry) = entries_for_group.first()
+            && !first_entry.pronunciations.is_empty()
+        {
+            print!("  Pronunciations: ");
+            let pron_strings: Vec<String> = first_entry
+                .pronunciations
+                .iter()
+                .map(|p| format!("{}[{}]", p.text.green(), p.variety.dimmed()))
+                .collect();
+            println!("{}", pron_strings.join(", "));
+        }
+
+        // Print variant forms (e.g. inflections and alternative spellings)
+        let mut forms: Vec<&str> = Vec::new();
+        for form in entries_for_group.iter().flat_map(|entry| &entry.forms) {
+            if !forms.contains(&form.written_form.as_str()) {
+                forms.push(&form.written_form);
             }
         }
+        if !forms.is_empty() {
+            println!("  Forms: {}", forms.join(", ").green());
+        }
 
         let mut sense_counter = 1;
         for entry in entries_for_group {
@@ -250,7 +651,14 @@ async fn handle_define(wn: &WordNet, word: &str, pos_filter: Option<PartOfSpeech
                 let start_sense_processing = Instant::now();
                 match wn.get_synset(&sense.synset) {
                     Ok(synset) => {
-                        print_sense_details(wn, &lemma_form, &synset, sense_counter)?;
+                        print_sense_details(
+                            wn,
+                            &lemma_form,
+                            Some(&sense),
+                            &synset,
+                            sense_counter,
+                            relations,
+                        )?;
                         sense_counter += 1;
                         debug!(
                             "Processing sense {} / synset {} took: {:?}",
@@ -274,11 +682,16 @@ async fn handle_define(wn: &WordNet, word: &str, pos_filter: Option<PartOfSpeech
 }
 
 /// Prints details for a single sense/synset combination.
+///
+/// Sense relations are those of `sense` if given, and otherwise, for the summary, those
+/// of any member of the synset.
 fn print_sense_details(
     wn: &WordNet,
     current_lemma: &str,
+    sense: Option<&Sense>,
     synset: &Synset,
     counter: usize,
+    relations: &RelationDisplay,
 ) -> Result<()> {
     // Print definition(s)
     for def in &synset.definitions {
@@ -297,24 +710,8 @@ fn print_sense_details(
 
     // Print synonyms
     let start_synonyms = Instant::now();
-    let member_senses = wn.get_senses_for_synset(&synset.id)?;
-    let mut synonyms = Vec::new();
-    for member_sense in member_senses {
-        if let Some(entry_id) = wn.get_entry_id_for_sense(&member_sense.id)? {
-            if let Some(entry) = wn.get_entry_by_id(&entry_id)? {
-                if entry.lemma.written_form != current_lemma
-                    && !synonyms.contains(&entry.lemma.written_form)
-                {
-                    synonyms.push(entry.lemma.written_form.clone());
-                }
-            } else {
-                warn!(
-                    "Entry ID {} found in sense_to_entry_index but not in lexical_entries map.",
-                    entry_id
-                );
-            }
-        }
-    }
+    let mut synonyms = synset_lemmas(wn, synset)?;
+    synonyms.retain(|lemma| lemma != current_lemma);
     debug!(
         "Synonym lookup for synset {} took: {:?}",
         synset.id,
@@ -331,55 +728,77 @@ fn print_sense_details(
     }
 
     // Print selected relations
-    print_relation(wn, synset, SenseRelType::Antonym, "Antonyms")?;
-    print_relation(wn, synset, SynsetRelType::Hypernym, "Hypernyms")?;
-    print_relation(wn, synset, SynsetRelType::Hyponym, "Hyponyms")?;
+    if *relations == RelationDisplay::Summary {
+        print_relation(wn, synset, SenseRelType::Antonym, "Antonyms")?;
+        print_relation(wn, synset, SynsetRelType::Hypernym, "Hypernyms")?;
+        print_relation(wn, synset, SynsetRelType::Hyponym, "Hyponyms")?;
+    } else {
+        print_relation_groups(wn, sense, synset, relations)?;
+    }
 
     println!();
     Ok(())
 }
 
+/// Returns the lemmas of a synset's member senses, in member order and without duplicates.
+fn synset_lemmas(wn: &WordNet, synset: &Synset) -> Result<Vec<String>> {
+    let mut lemmas = Vec::new();
+    for member_sense in wn.get_senses_for_synset(&synset.id)? {
+        if let Some(entry_id) = wn.get_entry_id_for_sense(&member_sense.id)? {
+            if let Some(entry) = wn.get_entry_by_id(&entry_id)? {
+                if !lemmas.contains(&entry.lemma.written_form) {
+                    lemmas.push(entry.lemma.written_form);
+                }
+            } else {
+                warn!(
+                    "Entry ID {} found for sense {} but not in lexical_entries.",
+                    entry_id, member_sense.id
+                );
+            }
+        }
+    }
+    Ok(lemmas)
+}
+
 /// Prints lemmas for a specific relation type.
 /// Handles both SenseRelations (like Antonym) and SynsetRelations (like Hypernym).
 fn print_relation(
     wn: &WordNet,
     synset: &Synset,
-    rel_type: impl Into<RelTypeMarker>,
+    rel_type: impl Into<RelationType>,
     label: &str,
 ) -> Result<()> {
     let start_relation = Instant::now();
-    let rel_type_marker = rel_type.into();
+    let rel_type = rel_type.into();
     let mut related_lemmas = Vec::new();
 
-    match rel_type_marker {
-        RelTypeMarker::Sense(sense_rel) => {
+    match rel_type {
+        RelationType::Sense(sense_rel) => {
             let member_senses = wn.get_senses_for_synset(&synset.id)?;
             for member_sense in member_senses {
-                let related_target_senses = wn.get_related_senses(&member_sense.id, sense_rel)?;
+                let related_target_senses =
+                    wn.get_related_senses(&member_sense.id, sense_rel.clone())?;
                 for target_sense in related_target_senses {
-                    if target_sense.synset != synset.id {
-                        if let Some(entry_id) = wn.get_entry_id_for_sense(&target_sense.id)? {
-                            if let Some(entry) = wn.get_entry_by_id(&entry_id)? {
-                                if !related_lemmas.contains(&entry.lemma.written_form) {
-                                    related_lemmas.push(entry.lemma.written_form.clone());
-                                }
-                            }
-                        }
+                    if target_sense.synset != synset.id
+                        && let Some(entry_id) = wn.get_entry_id_for_sense(&target_sense.id)?
+                        && let Some(entry) = wn.get_entry_by_id(&entry_id)?
+                        && !related_lemmas.contains(&entry.lemma.written_form)
+                    {
+                        related_lemmas.push(entry.lemma.written_form.clone());
                     }
                 }
             }
         }
-        RelTypeMarker::Synset(synset_rel) => {
+        RelationType::Synset(synset_rel) => {
             let related_synsets = wn.get_related_synsets(&synset.id, synset_rel)?;
             for target_synset in related_synsets {
                 let target_senses = wn.get_senses_for_synset(&target_synset.id)?;
                 for target_sense in target_senses {
-                    if let Some(entry_id) = wn.get_entry_id_for_sense(&target_sense.id)? {
-                        if let Some(entry) = wn.get_entry_by_id(&entry_id)? {
-                            if !related_lemmas.contains(&entry.lemma.written_form) {
-                                related_lemmas.push(entry.lemma.written_form.clone());
-                            }
-                        }
+                    if let Some(entry_id) = wn.get_entry_id_for_sense(&target_sense.id)?
+                        && let Some(entry) = wn.get_entry_by_id(&entry_id)?
+                        && !related_lemmas.contains(&entry.lemma.written_form)
+                    {
+                        related_lemmas.push(entry.lemma.written_form.clone());
                     }
                 }
             }
@@ -405,27 +824,506 @@ fn print_relation(
     Ok(())
 }
 
-// Helper enum to dispatch between SenseRelType and SynsetRelType
-enum RelTypeMarker {
-    Sense(SenseRelType),
-    Synset(SynsetRelType),
+/// Prints the shown relations of a sense and of its synset, one line per relation type
+/// with its targets, sense relations first, each in source order.
+fn print_relation_groups(
+    wn: &WordNet,
+    sense: Option<&Sense>,
+    synset: &Synset,
+    relations: &RelationDisplay,
+) -> Result<()> {
+    let mut groups: Vec<(RelationType, Vec<String>)> = Vec::new();
+    let sense_relations = sense.iter().flat_map(|sense| &sense.sense_relations);
+    for relation in sense_relations {
+        let rel_type = RelationType::Sense(relation.rel_type.clone());
+        if relations.shows(&rel_type) {
+            let target = sense_lemma(wn, &relation.target)?;
+            add_to_group(&mut groups, rel_type, target);
+        }
+    }
+    for relation in &synset.synset_relations {
+        let rel_type = RelationType::Synset(relation.rel_type.clone());
+        if relations.shows(&rel_type) {
+            let target = synset_lemmas(wn, &wn.get_synset(&relation.target)?)?.join(", ");
+            add_to_group(&mut groups, rel_type, target);
+        }
+    }
+
+    for (rel_type, targets) in groups {
+        let (name, level) = match &rel_type {
+            RelationType::Sense(rel_type) => (rel_type.as_str(), "sense"),
+            RelationType::Synset(rel_type) => (rel_type.as_str(), "synset"),
+        };
+        let label = RELATION_LABELS
+            .iter()
+            .find(|(rel_name, _)| *rel_name == name)
+            .map_or(name, |(_, label)| label);
+        println!(
+            "        {} {}: {}",
+            label.magenta(),
+            format!("[{}]", level).dimmed(),
+            targets.join("; ").green()
+        );
+    }
+    Ok(())
+}
+
+fn add_to_group(
+    groups: &mut Vec<(RelationType, Vec<String>)>,
+    rel_type: RelationType,
+    target: String,
+) {
+    match groups
+        .iter_mut()
+        .find(|(group_type, _)| *group_type == rel_type)
+    {
+        Some((_, targets)) => {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        None => groups.push((rel_type, vec![target])),
+    }
+}
+
+/// Handles the search command by listing synsets whose glosses match the query.
+fn handle_search(
+    wn: &WordNet,
+    query: &str,
+    pos_filter: Option<PartOfSpeech>,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    info!(
+        "Searching glosses for: '{}', PoS filter: {:?}",
+        query, pos_filter
+    );
+    let matches = wn.search_glosses(query, pos_filter, limit)?;
+    if format != OutputFormat::Text {
+        let records = matches
+            .iter()
+            .map(|hit| {
+                Ok(GlossRecord {
+                    synset: output::synset_record(wn, &hit.synset)?,
+                    snippet: hit.snippet.clone(),
+                    score: hit.score,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return write_records(
+            format,
+            records,
+            |results| SearchOutput {
+                query: query.to_string(),
+                results,
+            },
+            &["synset_id", "part_of_speech", "lemmas", "score", "snippet"],
+            |out, record| {
+                write_tsv(
+                    out,
+                    &[
+                        &record.synset.id,
+                        &pos_code(record.synset.part_of_speech),
+                        &record.synset.lemmas.join(", "),
+                        &record.score.to_string(),
+                        &record.snippet,
+                    ],
+                )
+            },
+        );
+    }
+    if matches.is_empty() {
+        println!("No glosses found matching '{}'.", query.yellow());
+        return Ok(());
+    }
+
+    for (index, hit) in matches.iter().enumerate() {
+        println!(
+            "\n{}. {} ~ {} {}",
+            (index + 1).to_string().bold(),
+            synset_lemmas(wn, &hit.synset)?.join(", ").bold().cyan(),
+            hit.synset.part_of_speech.to_string().italic(),
+            format!("({})", hit.synset.id).dimmed()
+        );
+        println!("   {}", highlight_snippet(&hit.snippet));
+    }
+    Ok(())
+}
+
+/// Replaces the match markers of a search snippet with terminal highlighting.
+fn highlight_snippet(snippet: &str) -> String {
+    let mut highlighted = String::new();
+    let mut rest = snippet;
+    while let Some(start) = rest.find(search::HIGHLIGHT_START) {
+        highlighted.push_str(&rest[..start]);
+        rest = &rest[start + search::HIGHLIGHT_START.len()..];
+        let end = rest.find(search::HIGHLIGHT_END).unwrap_or(rest.len());
+        highlighted.push_str(&rest[..end].bold().yellow().to_string());
+        rest = rest.get(end + search::HIGHLIGHT_END.len()..).unwrap_or("");
+    }
+    highlighted.push_str(rest);
+    highlighted
 }
 
-impl From<SenseRelType> for RelTypeMarker {
-    fn from(rel_type: SenseRelType) -> Self {
-        RelTypeMarker::Sense(rel_type)
+/// Handles the grep command by printing each matching lemma as it is found.
+fn handle_grep(
+    wn: &WordNet,
+    pattern: &str,
+    mode: PatternMode,
+    pos_filter: Option<PartOfSpeech>,
+    format: OutputFormat,
+) -> Result<()> {
+    info!(
+        "Matching lemmas against {} pattern: '{}', PoS filter: {:?}",
+        mode, pattern, pos_filter
+    );
+    let matches = wn.search_lemmas(pattern, mode, pos_filter)?;
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => {
+            let lemmas = matches
+                .map(|lemma_match| lemma_match.map(output::lemma_record))
+                .collect::<Result<Vec<_>>>()?;
+            return write_records(
+                format,
+                lemmas,
+                |lemmas| GrepOutput {
+                    pattern: pattern.to_string(),
+                    mode: mode.to_string(),
+                    lemmas,
+                },
+                LEMMA_TSV_HEADER,
+                output::write_lemma_tsv,
+            );
+        }
+        OutputFormat::Ndjson | OutputFormat::Tsv => {
+            return output::ignore_broken_pipe(stream_lemma_records(matches, format));
+        }
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    let mut found = 0;
+    for lemma_match in matches {
+        let lemma_match = lemma_match?;
+        let line = writeln!(
+            stdout,
+            "{} {}",
+            lemma_match.lemma.cyan(),
+            lemma_match.part_of_speech.to_string().italic().dimmed()
+        );
+        // Stop quietly when the output is closed, e.g. when piped into `head`
+        if line.is_err() {
+            return Ok(());
+        }
+        found += 1;
     }
+    if found == 0 {
+        println!("No lemmas found matching '{}'.", pattern.yellow());
+    }
+    Ok(())
 }
 
-impl From<SynsetRelType> for RelTypeMarker {
-    fn from(rel_type: SynsetRelType) -> Self {
-        RelTypeMarker::Synset(rel_type)
+/// Writes lemma matches as NDJSON or TSV lines as they are found.
+fn stream_lemma_records(matches: LemmaMatches, format: OutputFormat) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    if format == OutputFormat::Tsv {
+        write_tsv(&mut stdout, LEMMA_TSV_HEADER)?;
+    }
+    for lemma_match in matches {
+        let record = output::lemma_record(lemma_match?);
+        if format == OutputFormat::Tsv {
+            output::write_lemma_tsv(&mut stdout, &record)?;
+        } else {
+            write_ndjson(&mut stdout, &record)?;
+        }
     }
+    Ok(())
 }
 
-async fn handle_random(wn: &WordNet) -> Result<()> {
+/// Handles the path command by printing each synset on the path and the relation
+/// leading to the next.
+fn handle_path(
+    wn: &WordNet,
+    from: &str,
+    to: &str,
+    relation_names: &[String],
+    format: OutputFormat,
+) -> Result<()> {
+    info!(
+        "Finding a path from '{}' to '{}', relations: {:?}",
+        from, to, relation_names
+    );
+    let allowed = parse_relation_types(relation_names)?;
+    let path = wn.shortest_path(from, to, &allowed)?;
+    if format != OutputFormat::Text {
+        let mut synsets = Vec::new();
+        for synset_id in path.iter().flat_map(|path| &path.synsets) {
+            let synset = wn.get_synset(synset_id)?;
+            synsets.push(PathSynsetRecord {
+                lemmas: synset_lemmas(wn, &synset)?,
+                id: synset.id,
+                part_of_speech: synset.part_of_speech,
+            });
+        }
+        let edges = path
+            .iter()
+            .flat_map(|path| &path.edges)
+            .map(output::path_edge_record)
+            .collect();
+        let found = path.is_some();
+        return write_records(
+            format,
+            edges,
+            |edges| PathOutput {
+                from: from.to_string(),
+                to: to.to_string(),
+                path: found.then_some(PathRecord { synsets, edges }),
+            },
+            &[
+                "source_synset",
+                "rel_type",
+                "target_synset",
+                "source_sense",
+                "target_sense",
+            ],
+            |out, edge| {
+                write_tsv(
+                    out,
+                    &[
+                        &edge.source_synset,
+                        &edge.rel_type,
+                        &edge.target_synset,
+                        edge.source_sense.as_deref().unwrap_or(""),
+                        edge.target_sense.as_deref().unwrap_or(""),
+                    ],
+                )
+            },
+        );
+    }
+    let Some(path) = path else {
+        println!(
+            "No path found from '{}' to '{}'.",
+            from.yellow(),
+            to.yellow()
+        );
+        return Ok(());
+    };
+
+    let relations = if path.len() == 1 {
+        "relation"
+    } else {
+        "relations"
+    };
+    println!(
+        "Path from '{}' to '{}' ({} {}):",
+        from.bold(),
+        to.bold(),
+        path.len(),
+        relations
+    );
+    for (index, synset_id) in path.synsets.iter().enumerate() {
+        let synset = wn.get_synset(synset_id)?;
+        println!(
+            "  {} ~ {} {}",
+            synset_lemmas(wn, &synset)?.join(", ").bold().cyan(),
+            synset.part_of_speech.to_string().italic(),
+            format!("({})", synset.id).dimmed()
+        );
+        let Some(edge) = path.edges.get(index) else {
+            continue;
+        };
+        match &edge.senses {
+            Some((source_sense, target_sense)) => println!(
+                "    {} {} {}",
+                "↓".dimmed(),
+                edge.rel_type.to_string().magenta(),
+                format!(
+                    "({} → {})",
+                    sense_lemma(wn, source_sense)?,
+                    sense_lemma(wn, target_sense)?
+                )
+                .green()
+            ),
+            None => println!(
+                "    {} {}",
+                "↓".dimmed(),
+                edge.rel_type.to_string().magenta()
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the synset with ID `target`, or else the synsets of the word `target`, in
+/// sense order.
+fn target_synsets(
+    wn: &WordNet,
+    target: &str,
+    pos_filter: Option<PartOfSpeech>,
+) -> Result<Vec<Synset>> {
+    match wn.get_synset(target) {
+        Ok(synset) => return Ok(vec![synset]),
+        Err(OewnError::SynsetNotFound(_)) => {}
+        Err(e) => return Err(e),
+    }
+    let mut synsets: Vec<Synset> = Vec::new();
+    for entry in wn.lookup_entries(target, pos_filter)? {
+        for sense in wn.get_senses_for_entry(&entry.id)? {
+            if !synsets.iter().any(|synset| synset.id == sense.synset) {
+                synsets.push(wn.get_synset(&sense.synset)?);
+            }
+        }
+    }
+    Ok(synsets)
+}
+
+/// Resolves relation type names to the synset and sense relation types they name.
+fn parse_relation_types(names: &[String]) -> Result<Vec<RelationType>> {
+    let mut rel_types = Vec::new();
+    for name in names {
+        let synset_rel = SynsetRelType::from(name.as_str());
+        let sense_rel = SenseRelType::from(name.as_str());
+        let known_synset_rel = SynsetRelType::KNOWN.contains(&synset_rel);
+        let known_sense_rel = SenseRelType::KNOWN.contains(&sense_rel);
+        if !known_synset_rel && !known_sense_rel {
+            return Err(OewnError::InvalidArgument(format!(
+                "Unknown relation type: {}",
+                name
+            )));
+        }
+        if known_synset_rel {
+            rel_types.push(RelationType::Synset(synset_rel));
+        }
+        if known_sense_rel {
+            rel_types.push(RelationType::Sense(sense_rel));
+        }
+    }
+    Ok(rel_types)
+}
+
+/// Returns the lemma of a sense's entry, or the sense ID if it has none.
+fn sense_lemma(wn: &WordNet, sense_id: &str) -> Result<String> {
+    if let Some(entry_id) = wn.get_entry_id_for_sense(sense_id)?
+        && let Some(entry) = wn.get_entry_by_id(&entry_id)?
+    {
+        return Ok(entry.lemma.written_form);
+    }
+    Ok(sense_id.to_string())
+}
+
+/// Handles the batch command by looking up the words of a file or standard input in
+/// chunks and writing one record per word as each chunk is done.
+fn handle_batch(
+    wn: &WordNet,
+    file: Option<&Path>,
+    pos_filter: Option<PartOfSpeech>,
+    format: OutputFormat,
+) -> Result<()> {
+    let input: Box<dyn BufRead> = match file {
+        Some(path) if path != Path::new("-") => Box::new(BufReader::new(File::open(path)?)),
+        _ => Box::new(std::io::stdin().lock()),
+    };
+    let mut words = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let word = line.trim();
+        if !word.is_empty() {
+            words.push(word.to_string());
+        }
+    }
+    info!(
+        "Looking up {} words in batch, PoS filter: {:?}",
+        words.len(),
+        pos_filter
+    );
+
+    let mut stdout = std::io::stdout().lock();
+    let mut json_records = Vec::new();
+    if format == OutputFormat::Tsv {
+        output::ignore_broken_pipe(write_tsv(&mut stdout, BATCH_TSV_HEADER))?;
+    }
+    for chunk in words.chunks(BATCH_CHUNK_SIZE) {
+        let chunk: Vec<&str> = chunk.iter().map(String::as_str).collect();
+        let start_lookup = Instant::now();
+        let lookups = wn.lookup_many(&chunk, pos_filter)?;
+        debug!(
+            "lookup_many for {} words took: {:?}",
+            chunk.len(),
+            start_lookup.elapsed()
+        );
+        for lookup in lookups {
+            let written = match format {
+                OutputFormat::Text => write_batch_text(&mut stdout, &lookup),
+                OutputFormat::Json => {
+                    json_records.push(output::batch_record(lookup));
+                    Ok(())
+                }
+                OutputFormat::Ndjson => write_ndjson(&mut stdout, &output::batch_record(lookup)),
+                OutputFormat::Tsv => {
+                    output::write_batch_tsv(&mut stdout, &output::batch_record(lookup))
+                }
+            };
+            // Stop quietly when the output is closed, e.g. when piped into `head`
+            if let Err(OewnError::Io(e)) = &written
+                && e.kind() == std::io::ErrorKind::BrokenPipe
+            {
+                return Ok(());
+            }
+            written?;
+        }
+    }
+    if format == OutputFormat::Json {
+        let document = BatchOutput {
+            words: json_records,
+        };
+        output::ignore_broken_pipe(output::write_json(&mut stdout, &document))?;
+    }
+    Ok(())
+}
+
+/// Writes the senses of a batch word as text, or marks it as not found.
+fn write_batch_text(out: &mut impl Write, lookup: &WordLookup) -> Result<()> {
+    if !lookup.is_found() {
+        writeln!(out, "{}: {}", lookup.word.bold(), "not found".yellow())?;
+        return Ok(());
+    }
+    writeln!(out, "{}", lookup.word.bold())?;
+    for entry in &lookup.entries {
+        writeln!(
+            out,
+            "  {} ~ {}",
+            entry.lemma.written_form.cyan(),
+            entry.lemma.part_of_speech.to_string().italic()
+        )?;
+        for (index, sense) in entry.senses.iter().enumerate() {
+            let definition = lookup
+                .synset(&sense.synset)
+                .and_then(|synset| synset.definitions.first())
+                .map_or("", |definition| definition.text.trim());
+            writeln!(
+                out,
+                "    {}: {}",
+                (index + 1).to_string().bold(),
+                definition
+            )?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_random(wn: &WordNet, format: OutputFormat) -> Result<()> {
     info!("Getting random word...");
     match wn.get_random_entry() {
+        Ok(entry) if format != OutputFormat::Text => {
+            let record = output::entry_record(wn, &entry)?;
+            write_records(
+                format,
+                vec![record],
+                |records| records.into_iter().next(),
+                ENTRY_TSV_HEADER,
+                output::write_entry_tsv,
+            )?;
+        }
         Ok(entry) => {
             println!(
                 "Random word: {} ({})",
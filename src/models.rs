@@ -39,10 +39,10 @@ pub struct Lexicon {
     #[serde(rename = "@confidenceScore", default)]
     pub confidence_score: Option<f32>,
     /// Dublin Core publisher information
-    #[serde(rename = "@dc:publisher", default)]
+    #[serde(rename = "@publisher", default)]
     pub dc_publisher: Option<String>,
     /// Dublin Core contributor information
-    #[serde(rename = "@dc:contributor", default)]
+    #[serde(rename = "@contributor", default)]
     pub dc_contributor: Option<String>,
 
     /// Dependencies required by this lexicon
@@ -55,6 +55,9 @@ pub struct Lexicon {
     /// Synsets contained in this lexicon
     #[serde(rename = "Synset", default)]
     pub synsets: Vec<Synset>,
+    /// Syntactic frames shared by the senses of this lexicon (WN-LMF 1.1+)
+    #[serde(rename = "SyntacticBehaviour", default)]
+    pub syntactic_behaviours: Vec<SyntacticBehaviour>,
 }
 
 /// Dependency requirement for a lexicon.
@@ -73,12 +76,21 @@ pub struct LexicalEntry {
     pub id: String,
     #[serde(rename = "Lemma")]
     pub lemma: Lemma,
-    /// Pronunciation variants for this entry
+    /// Pronunciation variants for this entry.
+    ///
+    /// Entries read from the database report the lemma's pronunciations here,
+    /// including those nested in [`Lemma::pronunciations`] in the source data.
     #[serde(rename = "Pronunciation", default)]
     pub pronunciations: Vec<Pronunciation>,
+    /// Variant written forms (e.g. alternative spellings) of this entry
+    #[serde(rename = "Form", default)]
+    pub forms: Vec<Form>,
     /// Senses associated with this entry
     #[serde(rename = "Sense", default)]
     pub senses: Vec<Sense>,
+    /// Syntactic frames of this entry's senses (WN-LMF 1.0 style)
+    #[serde(rename = "SyntacticBehaviour", default)]
+    pub syntactic_behaviours: Vec<SyntacticBehaviour>,
 }
 
 /// The canonical form of a lexical entry.
@@ -88,6 +100,42 @@ pub struct Lemma {
     pub written_form: String,
     #[serde(rename = "@partOfSpeech")]
     pub part_of_speech: PartOfSpeech,
+    /// Script of the written form (ISO 15924)
+    #[serde(rename = "@script", default)]
+    pub script: Option<String>,
+    /// Pronunciations of the lemma (WN-LMF 1.1+)
+    #[serde(rename = "Pronunciation", default)]
+    pub pronunciations: Vec<Pronunciation>,
+    /// Tags categorizing the lemma
+    #[serde(rename = "Tag", default)]
+    pub tags: Vec<Tag>,
+}
+
+/// A variant written form of a lexical entry, such as an alternative spelling.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Form {
+    #[serde(rename = "@id", default)]
+    pub id: Option<String>,
+    #[serde(rename = "@writtenForm")]
+    pub written_form: String,
+    /// Script of the written form (ISO 15924)
+    #[serde(rename = "@script", default)]
+    pub script: Option<String>,
+    /// Pronunciations specific to this form
+    #[serde(rename = "Pronunciation", default)]
+    pub pronunciations: Vec<Pronunciation>,
+    /// Tags categorizing this form
+    #[serde(rename = "Tag", default)]
+    pub tags: Vec<Tag>,
+}
+
+/// A categorized tag attached to a lemma or form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Tag {
+    #[serde(rename = "@category")]
+    pub category: String,
+    #[serde(rename = "$text")]
+    pub text: String,
 }
 
 /// Part-of-speech enumeration following WordNet conventions.
@@ -138,9 +186,60 @@ pub struct Sense {
     /// Reference to the synset this sense belongs to
     #[serde(rename = "@synset")]
     pub synset: String,
+    /// Position of this sense among the senses of its entry
+    #[serde(rename = "@n", default)]
+    pub n: Option<u32>,
+    /// Whether the sense is lexicalized in the language
+    #[serde(rename = "@lexicalized", default = "default_lexicalized")]
+    pub lexicalized: bool,
+    /// Syntactic position of an adjective sense
+    #[serde(rename = "@adjposition", default)]
+    pub adjposition: Option<AdjPosition>,
+    /// Space-separated IDs of the lexicon's [`SyntacticBehaviour`]s that apply to this sense
+    #[serde(rename = "@subcat", default)]
+    pub subcat: Option<String>,
     /// Relations to other senses
     #[serde(rename = "SenseRelation", default)]
     pub sense_relations: Vec<SenseRelation>,
+    /// Corpus frequency counts of this sense
+    #[serde(rename = "Count", default)]
+    pub counts: Vec<Count>,
+}
+
+/// Default value for lexicalized fields.
+fn default_lexicalized() -> bool {
+    true
+}
+
+/// Syntactic position of an adjective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdjPosition {
+    /// Attributive (prenominal), e.g. "a *former* president"
+    A,
+    /// Immediately postnominal, e.g. "the president *elect*"
+    Ip,
+    /// Predicative, e.g. "the child is *awake*"
+    P,
+}
+
+/// A corpus frequency count for a sense.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Count {
+    #[serde(rename = "$text")]
+    pub value: u32,
+}
+
+/// A syntactic frame (subcategorization frame) such as "Somebody ----s something".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SyntacticBehaviour {
+    #[serde(rename = "@id", default)]
+    pub id: Option<String>,
+    #[serde(rename = "@subcategorizationFrame")]
+    pub subcategorization_frame: String,
+    /// Space-separated IDs of the senses this frame applies to, if restricted
+    #[serde(rename = "@senses", default)]
+    pub senses: Option<String>,
 }
 
 /// A relationship between senses.
@@ -151,26 +250,149 @@ pub struct SenseRelation {
     /// Reference to the target sense
     #[serde(rename = "@target")]
     pub target: String,
+    /// Dublin Core and other metadata attributes. OEWN gives relations WN-LMF has no
+    /// `relType` for as `relType="other"` with their actual type in `dc:type`.
+    #[serde(flatten)]
+    pub metadata: Metadata,
 }
 
-/// Types of relationships between senses.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SenseRelType {
-    Antonym,
-    Also,
-    Participle,
-    Pertainym,
-    Derivation,
-    DomainTopic,
-    DomainMemberTopic,
-    DomainRegion,
-    DomainMemberRegion,
-    Exemplifies,
-    IsExemplifiedBy,
-    /// Catch-all for any other relation types found
-    #[serde(other)]
-    Other,
+impl SenseRelation {
+    /// The type of this relation, taking a `dc:type` refining an `other` relation into
+    /// account (e.g. `relType="other" dc:type="agent"` is [`SenseRelType::Agent`]).
+    pub fn resolved_type(&self) -> SenseRelType {
+        match (&self.rel_type, &self.metadata.dc_type) {
+            (SenseRelType::Other, Some(dc_type)) => SenseRelType::from(dc_type.as_str()),
+            (rel_type, _) => rel_type.clone(),
+        }
+    }
+}
+
+/// Declares a relation type enum whose variants map to WN-LMF `relType` strings.
+///
+/// Besides the listed variants, the generated enum has an `Unrecognized` variant that
+/// keeps any other string verbatim, so relations survive a round trip through the
+/// database even if this crate does not know them yet.
+macro_rules! relation_types {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident => $text:literal, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$variant_meta])* $variant, )*
+            /// A relation type outside the known vocabulary, as written in the source
+            Unrecognized(String),
+        }
+
+        impl $name {
+            /// All relation types in the known vocabulary.
+            pub const KNOWN: &'static [$name] = &[$($name::$variant),*];
+
+            /// Returns the WN-LMF `relType` string for this relation.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( $name::$variant => $text, )*
+                    $name::Unrecognized(s) => s,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                match s {
+                    $( $text => $name::$variant, )*
+                    other => $name::Unrecognized(other.to_string()),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(s))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                Ok(Self::from(s.as_str()))
+            }
+        }
+    };
+}
+
+relation_types! {
+    /// Types of relationships between senses.
+    pub enum SenseRelType {
+        Antonym => "antonym",
+        Also => "also",
+        Participle => "participle",
+        Pertainym => "pertainym",
+        Derivation => "derivation",
+        DomainTopic => "domain_topic",
+        HasDomainTopic => "has_domain_topic",
+        DomainRegion => "domain_region",
+        HasDomainRegion => "has_domain_region",
+        Exemplifies => "exemplifies",
+        IsExemplifiedBy => "is_exemplified_by",
+        Similar => "similar",
+        /// The generic `other` relation of WN-LMF
+        Other => "other",
+        Feminine => "feminine",
+        HasFeminine => "has_feminine",
+        Masculine => "masculine",
+        HasMasculine => "has_masculine",
+        Young => "young",
+        HasYoung => "has_young",
+        Diminutive => "diminutive",
+        HasDiminutive => "has_diminutive",
+        Augmentative => "augmentative",
+        HasAugmentative => "has_augmentative",
+        AntoGradable => "anto_gradable",
+        AntoSimple => "anto_simple",
+        AntoConverse => "anto_converse",
+        SimpleAspectIp => "simple_aspect_ip",
+        SecondaryAspectIp => "secondary_aspect_ip",
+        SimpleAspectPi => "simple_aspect_pi",
+        SecondaryAspectPi => "secondary_aspect_pi",
+        Metaphor => "metaphor",
+        HasMetaphor => "has_metaphor",
+        Metonym => "metonym",
+        HasMetonym => "has_metonym",
+
+        // Morphosemantic links between verbs and their derived nouns, given by OEWN as
+        // the `dc:type` of an `other` relation (see [`SenseRelation::resolved_type`])
+        Agent => "agent",
+        BodyPart => "body_part",
+        ByMeansOf => "by_means_of",
+        Destination => "destination",
+        Event => "event",
+        Instrument => "instrument",
+        Location => "location",
+        Material => "material",
+        Property => "property",
+        Result => "result",
+        State => "state",
+        Undergoer => "undergoer",
+        Uses => "uses",
+        Vehicle => "vehicle",
+    }
 }
 
 /// A synset (synonym set) representing a concept.
@@ -186,6 +408,15 @@ pub struct Synset {
     /// Space-separated list of sense IDs that belong to this synset
     #[serde(rename = "@members", default)]
     pub members: String,
+    /// Lexicographer file this synset was defined in (e.g. "noun.animal")
+    #[serde(rename = "@lexfile", default)]
+    pub lexfile: Option<String>,
+    /// Whether the concept is lexicalized in the language
+    #[serde(rename = "@lexicalized", default = "default_lexicalized")]
+    pub lexicalized: bool,
+    /// Dublin Core and other metadata attributes
+    #[serde(flatten)]
+    pub metadata: Metadata,
     /// Definitions for this synset
     #[serde(rename = "Definition", default)]
     pub definitions: Vec<Definition>,
@@ -200,10 +431,52 @@ pub struct Synset {
     pub examples: Vec<Example>,
 }
 
+/// Dublin Core and other metadata attributes that WN-LMF allows on most elements.
+///
+/// quick-xml matches attributes by local name, so the `dc:` prefix is left out of the renames.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Metadata {
+    #[serde(rename = "@contributor", default)]
+    pub dc_contributor: Option<String>,
+    #[serde(rename = "@coverage", default)]
+    pub dc_coverage: Option<String>,
+    #[serde(rename = "@creator", default)]
+    pub dc_creator: Option<String>,
+    #[serde(rename = "@date", default)]
+    pub dc_date: Option<String>,
+    #[serde(rename = "@description", default)]
+    pub dc_description: Option<String>,
+    #[serde(rename = "@format", default)]
+    pub dc_format: Option<String>,
+    #[serde(rename = "@identifier", default)]
+    pub dc_identifier: Option<String>,
+    #[serde(rename = "@publisher", default)]
+    pub dc_publisher: Option<String>,
+    #[serde(rename = "@relation", default)]
+    pub dc_relation: Option<String>,
+    #[serde(rename = "@rights", default)]
+    pub dc_rights: Option<String>,
+    #[serde(rename = "@source", default)]
+    pub dc_source: Option<String>,
+    #[serde(rename = "@subject", default)]
+    pub dc_subject: Option<String>,
+    #[serde(rename = "@title", default)]
+    pub dc_title: Option<String>,
+    #[serde(rename = "@type", default)]
+    pub dc_type: Option<String>,
+    #[serde(rename = "@status", default)]
+    pub status: Option<String>,
+    #[serde(rename = "@note", default)]
+    pub note: Option<String>,
+    /// Kept as text, as flattened attributes cannot be parsed as numbers
+    #[serde(rename = "@confidenceScore", default)]
+    pub confidence_score: Option<String>,
+}
+
 /// A definition of a synset.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Definition {
-    #[serde(rename = "@dc:source", default)]
+    #[serde(rename = "@source", default)]
     pub dc_source: Option<String>,
     #[serde(rename = "$text")]
     pub text: String,
@@ -212,7 +485,7 @@ pub struct Definition {
 /// An Inter-Lingual Index definition.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ILIDefinition {
-    #[serde(rename = "@dc:source", default)]
+    #[serde(rename = "@source", default)]
     pub dc_source: Option<String>,
     #[serde(rename = "$text")]
     pub text: String,
@@ -228,120 +501,116 @@ pub struct SynsetRelation {
     pub target: String,
 }
 
-/// Types of relationships between synsets.
-///
-/// This enum covers both Princeton WordNet relations and extended relations
-/// from the WordNet-LMF specification.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SynsetRelType {
-    // Princeton WordNet Properties
-    Hypernym,
-    Hyponym,
-    InstanceHypernym,
-    InstanceHyponym,
-    MeroMember,
-    MeroPart,
-    MeroSubstance,
-    HoloMember,
-    HoloPart,
-    HoloSubstance,
-    Entails,
-    Causes,
-    Similar, // Note: 'similar_to' in WNDB, 'similar' in LMF spec text
-    Attribute,
-    DomainRegion,
-    DomainTopic,
-    HasDomainRegion,
-    HasDomainTopic,
-    Exemplifies,
-    IsExemplifiedBy,
-
-    // Non-Princeton WordNet Relations (from spec text)
-    Agent,
-    Also,
-    AntoConverse,
-    AntoGradable,
-    AntoSimple,
-    Antonym,
-    Augmentative,
-    BeInState,
-    ClassifiedBy,
-    Classifies,
-    CoAgentInstrument,
-    CoAgentPatient,
-    CoAgentResult,
-    CoInstrumentAgent,
-    CoInstrumentPatient,
-    CoInstrumentResult,
-    CoPatientAgent,
-    CoPatientInstrument,
-    CoResultAgent,
-    CoResultInstrument,
-    CoRole,
-    Constitutive,
-    Derivation,
-    Diminutive,
-    Direction,
-    Domain, // General domain
-    EqSynonym,
-    Feminine,
-    HasAugmentative,
-    HasDiminutive,
-    HasDomain, // General has_domain
-    HasFeminine,
-    HasMasculine,
-    HasYoung,
-    HoloLocation,
-    HoloPortion,
-    Holonym, // General holonym
-    InManner,
-    Instrument,
-    Involved,
-    InvolvedAgent,
-    InvolvedDirection,
-    InvolvedInstrument,
-    InvolvedLocation,
-    InvolvedPatient,
-    InvolvedResult,
-    InvolvedSourceDirection,
-    InvolvedTargetDirection,
-    IrSynonym,
-    IsCausedBy,
-    IsEntailedBy,
-    IsSubeventOf,
-    Location,
-    MannerOf,
-    Masculine,
-    MeroLocation,
-    MeroPortion,
-    Meronym, // General meronym
-    Other,   // Explicitly listed
-    Participle,
-    Patient,
-    Pertainym,
-    RestrictedBy,
-    Restricts,
-    Result,
-    Role,
-    SecondaryAspectIp,
-    SecondaryAspectPi,
-    SimpleAspectIp,
-    SimpleAspectPi,
-    SourceDirection,
-    StateOf,
-    Subevent,
-    TargetDirection,
-    Young,
-
-    // Catch-all for any others not explicitly listed or typos
-    #[serde(other)]
-    Unknown,
+relation_types! {
+    /// Types of relationships between synsets.
+    ///
+    /// This enum covers both Princeton WordNet relations and extended relations
+    /// from the WordNet-LMF specification.
+    pub enum SynsetRelType {
+        // Princeton WordNet Properties
+        Hypernym => "hypernym",
+        Hyponym => "hyponym",
+        InstanceHypernym => "instance_hypernym",
+        InstanceHyponym => "instance_hyponym",
+        MeroMember => "mero_member",
+        MeroPart => "mero_part",
+        MeroSubstance => "mero_substance",
+        HoloMember => "holo_member",
+        HoloPart => "holo_part",
+        HoloSubstance => "holo_substance",
+        Entails => "entails",
+        Causes => "causes",
+        Similar => "similar", // 'similar_to' in WNDB
+        Attribute => "attribute",
+        DomainRegion => "domain_region",
+        DomainTopic => "domain_topic",
+        HasDomainRegion => "has_domain_region",
+        HasDomainTopic => "has_domain_topic",
+        Exemplifies => "exemplifies",
+        IsExemplifiedBy => "is_exemplified_by",
+
+        // Non-Princeton WordNet Relations (from spec text)
+        Agent => "agent",
+        Also => "also",
+        AntoConverse => "anto_converse",
+        AntoGradable => "anto_gradable",
+        AntoSimple => "anto_simple",
+        Antonym => "antonym",
+        Augmentative => "augmentative",
+        BeInState => "be_in_state",
+        ClassifiedBy => "classified_by",
+        Classifies => "classifies",
+        CoAgentInstrument => "co_agent_instrument",
+        CoAgentPatient => "co_agent_patient",
+        CoAgentResult => "co_agent_result",
+        CoInstrumentAgent => "co_instrument_agent",
+        CoInstrumentPatient => "co_instrument_patient",
+        CoInstrumentResult => "co_instrument_result",
+        CoPatientAgent => "co_patient_agent",
+        CoPatientInstrument => "co_patient_instrument",
+        CoResultAgent => "co_result_agent",
+        CoResultInstrument => "co_result_instrument",
+        CoRole => "co_role",
+        Constitutive => "constitutive",
+        Derivation => "derivation",
+        Diminutive => "diminutive",
+        Direction => "direction",
+        Domain => "domain", // General domain
+        EqSynonym => "eq_synonym",
+        Feminine => "feminine",
+        HasAugmentative => "has_augmentative",
+        HasDiminutive => "has_diminutive",
+        HasDomain => "has_domain", // General has_domain
+        HasFeminine => "has_feminine",
+        HasMasculine => "has_masculine",
+        HasYoung => "has_young",
+        HoloLocation => "holo_location",
+        HoloPortion => "holo_portion",
+        Holonym => "holonym", // General holonym
+        InManner => "in_manner",
+        Instrument => "instrument",
+        Involved => "involved",
+        InvolvedAgent => "involved_agent",
+        InvolvedDirection => "involved_direction",
+        InvolvedInstrument => "involved_instrument",
+        InvolvedLocation => "involved_location",
+        InvolvedPatient => "involved_patient",
+        InvolvedResult => "involved_result",
+        InvolvedSourceDirection => "involved_source_direction",
+        InvolvedTargetDirection => "involved_target_direction",
+        IrSynonym => "ir_synonym",
+        IsCausedBy => "is_caused_by",
+        IsEntailedBy => "is_entailed_by",
+        IsSubeventOf => "is_subevent_of",
+        Location => "location",
+        MannerOf => "manner_of",
+        Masculine => "masculine",
+        MeroLocation => "mero_location",
+        MeroPortion => "mero_portion",
+        Meronym => "meronym", // General meronym
+        Other => "other",
+        Participle => "participle",
+        Patient => "patient",
+        Pertainym => "pertainym",
+        RestrictedBy => "restricted_by",
+        Restricts => "restricts",
+        Result => "result",
+        Role => "role",
+        SecondaryAspectIp => "secondary_aspect_ip",
+        SecondaryAspectPi => "secondary_aspect_pi",
+        SimpleAspectIp => "simple_aspect_ip",
+        SimpleAspectPi => "simple_aspect_pi",
+        SourceDirection => "source_direction",
+        StateOf => "state_of",
+        Subevent => "subevent",
+        TargetDirection => "target_direction",
+        Young => "young",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Example {
-    #[serde(rename = "@dc:source", default)]
+    #[serde(rename = "@source", default)]
     pub dc_source: Option<String>,
     #[serde(rename = "$text")]
     pub text: String,
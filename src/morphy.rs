@@ -0,0 +1,237 @@
+//! Morphological analysis in the style of WordNet's `morphy`.
+//!
+//! Inflected words are reduced to base forms in two ways:
+//!
+//! - **Exceptions**: irregular inflections such as "geese" or "ran". Instead of bundled
+//!   exception files, the variant `<Form>`s of the loaded data are used, which OEWN
+//!   provides for irregular nouns, verbs and adjectives.
+//! - **Detachment rules**: per part of speech suffix substitutions, e.g. "-ies" to "-y"
+//!   for nouns or "-ing" to "-e" for verbs. When a suffix is simply removed and leaves a
+//!   doubled final consonant ("running", "bigger"), the undoubled stem is tried as well.
+//!
+//! Candidates are only returned if they exist as lemmas in the database. The exceptions
+//! and candidate lemmas of many words are looked up together, in a few queries.
+
+use crate::db::{query_in_batches, stored_parts_of_speech, string_to_part_of_speech};
+use crate::error::Result;
+use crate::models::PartOfSpeech;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// Suffix substitutions for nouns, as `(suffix, replacement)`.
+pub const NOUN_RULES: &[(&str, &str)] = &[
+    ("s", ""),
+    ("ses", "s"),
+    ("xes", "x"),
+    ("zes", "z"),
+    ("ches", "ch"),
+    ("shes", "sh"),
+    ("men", "man"),
+    ("ies", "y"),
+];
+
+/// Suffix substitutions for verbs, as `(suffix, replacement)`.
+pub const VERB_RULES: &[(&str, &str)] = &[
+    ("s", ""),
+    ("ies", "y"),
+    ("es", "e"),
+    ("es", ""),
+    ("ed", "e"),
+    ("ed", ""),
+    ("ing", "e"),
+    ("ing", ""),
+];
+
+/// Suffix substitutions for adjectives, as `(suffix, replacement)`.
+pub const ADJECTIVE_RULES: &[(&str, &str)] = &[("er", ""), ("est", ""), ("er", "e"), ("est", "e")];
+
+/// Parts of speech tried, in order, when no part of speech is given.
+const MORPHY_POS: [PartOfSpeech; 4] = [
+    PartOfSpeech::N,
+    PartOfSpeech::V,
+    PartOfSpeech::A,
+    PartOfSpeech::R,
+];
+
+/// Returns the detachment rules for a part of speech.
+///
+/// Adjective satellites share the adjective rules; other parts of speech have none.
+pub fn detachment_rules(pos: PartOfSpeech) -> &'static [(&'static str, &'static str)] {
+    match pos {
+        PartOfSpeech::N => NOUN_RULES,
+        PartOfSpeech::V => VERB_RULES,
+        PartOfSpeech::A | PartOfSpeech::S => ADJECTIVE_RULES,
+        _ => &[],
+    }
+}
+
+/// Applies the detachment rules for `pos` to `word`, without checking the results exist.
+///
+/// Candidates are returned in rule order, each at most once.
+///
+/// # Example
+///
+/// ```
+/// use oewn_rs::{PartOfSpeech, morphy::apply_rules};
+///
+/// assert!(apply_rules("churches", PartOfSpeech::N).contains(&"church".to_string()));
+/// assert!(apply_rules("hoping", PartOfSpeech::V).contains(&"hope".to_string()));
+/// ```
+pub fn apply_rules(word: &str, pos: PartOfSpeech) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for (suffix, replacement) in detachment_rules(pos) {
+        if let Some(stem) = word.strip_suffix(suffix)
+            && !stem.is_empty()
+        {
+            let mut stems = vec![format!("{}{}", stem, replacement)];
+            if replacement.is_empty()
+                && let Some(undoubled) = undouble_consonant(stem)
+            {
+                stems.push(undoubled.to_string());
+            }
+            for candidate in stems {
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// Strips one letter from a stem ending in a doubled consonant, e.g. "runn" to "run".
+fn undouble_consonant(stem: &str) -> Option<&str> {
+    let mut chars = stem.chars().rev();
+    let (last, previous) = (chars.next()?, chars.next()?);
+    let is_consonant = last.is_ascii_alphabetic() && !"aeiou".contains(last);
+    (last == previous && is_consonant).then(|| &stem[..stem.len() - last.len_utf8()])
+}
+
+/// Finds the base forms of `word` that exist as lemmas, with their stored part of speech.
+///
+/// The word itself is included if it is a lemma. Exceptions take precedence over the
+/// detachment rules: if `word` is a variant form of some entry, the rules are not applied.
+/// Without a part of speech, nouns, verbs, adjectives and adverbs are tried in that order.
+pub(crate) fn lemmatize(
+    conn: &Connection,
+    word: &str,
+    pos: Option<PartOfSpeech>,
+) -> Result<Vec<(String, PartOfSpeech)>> {
+    Ok(lemmatize_many(conn, &[word], pos)?
+        .pop()
+        .unwrap_or_default())
+}
+
+/// Finds the base forms of each of `words` as [`lemmatize`] does, looking up the
+/// exceptions and candidate lemmas of all words together.
+pub(crate) fn lemmatize_many(
+    conn: &Connection,
+    words: &[&str],
+    pos: Option<PartOfSpeech>,
+) -> Result<Vec<Vec<(String, PartOfSpeech)>>> {
+    let words: Vec<String> = words.iter().map(|w| w.trim().to_lowercase()).collect();
+    let parts_of_speech = match pos {
+        Some(pos) => vec![pos],
+        None => MORPHY_POS.to_vec(),
+    };
+
+    let mut forms: Vec<String> = Vec::new();
+    for word in &words {
+        forms.push(word.clone());
+        for pos in &parts_of_speech {
+            forms.extend(apply_rules(word, *pos));
+        }
+    }
+    forms.sort();
+    forms.dedup();
+    // Lemmas and exceptions by lowercase form, as (lemma, part of speech) in entry order
+    let lemmas = group_by_form(query_in_batches(
+        conn,
+        "SELECT lemma_written_form_lower, lemma_written_form, part_of_speech
+         FROM lexical_entries
+         WHERE lemma_written_form_lower IN ({values})
+         ORDER BY position",
+        &forms,
+        |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))),
+    )?);
+    let exceptions = group_by_form(query_in_batches(
+        conn,
+        "SELECT f.written_form_lower, le.lemma_written_form, le.part_of_speech
+         FROM forms f
+         JOIN lexical_entries le ON le.id = f.entry_id
+         WHERE f.written_form_lower IN ({values})
+         ORDER BY le.position",
+        &words,
+        |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))),
+    )?);
+
+    let mut all_results = Vec::with_capacity(words.len());
+    for word in &words {
+        let mut found: Vec<&(String, String)> = Vec::new();
+        for pos in &parts_of_speech {
+            let (first, second) = stored_parts_of_speech(*pos);
+            let with_pos = |form: &str| {
+                let matches = lemmas.get(form).into_iter().flatten();
+                matches.filter(move |(_, p)| p == first || p == second)
+            };
+            found.extend(with_pos(word));
+            let word_exceptions: Vec<_> = exceptions
+                .get(word)
+                .into_iter()
+                .flatten()
+                .filter(|(_, p)| p == first || p == second)
+                .collect();
+            if word_exceptions.is_empty() {
+                for candidate in apply_rules(word, *pos) {
+                    found.extend(with_pos(&candidate));
+                }
+            } else {
+                found.extend(word_exceptions);
+            }
+        }
+
+        let mut results: Vec<(String, PartOfSpeech)> = Vec::new();
+        for (lemma, pos_str) in found {
+            let lemma = (lemma.clone(), string_to_part_of_speech(pos_str)?);
+            if !results.contains(&lemma) {
+                results.push(lemma);
+            }
+        }
+        all_results.push(results);
+    }
+    Ok(all_results)
+}
+
+/// Groups `(form, lemma)` rows by form, keeping the row order.
+fn group_by_form(rows: Vec<(String, (String, String))>) -> HashMap<String, Vec<(String, String)>> {
+    let mut groups: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for (form, lemma) in rows {
+        groups.entry(form).or_default().push(lemma);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_rules() {
+        assert_eq!(apply_rules("boxes", PartOfSpeech::N), ["boxe", "box"]);
+        assert_eq!(apply_rules("ponies", PartOfSpeech::N), ["ponie", "pony"]);
+        assert_eq!(
+            apply_rules("running", PartOfSpeech::V),
+            ["runne", "runn", "run"]
+        );
+        assert_eq!(
+            apply_rules("bigger", PartOfSpeech::S),
+            ["bigg", "big", "bigge"]
+        );
+        assert_eq!(
+            apply_rules("called", PartOfSpeech::V),
+            ["calle", "call", "cal"]
+        );
+        assert!(apply_rules("quickly", PartOfSpeech::R).is_empty());
+        assert!(apply_rules("s", PartOfSpeech::N).is_empty());
+    }
+}
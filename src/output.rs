@@ -0,0 +1,855 @@
+//! Machine-readable output of the CLI commands.
+//!
+//! With `--format json`, each command prints one JSON document; with `--format ndjson`,
+//! one JSON object per line for each item of its result; and with `--format tsv`, a
+//! header line followed by one tab-separated line per item. The records below are the
+//! JSON schema; their fields are only ever added to, never renamed or removed. Parts of
+//! speech are WN-LMF codes (`n`, `v`, `a`, `s`, `r`, ...) and relation types WN-LMF
+//! `relType` names.
+//!
+//! | Command    | `json` document  | `ndjson` line      | `tsv` line                   |
+//! |------------|------------------|--------------------|------------------------------|
+//! | `define`   | [`DefineOutput`] | [`EntryRecord`]    | one per sense of each entry  |
+//! | `search`   | [`SearchOutput`] | [`GlossRecord`]    | one per matching synset      |
+//! | `grep`     | [`GrepOutput`]   | [`LemmaRecord`]    | one per matching lemma       |
+//! | `path`     | [`PathOutput`]   | [`PathEdgeRecord`] | one per relation on the path |
+//! | `tree`     | [`TreeOutput`]   | [`TreeNodeRecord`] | one per node of each tree    |
+//! | `batch`    | [`BatchOutput`]  | [`BatchRecord`]    | one per word                 |
+//! | `random`   | [`EntryRecord`]  | [`EntryRecord`]    | one per sense of the entry   |
+//! | `clear-db` | [`ClearOutput`]  | [`ClearOutput`]    | the cleared database path    |
+
+use crate::synset_lemmas;
+use clap::ValueEnum;
+use oewn_rs::{
+    LemmaMatch, LexicalEntry, OewnError, PathEdge, RelationType, Synset, WordLookup, WordNet,
+    error::Result, models::PartOfSpeech,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io::{ErrorKind, StdoutLock, Write};
+
+/// Output format of the CLI commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text, colored when written to a terminal
+    #[default]
+    Text,
+    /// A single JSON document
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Tab-separated values with a header line
+    Tsv,
+}
+
+/// A lexical entry with its senses.
+#[derive(Debug, Serialize)]
+pub struct EntryRecord {
+    pub id: String,
+    pub lemma: String,
+    pub part_of_speech: PartOfSpeech,
+    pub pronunciations: Vec<PronunciationRecord>,
+    /// Variant written forms, e.g. inflections and alternative spellings
+    pub forms: Vec<String>,
+    pub senses: Vec<SenseRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PronunciationRecord {
+    pub text: String,
+    pub variety: String,
+    pub notation: Option<String>,
+    pub phonemic: bool,
+    pub audio: Option<String>,
+}
+
+/// A sense of an entry, with its synset.
+#[derive(Debug, Serialize)]
+pub struct SenseRecord {
+    pub id: String,
+    /// Relations to other senses, by sense ID
+    pub relations: Vec<RelationRecord>,
+    pub synset: SynsetRecord,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SynsetRecord {
+    pub id: String,
+    pub ili: Option<String>,
+    pub part_of_speech: PartOfSpeech,
+    pub lexfile: Option<String>,
+    /// Lemmas of the synset's members, in member order
+    pub lemmas: Vec<String>,
+    pub definitions: Vec<String>,
+    pub ili_definition: Option<String>,
+    pub examples: Vec<String>,
+    /// Relations to other synsets, by synset ID
+    pub relations: Vec<RelationRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelationRecord {
+    pub rel_type: String,
+    pub target: String,
+}
+
+/// Result of `define`.
+#[derive(Debug, Serialize)]
+pub struct DefineOutput {
+    pub word: String,
+    pub entries: Vec<EntryRecord>,
+    /// Similarly spelled lemmas, when no entry is found
+    pub suggestions: Vec<String>,
+}
+
+/// A synset whose glosses match a search.
+#[derive(Debug, Serialize)]
+pub struct GlossRecord {
+    pub synset: SynsetRecord,
+    /// Extract of the best matching gloss, with matches in square brackets
+    pub snippet: String,
+    pub score: f64,
+}
+
+/// Result of `search`.
+#[derive(Debug, Serialize)]
+pub struct SearchOutput {
+    pub query: String,
+    pub results: Vec<GlossRecord>,
+}
+
+/// A lemma matching a pattern.
+#[derive(Debug, Serialize)]
+pub struct LemmaRecord {
+    pub entry_id: String,
+    pub lemma: String,
+    pub part_of_speech: PartOfSpeech,
+}
+
+/// Result of `grep`.
+#[derive(Debug, Serialize)]
+pub struct GrepOutput {
+    pub pattern: String,
+    /// `glob` or `regex`
+    pub mode: String,
+    pub lemmas: Vec<LemmaRecord>,
+}
+
+/// A synset on a path.
+#[derive(Debug, Serialize)]
+pub struct PathSynsetRecord {
+    pub id: String,
+    pub part_of_speech: PartOfSpeech,
+    pub lemmas: Vec<String>,
+}
+
+/// A relation on a path, in its stored direction.
+#[derive(Debug, Serialize)]
+pub struct PathEdgeRecord {
+    pub source_synset: String,
+    pub target_synset: String,
+    pub rel_type: String,
+    /// `synset` or `sense`
+    pub kind: &'static str,
+    /// Source sense of a sense relation
+    pub source_sense: Option<String>,
+    /// Target sense of a sense relation
+    pub target_sense: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PathRecord {
+    pub synsets: Vec<PathSynsetRecord>,
+    pub edges: Vec<PathEdgeRecord>,
+}
+
+/// Result of `path`.
+#[derive(Debug, Serialize)]
+pub struct PathOutput {
+    pub from: String,
+    pub to: String,
+    /// `null` if the words are not connected
+    pub path: Option<PathRecord>,
+}
+
+/// A synset in a relation tree, with the synsets it leads to.
+#[derive(Debug, Serialize)]
+pub struct TreeNodeRecord {
+    pub id: String,
+    pub part_of_speech: PartOfSpeech,
+    pub lemmas: Vec<String>,
+    /// The relation from the parent node, `null` at the root
+    pub rel_type: Option<String>,
+    /// Whether the synset already appears higher up the tree, where its children are
+    /// listed instead
+    pub repeated: bool,
+    pub children: Vec<TreeNodeRecord>,
+    /// Number of children left out by the breadth limit
+    pub omitted_children: usize,
+}
+
+/// Result of `tree`.
+#[derive(Debug, Serialize)]
+pub struct TreeOutput {
+    pub word: String,
+    /// The relation types followed
+    pub relations: Vec<String>,
+    /// One tree per synset of the word
+    pub trees: Vec<TreeNodeRecord>,
+}
+
+/// The entries found for one word of a batch.
+#[derive(Debug, Serialize)]
+pub struct BatchRecord {
+    /// The word as given
+    pub word: String,
+    /// `false` if the word has no entries
+    pub found: bool,
+    pub entries: Vec<BatchEntryRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchEntryRecord {
+    pub id: String,
+    pub lemma: String,
+    pub part_of_speech: PartOfSpeech,
+    pub senses: Vec<BatchSenseRecord>,
+}
+
+/// A sense of a batch entry, with the glosses of its synset.
+#[derive(Debug, Serialize)]
+pub struct BatchSenseRecord {
+    pub id: String,
+    pub synset_id: String,
+    pub lexfile: Option<String>,
+    pub definitions: Vec<String>,
+    pub examples: Vec<String>,
+}
+
+/// Result of `batch`.
+#[derive(Debug, Serialize)]
+pub struct BatchOutput {
+    pub words: Vec<BatchRecord>,
+}
+
+/// Result of `clear-db`.
+#[derive(Debug, Serialize)]
+pub struct ClearOutput {
+    pub cleared: bool,
+    pub db_path: Option<String>,
+}
+
+/// Builds the record of an entry, loading its senses and their synsets.
+pub fn entry_record(wn: &WordNet, entry: &LexicalEntry) -> Result<EntryRecord> {
+    let mut senses = Vec::new();
+    for sense in wn.get_senses_for_entry(&entry.id)? {
+        let synset = wn.get_synset(&sense.synset)?;
+        senses.push(SenseRecord {
+            relations: sense
+                .sense_relations
+                .iter()
+                .map(|relation| RelationRecord {
+                    rel_type: relation.rel_type.to_string(),
+                    target: relation.target.clone(),
+                })
+                .collect(),
+            id: sense.id,
+            synset: synset_record(wn, &synset)?,
+        });
+    }
+    Ok(EntryRecord {
+        id: entry.id.clone(),
+        lemma: entry.lemma.written_form.clone(),
+        part_of_speech: entry.lemma.part_of_speech,
+        pronunciations: entry
+            .pronunciations
+            .iter()
+            .map(|p| PronunciationRecord {
+                text: p.text.clone(),
+                variety: p.variety.clone(),
+                notation: p.notation.clone(),
+                phonemic: p.phonemic,
+                audio: p.audio.clone(),
+            })
+            .collect(),
+        forms: entry.forms.iter().map(|f| f.written_form.clone()).collect(),
+        senses,
+    })
+}
+
+pub fn synset_record(wn: &WordNet, synset: &Synset) -> Result<SynsetRecord> {
+    Ok(SynsetRecord {
+        id: synset.id.clone(),
+        ili: synset.ili.clone(),
+        part_of_speech: synset.part_of_speech,
+        lexfile: synset.lexfile.clone(),
+        lemmas: synset_lemmas(wn, synset)?,
+        definitions: synset.definitions.iter().map(|d| d.text.clone()).collect(),
+        ili_definition: synset.ili_definition.as_ref().map(|d| d.text.clone()),
+        examples: synset.examples.iter().map(|e| e.text.clone()).collect(),
+        relations: synset
+            .synset_relations
+            .iter()
+            .map(|relation| RelationRecord {
+                rel_type: relation.rel_type.to_string(),
+                target: relation.target.clone(),
+            })
+            .collect(),
+    })
+}
+
+/// Builds the record of a word looked up with [`WordNet::lookup_many`].
+pub fn batch_record(lookup: WordLookup) -> BatchRecord {
+    let entries = lookup
+        .entries
+        .iter()
+        .map(|entry| BatchEntryRecord {
+            id: entry.id.clone(),
+            lemma: entry.lemma.written_form.clone(),
+            part_of_speech: entry.lemma.part_of_speech,
+            senses: entry
+                .senses
+                .iter()
+                .map(|sense| {
+                    let synset = lookup.synset(&sense.synset);
+                    BatchSenseRecord {
+                        id: sense.id.clone(),
+                        synset_id: sense.synset.clone(),
+                        lexfile: synset.and_then(|synset| synset.lexfile.clone()),
+                        definitions: synset
+                            .iter()
+                            .flat_map(|synset| &synset.definitions)
+                            .map(|d| d.text.clone())
+                            .collect(),
+                        examples: synset
+                            .iter()
+                            .flat_map(|synset| &synset.examples)
+                            .map(|e| e.text.clone())
+                            .collect(),
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+    BatchRecord {
+        found: lookup.is_found(),
+        word: lookup.word,
+        entries,
+    }
+}
+
+pub fn lemma_record(lemma_match: LemmaMatch) -> LemmaRecord {
+    LemmaRecord {
+        entry_id: lemma_match.entry_id,
+        lemma: lemma_match.lemma,
+        part_of_speech: lemma_match.part_of_speech,
+    }
+}
+
+pub fn path_edge_record(edge: &PathEdge) -> PathEdgeRecord {
+    let (source_sense, target_sense) = edge.senses.clone().unzip();
+    PathEdgeRecord {
+        source_synset: edge.source_synset.clone(),
+        target_synset: edge.target_synset.clone(),
+        rel_type: edge.rel_type.to_string(),
+        kind: match edge.rel_type {
+            RelationType::Synset(_) => "synset",
+            RelationType::Sense(_) => "sense",
+        },
+        source_sense,
+        target_sense,
+    }
+}
+
+/// Header of the `tsv` lines of entries, written by [`write_entry_tsv`].
+pub const ENTRY_TSV_HEADER: &[&str] = &[
+    "entry_id",
+    "lemma",
+    "part_of_speech",
+    "sense_id",
+    "synset_id",
+    "definition",
+];
+
+/// Header of the `tsv` lines of lemmas, written by [`write_lemma_tsv`].
+pub const LEMMA_TSV_HEADER: &[&str] = &["entry_id", "lemma", "part_of_speech"];
+
+pub fn write_lemma_tsv(out: &mut impl Write, lemma: &LemmaRecord) -> Result<()> {
+    write_tsv(
+        out,
+        &[
+            &lemma.entry_id,
+            &lemma.lemma,
+            &pos_code(lemma.part_of_speech),
+        ],
+    )
+}
+
+/// Writes one `tsv` line per sense of an entry.
+pub fn write_entry_tsv(out: &mut impl Write, entry: &EntryRecord) -> Result<()> {
+    let part_of_speech = pos_code(entry.part_of_speech);
+    for sense in &entry.senses {
+        write_tsv(
+            out,
+            &[
+                &entry.id,
+                &entry.lemma,
+                &part_of_speech,
+                &sense.id,
+                &sense.synset.id,
+                &sense.synset.definitions.join("; "),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Header of the `tsv` lines of trees, written by [`write_tree_tsv`].
+pub const TREE_TSV_HEADER: &[&str] = &[
+    "root_synset",
+    "depth",
+    "rel_type",
+    "synset_id",
+    "part_of_speech",
+    "lemmas",
+    "repeated",
+];
+
+/// Writes one `tsv` line per node of a tree, parents before their children.
+pub fn write_tree_tsv(out: &mut impl Write, root: &TreeNodeRecord) -> Result<()> {
+    let mut stack = vec![(root, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        write_tsv(
+            out,
+            &[
+                &root.id,
+                &depth.to_string(),
+                node.rel_type.as_deref().unwrap_or(""),
+                &node.id,
+                &pos_code(node.part_of_speech),
+                &node.lemmas.join(", "),
+                &node.repeated.to_string(),
+            ],
+        )?;
+        stack.extend(node.children.iter().rev().map(|child| (child, depth + 1)));
+    }
+    Ok(())
+}
+
+/// Header of the `tsv` lines of batch words, written by [`write_batch_tsv`].
+pub const BATCH_TSV_HEADER: &[&str] = &[
+    "word",
+    "found",
+    "entry_ids",
+    "parts_of_speech",
+    "synset_ids",
+    "definitions",
+];
+
+/// Writes the `tsv` line of a batch word. IDs and parts of speech are separated by
+/// commas, and the definitions of different senses by ` | `.
+pub fn write_batch_tsv(out: &mut impl Write, record: &BatchRecord) -> Result<()> {
+    let mut parts_of_speech: Vec<String> = Vec::new();
+    for entry in &record.entries {
+        let code = pos_code(entry.part_of_speech);
+        if !parts_of_speech.contains(&code) {
+            parts_of_speech.push(code);
+        }
+    }
+    let senses = || record.entries.iter().flat_map(|entry| &entry.senses);
+    let entry_ids: Vec<&str> = record.entries.iter().map(|e| e.id.as_str()).collect();
+    let synset_ids: Vec<&str> = senses().map(|sense| sense.synset_id.as_str()).collect();
+    let definitions: Vec<String> = senses().map(|sense| sense.definitions.join("; ")).collect();
+    write_tsv(
+        out,
+        &[
+            &record.word,
+            &record.found.to_string(),
+            &entry_ids.join(","),
+            &parts_of_speech.join(","),
+            &synset_ids.join(","),
+            &definitions.join(" | "),
+        ],
+    )
+}
+
+/// Returns the WN-LMF code of a part of speech, as in the JSON output.
+pub fn pos_code(pos: PartOfSpeech) -> String {
+    serde_json::to_value(pos)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Writes the records of a command's result in a machine-readable format: for JSON,
+/// as the document built by `document`; for NDJSON, one record per line; and for TSV,
+/// the header then the lines written by `write_tsv_record` for each record.
+///
+/// Output closed early, e.g. when piped into `head`, ends the output quietly.
+pub fn write_records<T: Serialize, D: Serialize>(
+    format: OutputFormat,
+    records: Vec<T>,
+    document: impl FnOnce(Vec<T>) -> D,
+    tsv_header: &[&str],
+    write_tsv_record: impl Fn(&mut StdoutLock<'static>, &T) -> Result<()>,
+) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    let written = match format {
+        OutputFormat::Json => write_json(&mut out, &document(records)),
+        OutputFormat::Ndjson => records
+            .iter()
+            .try_for_each(|record| write_ndjson(&mut out, record)),
+        OutputFormat::Tsv => write_tsv(&mut out, tsv_header).and_then(|()| {
+            records
+                .iter()
+                .try_for_each(|record| write_tsv_record(&mut out, record))
+        }),
+        OutputFormat::Text => Err(OewnError::Internal(
+            "Text output is not written as records".to_string(),
+        )),
+    };
+    ignore_broken_pipe(written)
+}
+
+/// Treats output closed by the reader as success.
+pub fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(OewnError::Io(e)) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Writes a value as a pretty-printed JSON document.
+pub fn write_json<T: Serialize>(out: &mut impl Write, value: &T) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value).map_err(serialization_error)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Writes a value as one line of JSON.
+pub fn write_ndjson<T: Serialize>(out: &mut impl Write, value: &T) -> Result<()> {
+    serde_json::to_writer(&mut *out, value).map_err(serialization_error)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Writes one line of tab-separated fields.
+pub fn write_tsv(out: &mut impl Write, fields: &[&str]) -> Result<()> {
+    let fields: Vec<Cow<str>> = fields.iter().map(|field| escape_tsv(field)).collect();
+    writeln!(out, "{}", fields.join("\t"))?;
+    Ok(())
+}
+
+/// Escapes backslashes, tabs and line breaks so that a field stays on its line.
+fn escape_tsv(field: &str) -> Cow<'_, str> {
+    if !field.contains(['\\', '\t', '\n', '\r']) {
+        return Cow::Borrowed(field);
+    }
+    let mut escaped = String::with_capacity(field.len() + 2);
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Keeps write failures as I/O errors, so that a closed pipe can be recognised.
+fn serialization_error(e: serde_json::Error) -> OewnError {
+    if e.is_io() {
+        OewnError::Io(e.into())
+    } else {
+        OewnError::Internal(format!("Failed to write JSON output: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oewn_rs::{SenseRelType, SynsetRelType};
+    use serde_json::json;
+
+    fn synset(id: &str) -> SynsetRecord {
+        SynsetRecord {
+            id: id.to_string(),
+            ili: Some("i1".to_string()),
+            part_of_speech: PartOfSpeech::N,
+            lexfile: Some("noun.animal".to_string()),
+            lemmas: vec!["dog".to_string()],
+            definitions: vec!["a domestic canine".to_string()],
+            ili_definition: None,
+            examples: vec![],
+            relations: vec![RelationRecord {
+                rel_type: "hypernym".to_string(),
+                target: "oewn-canine-n".to_string(),
+            }],
+        }
+    }
+
+    fn batch(definition: &str) -> BatchRecord {
+        BatchRecord {
+            word: "dogs".to_string(),
+            found: true,
+            entries: vec![
+                BatchEntryRecord {
+                    id: "oewn-dog-n".to_string(),
+                    lemma: "dog".to_string(),
+                    part_of_speech: PartOfSpeech::N,
+                    senses: vec![BatchSenseRecord {
+                        id: "oewn-dog__1.05.00::".to_string(),
+                        synset_id: "oewn-dog-n".to_string(),
+                        lexfile: Some("noun.animal".to_string()),
+                        definitions: vec![definition.to_string(), "a pet".to_string()],
+                        examples: vec!["the dog barked".to_string()],
+                    }],
+                },
+                BatchEntryRecord {
+                    id: "oewn-dog-v".to_string(),
+                    lemma: "dog".to_string(),
+                    part_of_speech: PartOfSpeech::V,
+                    senses: vec![BatchSenseRecord {
+                        id: "oewn-dog__2.38.00::".to_string(),
+                        synset_id: "oewn-chase-v".to_string(),
+                        lexfile: None,
+                        definitions: vec!["go after".to_string()],
+                        examples: vec![],
+                    }],
+                },
+            ],
+        }
+    }
+
+    fn tsv_lines(write: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> Vec<String> {
+        let mut out = Vec::new();
+        write(&mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_entry_record_json() {
+        let record = EntryRecord {
+            id: "oewn-dog-n".to_string(),
+            lemma: "dog".to_string(),
+            part_of_speech: PartOfSpeech::N,
+            pronunciations: vec![PronunciationRecord {
+                text: "dɒɡ".to_string(),
+                variety: "GB".to_string(),
+                notation: None,
+                phonemic: true,
+                audio: None,
+            }],
+            forms: vec!["dogs".to_string()],
+            senses: vec![SenseRecord {
+                id: "oewn-dog__1.05.00::".to_string(),
+                relations: vec![],
+                synset: synset("oewn-dog-n"),
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            json!({
+                "id": "oewn-dog-n",
+                "lemma": "dog",
+                "part_of_speech": "n",
+                "pronunciations": [{
+                    "text": "dɒɡ",
+                    "variety": "GB",
+                    "notation": null,
+                    "phonemic": true,
+                    "audio": null,
+                }],
+                "forms": ["dogs"],
+                "senses": [{
+                    "id": "oewn-dog__1.05.00::",
+                    "relations": [],
+                    "synset": {
+                        "id": "oewn-dog-n",
+                        "ili": "i1",
+                        "part_of_speech": "n",
+                        "lexfile": "noun.animal",
+                        "lemmas": ["dog"],
+                        "definitions": ["a domestic canine"],
+                        "ili_definition": null,
+                        "examples": [],
+                        "relations": [{"rel_type": "hypernym", "target": "oewn-canine-n"}],
+                    },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_record_json() {
+        assert_eq!(
+            serde_json::to_value(batch("a domestic canine")).unwrap(),
+            json!({
+                "word": "dogs",
+                "found": true,
+                "entries": [
+                    {
+                        "id": "oewn-dog-n",
+                        "lemma": "dog",
+                        "part_of_speech": "n",
+                        "senses": [{
+                            "id": "oewn-dog__1.05.00::",
+                            "synset_id": "oewn-dog-n",
+                            "lexfile": "noun.animal",
+                            "definitions": ["a domestic canine", "a pet"],
+                            "examples": ["the dog barked"],
+                        }],
+                    },
+                    {
+                        "id": "oewn-dog-v",
+                        "lemma": "dog",
+                        "part_of_speech": "v",
+                        "senses": [{
+                            "id": "oewn-dog__2.38.00::",
+                            "synset_id": "oewn-chase-v",
+                            "lexfile": null,
+                            "definitions": ["go after"],
+                            "examples": [],
+                        }],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_path_edge_record_json() {
+        let synset_edge = path_edge_record(&PathEdge {
+            source_synset: "oewn-dog-n".to_string(),
+            target_synset: "oewn-canine-n".to_string(),
+            rel_type: RelationType::Synset(SynsetRelType::Hypernym),
+            senses: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&synset_edge).unwrap(),
+            json!({
+                "source_synset": "oewn-dog-n",
+                "target_synset": "oewn-canine-n",
+                "rel_type": "hypernym",
+                "kind": "synset",
+                "source_sense": null,
+                "target_sense": null,
+            })
+        );
+
+        let sense_edge = path_edge_record(&PathEdge {
+            source_synset: "oewn-hot-a".to_string(),
+            target_synset: "oewn-cold-a".to_string(),
+            rel_type: RelationType::Sense(SenseRelType::Antonym),
+            senses: Some((
+                "oewn-hot__3.00.01::".to_string(),
+                "oewn-cold__3.00.01::".to_string(),
+            )),
+        });
+        assert_eq!(
+            serde_json::to_value(&sense_edge).unwrap(),
+            json!({
+                "source_synset": "oewn-hot-a",
+                "target_synset": "oewn-cold-a",
+                "rel_type": "antonym",
+                "kind": "sense",
+                "source_sense": "oewn-hot__3.00.01::",
+                "target_sense": "oewn-cold__3.00.01::",
+            })
+        );
+    }
+
+    #[test]
+    fn test_escape_tsv() {
+        assert!(matches!(
+            escape_tsv("plain text"),
+            Cow::Borrowed("plain text")
+        ));
+        assert_eq!(escape_tsv("a\tb"), "a\\tb");
+        assert_eq!(escape_tsv("line\nbreak\r\n"), "line\\nbreak\\r\\n");
+        assert_eq!(escape_tsv("back\\slash\\t"), "back\\\\slash\\\\t");
+    }
+
+    #[test]
+    fn test_write_tsv_escapes_fields() {
+        let lines = tsv_lines(|out| write_tsv(out, &["a\tb", "c\nd", "e\\f"]));
+        assert_eq!(lines, vec!["a\\tb\tc\\nd\te\\\\f"]);
+    }
+
+    #[test]
+    fn test_write_batch_tsv() {
+        let lines = tsv_lines(|out| write_batch_tsv(out, &batch("a\tdomestic\ncanine")));
+        assert_eq!(
+            lines,
+            vec![
+                "dogs\ttrue\toewn-dog-n,oewn-dog-v\tn,v\toewn-dog-n,oewn-chase-v\t\
+                 a\\tdomestic\\ncanine; a pet | go after"
+            ]
+        );
+        assert_eq!(lines[0].split('\t').count(), BATCH_TSV_HEADER.len());
+
+        let not_found = BatchRecord {
+            word: "xyzzy".to_string(),
+            found: false,
+            entries: vec![],
+        };
+        let lines = tsv_lines(|out| write_batch_tsv(out, &not_found));
+        assert_eq!(lines, vec!["xyzzy\tfalse\t\t\t\t"]);
+    }
+
+    #[test]
+    fn test_write_tree_tsv() {
+        let node = |id: &str, rel_type: Option<&str>, children| TreeNodeRecord {
+            id: id.to_string(),
+            part_of_speech: PartOfSpeech::N,
+            lemmas: vec![id.to_string(), format!("{}\\alt", id)],
+            rel_type: rel_type.map(str::to_string),
+            repeated: false,
+            children,
+            omitted_children: 0,
+        };
+        let mut repeated = node("b", Some("hyponym"), vec![]);
+        repeated.repeated = true;
+        let root = node(
+            "a",
+            None,
+            vec![
+                node(
+                    "b",
+                    Some("hyponym"),
+                    vec![node("c", Some("hyponym"), vec![])],
+                ),
+                repeated,
+            ],
+        );
+        let lines = tsv_lines(|out| write_tree_tsv(out, &root));
+        assert_eq!(
+            lines,
+            vec![
+                "a\t0\t\ta\tn\ta, a\\\\alt\tfalse",
+                "a\t1\thyponym\tb\tn\tb, b\\\\alt\tfalse",
+                "a\t2\thyponym\tc\tn\tc, c\\\\alt\tfalse",
+                "a\t1\thyponym\tb\tn\tb, b\\\\alt\ttrue",
+            ]
+        );
+        assert!(
+            lines
+                .iter()
+                .all(|line| line.split('\t').count() == TREE_TSV_HEADER.len())
+        );
+    }
+
+    #[test]
+    fn test_pos_code() {
+        assert_eq!(pos_code(PartOfSpeech::N), "n");
+        assert_eq!(pos_code(PartOfSpeech::A), "a");
+        assert_eq!(pos_code(PartOfSpeech::S), "s");
+        assert_eq!(pos_code(PartOfSpeech::R), "r");
+    }
+}
@@ -2,26 +2,334 @@
 //!
 //! This module handles parsing the WN-LMF (WordNet Lexical Markup Framework)
 //! XML format into Rust data structures using the quick-xml crate.
+//!
+//! [`LmfReader`] streams through a document with `quick_xml::Reader` and yields one
+//! [`LmfItem`] per lexicon, lexical entry and synset, so memory use stays constant
+//! regardless of the document size. Each item is deserialized with serde on its own.
 
 use crate::error::{OewnError, Result};
-use crate::models::LexicalResource;
-use log::debug;
+use crate::models::{LexicalEntry, LexicalResource, Lexicon, Requires, Synset, SyntacticBehaviour};
+use flate2::read::GzDecoder;
+use log::{debug, warn};
 use quick_xml::de::from_str;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::task;
 
+/// Magic bytes at the start of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A top-level item of a WN-LMF document, as emitted by [`LmfReader`].
+// Items are handed over one at a time, so boxing the larger variants buys nothing.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LmfItem {
+    /// A lexicon's attributes and requirements. Its `lexical_entries` and `synsets`
+    /// are always empty; they are emitted as separate items that follow it.
+    Lexicon(Lexicon),
+    /// A lexical entry of the most recently emitted lexicon
+    LexicalEntry(LexicalEntry),
+    /// A synset of the most recently emitted lexicon
+    Synset(Synset),
+    /// A syntactic frame declared at the level of the most recently emitted lexicon
+    SyntacticBehaviour(SyntacticBehaviour),
+}
+
+/// Counts the bytes read from the underlying reader.
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Event-driven WN-LMF parser yielding [`LmfItem`]s as they are read.
+pub struct LmfReader<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// Lexicon whose start tag has been read but which has not been emitted yet,
+    /// so that its `Requires` children can still be attached.
+    pending_lexicon: Option<Lexicon>,
+    /// Item read while a pending lexicon was emitted in its place
+    queued: Option<LmfItem>,
+    /// Whether a `<LexicalResource>` and a `<Lexicon>` have been started but not ended,
+    /// so that a document cut short between elements is reported
+    in_resource: bool,
+    in_lexicon: bool,
+    /// Bytes consumed from the source file, if reading from one
+    consumed: Option<Arc<AtomicU64>>,
+    total_bytes: Option<u64>,
+    finished: bool,
+}
+
+impl<R: BufRead> LmfReader<R> {
+    /// Creates a parser reading uncompressed XML from `reader`.
+    pub fn new(reader: R) -> Self {
+        LmfReader {
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            pending_lexicon: None,
+            queued: None,
+            in_resource: false,
+            in_lexicon: false,
+            consumed: None,
+            total_bytes: None,
+            finished: false,
+        }
+    }
+
+    /// Number of bytes consumed so far.
+    ///
+    /// For readers opened with [`open_lmf`] this counts bytes of the file on disk,
+    /// i.e. compressed bytes for a gzip archive, so it can be compared to [`Self::total_bytes`].
+    pub fn bytes_read(&self) -> u64 {
+        match &self.consumed {
+            Some(consumed) => consumed.load(Ordering::Relaxed),
+            None => self.reader.buffer_position(),
+        }
+    }
+
+    /// Size of the source file in bytes, if known.
+    pub fn total_bytes(&self) -> Option<u64> {
+        self.total_bytes
+    }
+
+    /// Reads the next item, returning `None` at the end of the document.
+    pub fn next_item(&mut self) -> Result<Option<LmfItem>> {
+        if let Some(item) = self.queued.take() {
+            return Ok(Some(item));
+        }
+        loop {
+            self.buf.clear();
+            let (start, is_empty) = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => (e.into_owned(), false),
+                Event::Empty(e) => (e.into_owned(), true),
+                Event::End(e) if e.name().as_ref() == b"Lexicon" => {
+                    self.in_lexicon = false;
+                    // A lexicon without entries or synsets
+                    if let Some(lexicon) = self.pending_lexicon.take() {
+                        return Ok(Some(LmfItem::Lexicon(lexicon)));
+                    }
+                    continue;
+                }
+                Event::End(e) if e.name().as_ref() == b"LexicalResource" => {
+                    self.in_resource = false;
+                    continue;
+                }
+                Event::Eof => {
+                    let open = if self.in_lexicon {
+                        Some("Lexicon")
+                    } else if self.in_resource {
+                        Some("LexicalResource")
+                    } else {
+                        None
+                    };
+                    if let Some(name) = open {
+                        return Err(OewnError::ParseError(format!(
+                            "Unexpected end of document inside <{}>",
+                            name
+                        )));
+                    }
+                    return Ok(None);
+                }
+                _ => continue,
+            };
+
+            let item = match start.name().as_ref() {
+                b"LexicalResource" => {
+                    self.in_resource = !is_empty;
+                    continue;
+                }
+                b"Lexicon" => {
+                    let lexicon: Lexicon = from_str(&self.read_fragment(start, true)?)?;
+                    if is_empty {
+                        return Ok(Some(LmfItem::Lexicon(lexicon)));
+                    }
+                    self.in_lexicon = true;
+                    self.pending_lexicon = Some(lexicon);
+                    continue;
+                }
+                b"Requires" => {
+                    let requires: Requires = self.parse_element(start, is_empty)?;
+                    match &mut self.pending_lexicon {
+                        Some(lexicon) => lexicon.requires.push(requires),
+                        None => warn!("Ignoring <Requires> after the lexicon's entries."),
+                    }
+                    continue;
+                }
+                b"LexicalEntry" => LmfItem::LexicalEntry(self.parse_element(start, is_empty)?),
+                b"Synset" => LmfItem::Synset(self.parse_element(start, is_empty)?),
+                b"SyntacticBehaviour" => {
+                    LmfItem::SyntacticBehaviour(self.parse_element(start, is_empty)?)
+                }
+                other => {
+                    debug!(
+                        "Skipping unsupported element <{}>",
+                        String::from_utf8_lossy(other)
+                    );
+                    if !is_empty {
+                        let end = start.to_end().into_owned();
+                        self.reader.read_to_end_into(end.name(), &mut self.buf)?;
+                    }
+                    continue;
+                }
+            };
+
+            return Ok(Some(match self.pending_lexicon.take() {
+                Some(lexicon) => {
+                    self.queued = Some(item);
+                    LmfItem::Lexicon(lexicon)
+                }
+                None => item,
+            }));
+        }
+    }
+
+    /// Reads the element started by `start` and deserializes it on its own.
+    fn parse_element<T: DeserializeOwned>(
+        &mut self,
+        start: BytesStart<'static>,
+        is_empty: bool,
+    ) -> Result<T> {
+        let fragment = self.read_fragment(start, is_empty)?;
+        Ok(from_str(&fragment)?)
+    }
+
+    /// Re-serializes the element started by `start`, including all of its children.
+    ///
+    /// If `start_only` is set, only the start tag (with its attributes) is serialized,
+    /// as an empty element, and the children are left for the caller to read.
+    fn read_fragment(&mut self, start: BytesStart<'static>, start_only: bool) -> Result<String> {
+        let mut writer = Writer::new(Vec::new());
+        if start_only {
+            writer.write_event(Event::Empty(start))?;
+        } else {
+            let name = start.name().as_ref().to_vec();
+            writer.write_event(Event::Start(start))?;
+            let mut depth = 1usize;
+            while depth > 0 {
+                self.buf.clear();
+                let event = self.reader.read_event_into(&mut self.buf)?;
+                match &event {
+                    Event::Start(_) => depth += 1,
+                    Event::End(_) => depth -= 1,
+                    Event::Eof => {
+                        return Err(OewnError::ParseError(format!(
+                            "Unexpected end of document inside <{}>",
+                            String::from_utf8_lossy(&name)
+                        )));
+                    }
+                    _ => {}
+                }
+                writer.write_event(event)?;
+            }
+        }
+        String::from_utf8(writer.into_inner())
+            .map_err(|e| OewnError::ParseError(format!("Invalid UTF-8 in LMF data: {}", e)))
+    }
+}
+
+impl<R: BufRead> Iterator for LmfReader<R> {
+    type Item = Result<LmfItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let item = self.next_item().transpose();
+        if !matches!(item, Some(Ok(_))) {
+            self.finished = true;
+        }
+        item
+    }
+}
+
+/// Opens a WN-LMF file for streaming, decompressing it on the fly if it is gzipped.
+///
+/// Gzip archives are detected by their magic bytes rather than the file extension.
+pub fn open_lmf(path: &Path) -> Result<LmfReader<Box<dyn BufRead + Send>>> {
+    let file = File::open(path).map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            OewnError::DataFileNotFound(format!("{:?}", path))
+        } else {
+            OewnError::Io(e)
+        }
+    })?;
+    let total_bytes = file.metadata()?.len();
+    let consumed = Arc::new(AtomicU64::new(0));
+    let mut source = BufReader::new(CountingReader {
+        inner: file,
+        count: consumed.clone(),
+    });
+
+    let reader: Box<dyn BufRead + Send> = if source.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        debug!("Streaming gzipped LMF data from {:?}", path);
+        Box::new(BufReader::new(GzDecoder::new(source)))
+    } else {
+        debug!("Streaming LMF data from {:?}", path);
+        Box::new(source)
+    };
+
+    let mut lmf_reader = LmfReader::new(reader);
+    lmf_reader.consumed = Some(consumed);
+    lmf_reader.total_bytes = Some(total_bytes);
+    Ok(lmf_reader)
+}
+
+/// Collects streamed items into a complete LexicalResource.
+fn collect_resource<R: BufRead>(reader: LmfReader<R>) -> Result<LexicalResource> {
+    let mut resource = LexicalResource { lexicons: vec![] };
+    for item in reader {
+        let item = item?;
+        let current = match item {
+            LmfItem::Lexicon(lexicon) => {
+                resource.lexicons.push(lexicon);
+                continue;
+            }
+            _ => resource.lexicons.last_mut().ok_or_else(|| {
+                OewnError::ParseError("LMF entry found outside of a <Lexicon>".to_string())
+            })?,
+        };
+        match item {
+            LmfItem::LexicalEntry(entry) => current.lexical_entries.push(entry),
+            LmfItem::Synset(synset) => current.synsets.push(synset),
+            LmfItem::SyntacticBehaviour(behaviour) => current.syntactic_behaviours.push(behaviour),
+            LmfItem::Lexicon(_) => {}
+        }
+    }
+    Ok(resource)
+}
+
 /// Parses WN-LMF XML content into a LexicalResource struct.
+///
+/// This holds the whole document in memory; prefer [`open_lmf`] or [`LmfReader`]
+/// for full OEWN releases.
 pub async fn parse_lmf(xml_content: String) -> Result<LexicalResource> {
     debug!("Starting WN-LMF XML parsing (using spawn_blocking)...");
     let resource: LexicalResource = task::spawn_blocking(move || -> Result<LexicalResource> {
-        from_str(&xml_content).map_err(OewnError::from)
+        collect_resource(LmfReader::new(xml_content.as_bytes()))
     })
     .await??;
     debug!("Successfully parsed WN-LMF XML into LexicalResource.");
     Ok(resource)
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::AdjPosition;
 
     const MINIMAL_LMF_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE LexicalResource SYSTEM "http://globalwordnet.github.io/schemas/WN-LMF-1.3.dtd">
@@ -105,4 +413,134 @@ mod tests {
         assert!(!entry.pronunciations[1].phonemic);
         assert_eq!(entry.pronunciations[1].audio, None);
     }
+
+    #[test]
+    fn test_lmf_reader_streams_items() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="first" label="First" language="en" email="a@b.c" license="l" version="1">
+    <Requires id="base" version="2"/>
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="cat &amp; dog" partOfSpeech="n"/>
+      <Sense id="s1" synset="syn1"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="n" members="s1"/>
+  </Lexicon>
+  <Lexicon id="empty" label="Empty" language="en" email="a@b.c" license="l" version="1"/>
+</LexicalResource>
+"#;
+        let items: Vec<LmfItem> = LmfReader::new(xml.as_bytes())
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(items.len(), 4);
+        match &items[0] {
+            LmfItem::Lexicon(lexicon) => {
+                assert_eq!(lexicon.id, "first");
+                assert_eq!(lexicon.requires[0].id, "base");
+                assert!(lexicon.lexical_entries.is_empty());
+            }
+            other => panic!("Expected lexicon, got {:?}", other),
+        }
+        match &items[1] {
+            LmfItem::LexicalEntry(entry) => assert_eq!(entry.lemma.written_form, "cat & dog"),
+            other => panic!("Expected entry, got {:?}", other),
+        }
+        assert!(matches!(&items[2], LmfItem::Synset(s) if s.id == "syn1"));
+        assert!(matches!(&items[3], LmfItem::Lexicon(l) if l.id == "empty"));
+
+        // Truncated documents are reported rather than silently cut short
+        let truncated = &xml[..xml.find("<Sense").unwrap()];
+        let result: Result<Vec<LmfItem>> = LmfReader::new(truncated.as_bytes()).collect();
+        assert!(result.is_err());
+
+        // Including between elements, after the last complete one
+        for end in ["</Lexicon>", "</LexicalResource>"] {
+            let truncated = &xml[..xml.rfind(end).unwrap()];
+            let result: Result<Vec<LmfItem>> = LmfReader::new(truncated.as_bytes()).collect();
+            assert!(
+                matches!(result, Err(OewnError::ParseError(ref message)) if message.starts_with("Unexpected end of document")),
+                "{:?}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_lmf_gz_reports_bytes() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.xml.gz");
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(MINIMAL_LMF_XML.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let mut reader = open_lmf(&path).unwrap();
+        let total = std::fs::metadata(&path).unwrap().len();
+        assert_eq!(reader.total_bytes(), Some(total));
+        let mut count = 0;
+        while reader.next_item().unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert_eq!(reader.bytes_read(), total);
+    }
+
+    #[test]
+    fn test_lmf_reader_parses_forms_and_metadata() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    <LexicalEntry id="w1">
+      <Lemma writtenForm="run" partOfSpeech="v" script="Latn">
+        <Tag category="register">informal</Tag>
+      </Lemma>
+      <Form writtenForm="ran"><Pronunciation variety="en-GB-fonipa">ræn</Pronunciation></Form>
+      <Sense id="s1" synset="syn1" n="2" adjposition="p" subcat="vframe-1 vframe-2" lexicalized="false">
+        <Count>12</Count>
+      </Sense>
+      <SyntacticBehaviour subcategorizationFrame="Somebody ----s" senses="s1"/>
+    </LexicalEntry>
+    <Synset id="syn1" partOfSpeech="v" members="s1" lexfile="verb.motion" dc:source="PWN" confidenceScore="0.9">
+      <Definition dc:source="PWN">move fast</Definition>
+    </Synset>
+    <SyntacticBehaviour id="vframe-1" subcategorizationFrame="Something ----s"/>
+  </Lexicon>
+</LexicalResource>
+"#;
+        let items: Vec<LmfItem> = LmfReader::new(xml.as_bytes())
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(items.len(), 4);
+
+        let LmfItem::LexicalEntry(entry) = &items[1] else {
+            panic!("Expected entry, got {:?}", items[1]);
+        };
+        assert_eq!(entry.lemma.script.as_deref(), Some("Latn"));
+        assert_eq!(entry.lemma.tags[0].category, "register");
+        assert_eq!(entry.forms[0].written_form, "ran");
+        assert_eq!(entry.forms[0].pronunciations[0].text, "ræn");
+        let sense = &entry.senses[0];
+        assert_eq!(sense.n, Some(2));
+        assert!(!sense.lexicalized);
+        assert_eq!(sense.adjposition, Some(AdjPosition::P));
+        assert_eq!(sense.subcat.as_deref(), Some("vframe-1 vframe-2"));
+        assert_eq!(sense.counts[0].value, 12);
+        assert_eq!(entry.syntactic_behaviours[0].senses.as_deref(), Some("s1"));
+
+        let LmfItem::Synset(synset) = &items[2] else {
+            panic!("Expected synset, got {:?}", items[2]);
+        };
+        assert_eq!(synset.lexfile.as_deref(), Some("verb.motion"));
+        assert_eq!(synset.metadata.dc_source.as_deref(), Some("PWN"));
+        assert_eq!(synset.metadata.confidence_score.as_deref(), Some("0.9"));
+        assert_eq!(synset.definitions[0].dc_source.as_deref(), Some("PWN"));
+
+        assert!(matches!(
+            &items[3],
+            LmfItem::SyntacticBehaviour(b) if b.id.as_deref() == Some("vframe-1")
+        ));
+    }
 }
@@ -0,0 +1,567 @@
+//! Search over the WordNet database beyond exact lemma lookups.
+//!
+//! Glosses (definitions and examples) are searched through the `gloss_fts` FTS5
+//! index, ranked with BM25. Lemma completion matches prefixes of whole lemmas and of
+//! the later words of multi-word lemmas, ranked by how common the lemma is. Lemmas can
+//! also be matched against wildcard patterns or regular expressions, with results
+//! streamed in batches, and misspelled words matched to lemmas within an edit distance.
+
+use crate::db::{stored_parts_of_speech, string_to_part_of_speech, trigrams};
+use crate::error::{OewnError, Result};
+use crate::models::{PartOfSpeech, Synset};
+use regex::{Regex, RegexBuilder};
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::Value;
+use rusqlite::{Connection, params, params_from_iter};
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Marks the start of a matched term in [`GlossMatch::snippet`].
+pub const HIGHLIGHT_START: &str = "[";
+/// Marks the end of a matched term in [`GlossMatch::snippet`].
+pub const HIGHLIGHT_END: &str = "]";
+
+/// Relative BM25 weight of matches in definitions compared to examples.
+const DEFINITION_WEIGHT: f64 = 2.0;
+/// Maximum number of tokens in a snippet.
+const SNIPPET_TOKENS: i32 = 16;
+/// Number of lemma matches fetched per query while streaming.
+const LEMMA_BATCH_SIZE: i64 = 512;
+/// Most trigrams a single edit can change: the three overlapping a substituted,
+/// inserted or deleted character, or the four overlapping a transposed pair.
+const TRIGRAMS_PER_EDIT: usize = 4;
+
+/// A synset whose glosses match a full-text query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlossMatch {
+    pub synset: Synset,
+    /// Extract of the best matching gloss, with matches between
+    /// [`HIGHLIGHT_START`] and [`HIGHLIGHT_END`]
+    pub snippet: String,
+    /// BM25 relevance; higher is better
+    pub score: f64,
+}
+
+/// A lemma completing a prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub lemma: String,
+    /// Parts of speech of the lemma's entries
+    pub parts_of_speech: Vec<PartOfSpeech>,
+    /// Number of senses across the lemma's entries
+    pub sense_count: u32,
+    /// Sum of the corpus tag counts of those senses
+    pub tag_count: u32,
+}
+
+/// A lemma spelled similarly to a word, as returned by [`crate::WordNet::suggest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub lemma: String,
+    /// Edit distance between the word and the lemma, ignoring case
+    pub distance: usize,
+}
+
+/// How a lemma pattern is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatternMode {
+    /// Wildcards matching the whole lemma: `*` matches any characters, `?` one
+    /// character and `[...]` one character of a set (e.g. "c?t", "*ology")
+    #[default]
+    Glob,
+    /// A regular expression matching anywhere in the lemma; anchor it with `^` and `$`
+    /// (e.g. "^un.*able$")
+    Regex,
+}
+
+impl fmt::Display for PatternMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternMode::Glob => write!(f, "glob"),
+            PatternMode::Regex => write!(f, "regex"),
+        }
+    }
+}
+
+impl FromStr for PatternMode {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "glob" | "wildcard" => Ok(PatternMode::Glob),
+            "regex" | "regexp" => Ok(PatternMode::Regex),
+            _ => Err(format!("Invalid pattern mode: {}", s)),
+        }
+    }
+}
+
+/// A lexical entry whose lemma matches a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LemmaMatch {
+    pub entry_id: String,
+    pub lemma: String,
+    pub part_of_speech: PartOfSpeech,
+}
+
+/// Streaming iterator over the entries matching a lemma pattern, in source order.
+///
+/// Matches are fetched in batches, and the database is only locked while a batch is
+/// fetched, so other `WordNet` methods can be called while iterating.
+pub struct LemmaMatches {
+    conn: Arc<Mutex<Connection>>,
+    /// Pattern as bound to the query (lowercased for globs, case-insensitive for regexes)
+    pattern: String,
+    mode: PatternMode,
+    pos: Option<PartOfSpeech>,
+    /// Rowid of the last entry fetched; rowids follow document order
+    last_rowid: i64,
+    batch: std::vec::IntoIter<LemmaMatch>,
+    finished: bool,
+}
+
+impl LemmaMatches {
+    /// Prepares a search; regular expressions are validated here rather than on the
+    /// first fetch.
+    pub(crate) fn new(
+        conn: Arc<Mutex<Connection>>,
+        pattern: &str,
+        mode: PatternMode,
+        pos: Option<PartOfSpeech>,
+    ) -> Result<Self> {
+        let pattern = match mode {
+            PatternMode::Glob => pattern.to_lowercase(),
+            PatternMode::Regex => {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| {
+                        OewnError::InvalidArgument(format!("Invalid regular expression: {}", e))
+                    })?;
+                format!("(?i){}", pattern)
+            }
+        };
+        Ok(LemmaMatches {
+            conn,
+            pattern,
+            mode,
+            pos,
+            last_rowid: 0,
+            batch: Vec::new().into_iter(),
+            finished: false,
+        })
+    }
+
+    /// Fetches the next batch of matches after `last_rowid`.
+    fn fetch_batch(&mut self) -> Result<Vec<LemmaMatch>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| OewnError::Internal("Mutex poisoned".to_string()))?;
+        // GLOB on the lowercase column can use idx_entry_lemma_lower for a literal prefix
+        let condition = match self.mode {
+            PatternMode::Glob => "lemma_written_form_lower GLOB ?1",
+            PatternMode::Regex => "lemma_written_form REGEXP ?1",
+        };
+        let sql = format!(
+            "SELECT rowid, id, lemma_written_form, part_of_speech FROM lexical_entries
+             WHERE {} AND rowid > ?2 AND (?3 IS NULL OR part_of_speech IN (?3, ?4))
+             ORDER BY rowid
+             LIMIT ?5",
+            condition
+        );
+        let (pos_first, pos_second) = self.pos.map(stored_parts_of_speech).unzip();
+        let mut stmt = conn.prepare_cached(&sql)?;
+        let rows = stmt
+            .query_map(
+                params![
+                    self.pattern,
+                    self.last_rowid,
+                    pos_first,
+                    pos_second,
+                    LEMMA_BATCH_SIZE
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        if rows.len() < LEMMA_BATCH_SIZE as usize {
+            self.finished = true;
+        }
+        let mut matches = Vec::with_capacity(rows.len());
+        for (rowid, entry_id, lemma, pos_str) in rows {
+            self.last_rowid = rowid;
+            matches.push(LemmaMatch {
+                entry_id,
+                lemma,
+                part_of_speech: string_to_part_of_speech(&pos_str)?,
+            });
+        }
+        Ok(matches)
+    }
+}
+
+impl Iterator for LemmaMatches {
+    type Item = Result<LemmaMatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(lemma_match) = self.batch.next() {
+                return Some(Ok(lemma_match));
+            }
+            if self.finished {
+                return None;
+            }
+            match self.fetch_batch() {
+                Ok(batch) => self.batch = batch.into_iter(),
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Registers the SQL functions used by searches on a connection.
+///
+/// `regexp(pattern, text)` backs SQLite's `text REGEXP pattern` operator; compiled
+/// patterns are cached for the duration of a statement.
+pub(crate) fn register_functions(conn: &Connection) -> Result<()> {
+    type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let regex: Arc<Regex> = ctx
+                .get_or_create_aux(0, |vr| -> std::result::Result<_, BoxError> {
+                    Ok(Regex::new(vr.as_str()?)?)
+                })?;
+            let text = ctx
+                .get_raw(1)
+                .as_str()
+                .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+            Ok(regex.is_match(text))
+        },
+    )?;
+    Ok(())
+}
+
+/// Converts free text into an FTS5 query matching all of its words.
+///
+/// Each word is quoted, so punctuation and FTS5 operators are matched literally.
+/// A trailing `*` is kept as a prefix match (e.g. "photo*").
+pub(crate) fn to_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .filter_map(|word| {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(stem) => (stem, "*"),
+                None => (word, ""),
+            };
+            (!word.is_empty()).then(|| format!("\"{}\"{}", word.replace('"', "\"\""), prefix))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds synsets whose glosses match `query`, best first.
+///
+/// Returns synset IDs with their snippet and score; adjectives include satellites.
+pub(crate) fn search_glosses(
+    conn: &Connection,
+    query: &str,
+    pos: Option<PartOfSpeech>,
+    limit: usize,
+) -> Result<Vec<(String, String, f64)>> {
+    let fts_query = to_fts_query(query);
+    if fts_query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (pos_first, pos_second) = pos.map(stored_parts_of_speech).unzip();
+
+    // bm25() is lower for better matches; column weights follow the table's columns
+    let sql = format!(
+        "SELECT synset_id,
+            snippet(gloss_fts, -1, '{start}', '{end}', '…', {tokens}),
+            -bm25(gloss_fts, 0.0, 0.0, {weight}, 1.0) AS score
+         FROM gloss_fts
+         WHERE gloss_fts MATCH ?1 AND (?2 IS NULL OR part_of_speech IN (?2, ?3))
+         ORDER BY score DESC, synset_id
+         LIMIT ?4",
+        start = HIGHLIGHT_START,
+        end = HIGHLIGHT_END,
+        tokens = SNIPPET_TOKENS,
+        weight = DEFINITION_WEIGHT,
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let matches = stmt
+        .query_map(params![fts_query, pos_first, pos_second, limit], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(matches)
+}
+
+/// Returns the smallest string greater than every string starting with `prefix`.
+///
+/// Used as an exclusive upper bound so that prefix matches are index range scans.
+fn prefix_upper_bound(prefix: &str) -> String {
+    format!("{}{}", prefix, char::MAX)
+}
+
+/// Finds lemmas that start with `prefix`, or have a later word starting with it.
+///
+/// Lemmas whose whole form matches come first; within each group, lemmas with higher
+/// tag counts, then more senses, then shorter forms are ranked higher.
+pub(crate) fn complete(
+    conn: &Connection,
+    prefix: &str,
+    pos: Option<PartOfSpeech>,
+    limit: usize,
+) -> Result<Vec<Completion>> {
+    let prefix = prefix.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (pos_first, pos_second) = pos.map(stored_parts_of_speech).unzip();
+
+    // Both branches are range scans: on idx_entry_lemma_lower and on the primary key
+    // of lemma_tokens
+    let mut stmt = conn.prepare_cached(
+        "WITH matches(entry_id, whole) AS (
+             SELECT id, 1 FROM lexical_entries
+             WHERE lemma_written_form_lower >= ?1 AND lemma_written_form_lower < ?2
+             UNION ALL
+             SELECT entry_id, 0 FROM lemma_tokens
+             WHERE token >= ?1 AND token < ?2
+         )
+         SELECT le.lemma_written_form,
+             group_concat(le.part_of_speech, ' ') AS parts_of_speech,
+             SUM(le.sense_count), SUM(le.tag_count), MAX(m.whole) AS whole
+         FROM (SELECT entry_id, MAX(whole) AS whole FROM matches GROUP BY entry_id) m
+         JOIN lexical_entries le ON le.id = m.entry_id
+         WHERE ?3 IS NULL OR le.part_of_speech IN (?3, ?4)
+         GROUP BY le.lemma_written_form
+         ORDER BY whole DESC, SUM(le.tag_count) DESC, SUM(le.sense_count) DESC,
+             length(le.lemma_written_form), le.lemma_written_form
+         LIMIT ?5",
+    )?;
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = stmt
+        .query_map(
+            params![
+                prefix,
+                prefix_upper_bound(&prefix),
+                pos_first,
+                pos_second,
+                limit
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, u32>(2)?,
+                    row.get::<_, u32>(3)?,
+                ))
+            },
+        )?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut completions = Vec::with_capacity(rows.len());
+    for (lemma, pos_list, sense_count, tag_count) in rows {
+        let mut parts_of_speech = Vec::new();
+        for pos_str in pos_list.split(' ') {
+            let pos = string_to_part_of_speech(pos_str)?;
+            if !parts_of_speech.contains(&pos) {
+                parts_of_speech.push(pos);
+            }
+        }
+        completions.push(Completion {
+            lemma,
+            parts_of_speech,
+            sense_count,
+            tag_count,
+        });
+    }
+    Ok(completions)
+}
+
+/// Computes the edit distance between two strings.
+///
+/// Counts the insertions, deletions, substitutions and transpositions of adjacent
+/// characters needed to turn `a` into `b` (the optimal string alignment distance).
+///
+/// # Example
+///
+/// ```
+/// use oewn_rs::search::edit_distance;
+///
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// assert_eq!(edit_distance("teh", "the"), 1);
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Rows of the distance matrix for the two previous prefixes of `a` and the current one
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+/// Finds the lemmas that could be within `max_distance` edits of `word`, returning
+/// the lemma, its lowercase form, tag count and sense count.
+///
+/// Only lemmas whose length differs by at most `max_distance` are considered, using the
+/// length column of the trigram index so that postings of other lengths are never read.
+/// By the q-gram lemma, a lemma within `max_distance` edits shares at least the larger of
+/// the two trigram counts minus [`TRIGRAMS_PER_EDIT`] per edit with `word`; candidates
+/// must also share at least one.
+pub(crate) fn suggestion_candidates(
+    conn: &Connection,
+    word: &str,
+    max_distance: usize,
+) -> Result<Vec<(String, String, i64, i64)>> {
+    let word_trigrams = trigrams(word);
+    let length = word.chars().count();
+    let max_changed = max_distance * TRIGRAMS_PER_EDIT;
+    let min_shared = word_trigrams.len().saturating_sub(max_changed).max(1);
+
+    let placeholders: Vec<String> = (0..word_trigrams.len())
+        .map(|i| format!("?{}", i + 5))
+        .collect();
+    let sql = format!(
+        "SELECT le.lemma_written_form, le.lemma_written_form_lower,
+            SUM(le.tag_count), SUM(le.sense_count)
+         FROM (SELECT lemma FROM lemma_trigrams
+               WHERE trigram IN ({}) AND lemma_length BETWEEN ?2 AND ?3
+               GROUP BY lemma HAVING COUNT(*) >= max(?1, MAX(trigram_count) - ?4)) c
+         JOIN lexical_entries le ON le.lemma_written_form_lower = c.lemma
+         GROUP BY le.lemma_written_form",
+        placeholders.join(", ")
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let bounds = [
+        min_shared,
+        length.saturating_sub(max_distance),
+        length.saturating_add(max_distance),
+        max_changed,
+    ]
+    .map(|n| Value::Integer(i64::try_from(n).unwrap_or(i64::MAX)));
+    let params = bounds
+        .into_iter()
+        .chain(word_trigrams.into_iter().map(Value::Text));
+    let candidates = stmt
+        .query_map(params_from_iter(params), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(candidates)
+}
+
+/// Finds the lemmas closest to `word` within `max_distance` edits.
+///
+/// Candidates come from [`suggestion_candidates`] and are each checked with
+/// [`edit_distance`]. Results are ordered by distance, then by tag count and sense count.
+pub(crate) fn suggest(
+    conn: &Connection,
+    word: &str,
+    max_distance: usize,
+    limit: usize,
+) -> Result<Vec<Suggestion>> {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+    let mut rows: Vec<_> = suggestion_candidates(conn, &word, max_distance)?
+        .into_iter()
+        .filter_map(|(lemma, lemma_lower, tag_count, sense_count)| {
+            let distance = edit_distance(&word, &lemma_lower);
+            (distance <= max_distance).then_some((
+                distance,
+                Reverse(tag_count),
+                Reverse(sense_count),
+                lemma,
+            ))
+        })
+        .collect();
+
+    rows.sort();
+    Ok(rows
+        .into_iter()
+        .take(limit)
+        .map(|(distance, _, _, lemma)| Suggestion { lemma, distance })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_fts_query() {
+        assert_eq!(to_fts_query("light  energy"), "\"light\" \"energy\"");
+        assert_eq!(to_fts_query("photo* AND"), "\"photo\"* \"AND\"");
+        assert_eq!(to_fts_query("say \"hi\""), "\"say\" \"\"\"hi\"\"\"");
+        assert_eq!(to_fts_query(" * "), "");
+    }
+
+    #[test]
+    fn test_prefix_upper_bound() {
+        let bound = prefix_upper_bound("cre");
+        assert!("cream" < bound.as_str() && "creéz" < bound.as_str());
+        assert!("crf" > bound.as_str() && "crè" > bound.as_str());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("acress", "caress"), 1);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_regexp_function() {
+        let conn = Connection::open_in_memory().unwrap();
+        register_functions(&conn).unwrap();
+        let is_match = |text: &str, pattern: &str| -> bool {
+            conn.query_row("SELECT ?1 REGEXP ?2", params![text, pattern], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        assert!(is_match("unbelievable", "^un.*able$"));
+        assert!(is_match("zoology", "olog"));
+        assert!(!is_match("Unable", "^un"));
+        assert!(is_match("Unable", "(?i)^un"));
+        assert!(
+            conn.query_row("SELECT 'a' REGEXP '('", [], |row| row.get::<_, bool>(0))
+                .is_err()
+        );
+    }
+}
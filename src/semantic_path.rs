@@ -0,0 +1,266 @@
+//! Shortest chains of relations between synsets.
+//!
+//! Synsets are linked by their synset relations and by the sense relations of their
+//! senses, each followed in its stored direction. The shortest chain between two sets
+//! of synsets is found with a bidirectional breadth-first search, which expands the
+//! smaller frontier one level at a time, following relations forwards from the start
+//! and backwards from the end, and loads the relations of each level in batches.
+
+use crate::error::Result;
+use crate::models::{SenseRelType, SynsetRelType};
+use rusqlite::types::Value;
+use rusqlite::{Connection, params_from_iter};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of frontier synsets whose relations are loaded per query.
+const FRONTIER_BATCH_SIZE: usize = 500;
+
+/// A synset or sense relation type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RelationType {
+    /// A relation between synsets
+    Synset(SynsetRelType),
+    /// A relation between senses, linking their synsets
+    Sense(SenseRelType),
+}
+
+impl fmt::Display for RelationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationType::Synset(rel_type) => f.write_str(rel_type.as_str()),
+            RelationType::Sense(rel_type) => f.write_str(rel_type.as_str()),
+        }
+    }
+}
+
+impl From<SynsetRelType> for RelationType {
+    fn from(rel_type: SynsetRelType) -> Self {
+        RelationType::Synset(rel_type)
+    }
+}
+
+impl From<SenseRelType> for RelationType {
+    fn from(rel_type: SenseRelType) -> Self {
+        RelationType::Sense(rel_type)
+    }
+}
+
+/// A relation on a semantic path, in its stored direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathEdge {
+    /// ID of the synset the relation goes from
+    pub source_synset: String,
+    /// ID of the synset the relation goes to
+    pub target_synset: String,
+    /// Type of the relation
+    pub rel_type: RelationType,
+    /// For a sense relation, the IDs of its source and target senses
+    pub senses: Option<(String, String)>,
+}
+
+/// A chain of relations linking two synsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticPath {
+    /// IDs of the synsets on the path, from start to end
+    pub synsets: Vec<String>,
+    /// The relations between consecutive synsets, so one fewer than the synsets
+    pub edges: Vec<PathEdge>,
+}
+
+impl SemanticPath {
+    /// Returns the number of relations on the path.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns `true` if the path starts and ends at the same synset.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+/// Direction in which one side of the search follows relations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+/// One side of the bidirectional search.
+struct Side {
+    direction: Direction,
+    /// Reached synsets, with their distance and the relation they were reached by
+    reached: HashMap<String, (usize, Option<PathEdge>)>,
+    /// Synsets reached at the latest depth, in order of discovery
+    frontier: Vec<String>,
+    depth: usize,
+}
+
+impl Side {
+    fn new(direction: Direction, synset_ids: &[String]) -> Self {
+        let mut frontier: Vec<String> = Vec::new();
+        for id in synset_ids {
+            if !frontier.contains(id) {
+                frontier.push(id.clone());
+            }
+        }
+        Side {
+            direction,
+            reached: frontier.iter().map(|id| (id.clone(), (0, None))).collect(),
+            frontier,
+            depth: 0,
+        }
+    }
+
+    fn distance(&self, synset_id: &str) -> usize {
+        self.reached
+            .get(synset_id)
+            .map_or(0, |(distance, _)| *distance)
+    }
+
+    /// Reaches the synsets one relation beyond the frontier, and returns those of them
+    /// already reached by `other`.
+    fn expand(
+        &mut self,
+        conn: &Connection,
+        allowed: &[RelationType],
+        other: &Side,
+    ) -> Result<Vec<String>> {
+        let frontier = std::mem::take(&mut self.frontier);
+        self.depth += 1;
+        let mut met = Vec::new();
+        for batch in frontier.chunks(FRONTIER_BATCH_SIZE) {
+            for edge in adjacent_edges(conn, batch, self.direction)? {
+                if !allowed.is_empty() && !allowed.contains(&edge.rel_type) {
+                    continue;
+                }
+                let next = match self.direction {
+                    Direction::Forward => edge.target_synset.clone(),
+                    Direction::Backward => edge.source_synset.clone(),
+                };
+                if self.reached.contains_key(&next) {
+                    continue;
+                }
+                if other.reached.contains_key(&next) {
+                    met.push(next.clone());
+                }
+                self.frontier.push(next.clone());
+                self.reached.insert(next, (self.depth, Some(edge)));
+            }
+        }
+        Ok(met)
+    }
+
+    /// Returns the relations from `synset_id` back to where this side started, nearest
+    /// first.
+    fn edges_from(&self, synset_id: &str) -> Vec<PathEdge> {
+        let mut edges = Vec::new();
+        let mut current = synset_id;
+        while let Some((_, Some(edge))) = self.reached.get(current) {
+            edges.push(edge.clone());
+            current = match self.direction {
+                Direction::Forward => &edge.source_synset,
+                Direction::Backward => &edge.target_synset,
+            };
+        }
+        edges
+    }
+}
+
+/// Finds a shortest path from any synset of `from` to any synset of `to`, following
+/// only `allowed` relation types, or all of them if `allowed` is empty.
+pub(crate) fn shortest_path(
+    conn: &Connection,
+    from: &[String],
+    to: &[String],
+    allowed: &[RelationType],
+) -> Result<Option<SemanticPath>> {
+    let mut forward = Side::new(Direction::Forward, from);
+    let mut backward = Side::new(Direction::Backward, to);
+    if let Some(shared) = forward
+        .frontier
+        .iter()
+        .find(|id| backward.reached.contains_key(*id))
+    {
+        return Ok(Some(join(&forward, &backward, shared)));
+    }
+
+    while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+        let (met, other) = if forward.frontier.len() <= backward.frontier.len() {
+            (forward.expand(conn, allowed, &backward)?, &backward)
+        } else {
+            (backward.expand(conn, allowed, &forward)?, &forward)
+        };
+        // All synsets met are at the same distance from the expanded side, but may be at
+        // different distances from the other
+        if let Some(meeting) = met.iter().min_by_key(|id| other.distance(id)) {
+            return Ok(Some(join(&forward, &backward, meeting)));
+        }
+    }
+    Ok(None)
+}
+
+/// Joins the paths of both sides at the synset where they meet.
+fn join(forward: &Side, backward: &Side, meeting: &str) -> SemanticPath {
+    let mut edges = forward.edges_from(meeting);
+    edges.reverse();
+    edges.extend(backward.edges_from(meeting));
+    let start = edges
+        .first()
+        .map_or(meeting, |edge| edge.source_synset.as_str());
+    let mut synsets = vec![start.to_string()];
+    synsets.extend(edges.iter().map(|edge| edge.target_synset.clone()));
+    SemanticPath { synsets, edges }
+}
+
+/// Loads the synset relations, and the sense relations of the senses, going from
+/// (forward) or to (backward) a batch of synsets.
+fn adjacent_edges(
+    conn: &Connection,
+    synset_ids: &[String],
+    direction: Direction,
+) -> Result<Vec<PathEdge>> {
+    let placeholders: Vec<String> = (1..=synset_ids.len()).map(|i| format!("?{}", i)).collect();
+    let (synset_column, sense_alias) = match direction {
+        Direction::Forward => ("source_synset_id", "ss"),
+        Direction::Backward => ("target_synset_id", "ts"),
+    };
+    // Synset relations first, then sense relations, each in source order
+    let sql = format!(
+        "SELECT source_synset_id, target_synset_id, rel_type, NULL, NULL, 0 AS kind, rowid
+         FROM synset_relations
+         WHERE {synset_column} IN ({ids})
+         UNION ALL
+         SELECT ss.synset_id, ts.synset_id, r.rel_type, r.source_sense_id, r.target_sense_id,
+             1 AS kind, r.rowid
+         FROM sense_relations r
+         JOIN senses ss ON ss.id = r.source_sense_id
+         JOIN senses ts ON ts.id = r.target_sense_id
+         WHERE {sense_alias}.synset_id IN ({ids})
+         ORDER BY kind, rowid",
+        ids = placeholders.join(", "),
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let edges = stmt
+        .query_map(
+            params_from_iter(synset_ids.iter().map(|id| Value::Text(id.clone()))),
+            |row| {
+                let rel_type: String = row.get(2)?;
+                let source_sense: Option<String> = row.get(3)?;
+                let target_sense: Option<String> = row.get(4)?;
+                let senses = source_sense.zip(target_sense);
+                Ok(PathEdge {
+                    source_synset: row.get(0)?,
+                    target_synset: row.get(1)?,
+                    rel_type: match senses {
+                        Some(_) => RelationType::Sense(SenseRelType::from(rel_type.as_str())),
+                        None => RelationType::Synset(SynsetRelType::from(rel_type.as_str())),
+                    },
+                    senses,
+                })
+            },
+        )?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(edges)
+}
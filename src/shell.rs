@@ -0,0 +1,390 @@
+//! Interactive shell over a loaded WordNet.
+//!
+//! The database is opened once for the whole session. Lines are split into words at
+//! whitespace, and double quotes group words, so multi-word lemmas are written
+//! `"ice cream"`. Tab completes command names and, in arguments, lemmas. History is
+//! kept across sessions in a file next to the database.
+
+use crate::output::OutputFormat;
+use crate::{
+    RelationDisplay, handle_define, handle_path, handle_random, handle_search, print_sense_details,
+    synset_lemmas, target_synsets,
+};
+use colored::*;
+use oewn_rs::{OewnError, Synset, SynsetRelType, WordNet, error::Result};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::Path;
+
+/// File name of the shell history, in the database's directory.
+pub const HISTORY_FILE_NAME: &str = "shell_history";
+/// Maximum number of lemmas offered by tab completion.
+const COMPLETION_LIMIT: usize = 50;
+/// Number of glosses listed by `search`.
+const SEARCH_LIMIT: usize = 10;
+
+const PROMPT: &str = "oewn> ";
+
+/// Commands with their arguments and description, as listed by `help`.
+const COMMANDS: &[(&str, &str, &str)] = &[
+    ("define", "<word> [pos]", "Define a word"),
+    (
+        "hyper",
+        "<word|synset>",
+        "List the hypernyms of a word's synsets",
+    ),
+    (
+        "hypo",
+        "<word|synset>",
+        "List the hyponyms of a word's synsets",
+    ),
+    ("synset", "<id>", "Show a synset"),
+    (
+        "path",
+        "<from> <to> [relations]",
+        "Show the shortest chain of relations between two words",
+    ),
+    ("search", "<words>", "Search definitions and examples"),
+    ("random", "", "Show a random word"),
+    ("help", "", "List the commands"),
+    ("exit", "", "Leave the shell (also Ctrl-D)"),
+];
+
+/// Commands whose arguments are completed as lemmas.
+const LEMMA_COMMANDS: &[&str] = &["define", "hyper", "hypo", "path"];
+
+/// Runs the shell until `exit` or end of input.
+pub async fn run(wn: &WordNet, history_path: Option<&Path>) -> Result<()> {
+    let mut editor: Editor<ShellHelper, _> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(ShellHelper { wn }));
+    if let Some(path) = history_path
+        && path.exists()
+        && let Err(e) = editor.load_history(path)
+    {
+        log::warn!(
+            "Could not load shell history from {}: {}",
+            path.display(),
+            e
+        );
+    }
+
+    println!("OEWN shell. Type 'help' for commands, 'exit' to quit.");
+    // Line editor failures end the session, but only once the history is saved
+    let result = loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            // Ctrl-C abandons the current line
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break Ok(()),
+            Err(e) => break Err(readline_error(e)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Err(e) = editor.add_history_entry(line.as_str()) {
+            break Err(readline_error(e));
+        }
+
+        match execute(wn, &split_words(&line)).await {
+            Ok(true) => {}
+            Ok(false) => break Ok(()),
+            Err(e) => eprintln!("{}", format!("Error: {}", e).red()),
+        }
+    };
+
+    if let Some(path) = history_path
+        && let Err(e) = editor.save_history(path)
+    {
+        log::warn!("Could not save shell history to {}: {}", path.display(), e);
+    }
+    result
+}
+
+/// Runs one command; returns `false` if the shell should exit.
+async fn execute(wn: &WordNet, words: &[String]) -> Result<bool> {
+    let Some((command, args)) = words.split_first() else {
+        return Ok(true);
+    };
+    let text = OutputFormat::Text;
+    match (command.as_str(), args) {
+        ("define", [word]) => {
+            handle_define(wn, word, None, &RelationDisplay::Summary, text).await?
+        }
+        ("define", [word, pos]) => {
+            let pos = pos.parse().map_err(OewnError::InvalidArgument)?;
+            handle_define(wn, word, Some(pos), &RelationDisplay::Summary, text).await?
+        }
+        ("hyper", [target]) => print_related(wn, target, SynsetRelType::Hypernym)?,
+        ("hypo", [target]) => print_related(wn, target, SynsetRelType::Hyponym)?,
+        ("synset", [id]) => print_synset(wn, id)?,
+        ("path", [from, to, relations @ ..]) => handle_path(wn, from, to, relations, text)?,
+        ("search", [_, ..]) => handle_search(wn, &args.join(" "), None, SEARCH_LIMIT, text)?,
+        ("random", []) => handle_random(wn, text).await?,
+        ("help", []) => print_help(),
+        ("exit" | "quit", []) => return Ok(false),
+        (command, _) => match COMMANDS.iter().find(|(name, _, _)| *name == command) {
+            Some((name, usage, _)) => {
+                return Err(OewnError::InvalidArgument(format!(
+                    "Usage: {} {}",
+                    name, usage
+                )));
+            }
+            None => {
+                return Err(OewnError::InvalidArgument(format!(
+                    "Unknown command '{}'; type 'help' for commands",
+                    command
+                )));
+            }
+        },
+    }
+    Ok(true)
+}
+
+fn print_help() {
+    for (name, args, description) in COMMANDS {
+        let usage = format!("{} {}", name, args);
+        println!("  {:<32} {}", usage.bold(), description);
+    }
+}
+
+/// Prints a synset with its glosses, members and main relations.
+fn print_synset(wn: &WordNet, synset_id: &str) -> Result<()> {
+    let synset = wn.get_synset(synset_id)?;
+    println!(
+        "\n{} ~ {}",
+        synset.id.bold().cyan(),
+        synset.part_of_speech.to_string().italic()
+    );
+    print_sense_details(wn, "", None, &synset, 1, &RelationDisplay::Summary)
+}
+
+/// Prints the synsets related by `rel_type` to a synset, or to each synset of a word.
+fn print_related(wn: &WordNet, target: &str, rel_type: SynsetRelType) -> Result<()> {
+    let synsets = target_synsets(wn, target, None)?;
+    if synsets.is_empty() {
+        println!("No synsets found for '{}'.", target.yellow());
+        return Ok(());
+    }
+    for synset in synsets {
+        println!("\n{}", synset_label(wn, &synset)?);
+        let related = wn.get_related_synsets(&synset.id, rel_type.clone())?;
+        if related.is_empty() {
+            println!("  {}", format!("No {}s", rel_type).dimmed());
+        }
+        for related_synset in related {
+            println!("  {} {}", "→".dimmed(), synset_label(wn, &related_synset)?);
+        }
+    }
+    Ok(())
+}
+
+fn synset_label(wn: &WordNet, synset: &Synset) -> Result<String> {
+    Ok(format!(
+        "{} ~ {} {}",
+        synset_lemmas(wn, synset)?.join(", ").bold().cyan(),
+        synset.part_of_speech.to_string().italic(),
+        format!("({})", synset.id).dimmed()
+    ))
+}
+
+/// Splits a line into words at whitespace, keeping text between double quotes together.
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Finds the word being typed at the end of `line`: its byte offset, whether it opens
+/// with a quote, and how many words precede it.
+fn current_word(line: &str) -> (usize, bool, usize) {
+    let mut start = 0;
+    let mut quoted = false;
+    let mut opens_quote = false;
+    let mut preceding = 0;
+    let mut in_word = false;
+    for (i, c) in line.char_indices() {
+        if c == '"' {
+            if !in_word {
+                opens_quote = true;
+            }
+            quoted = !quoted;
+            in_word = true;
+        } else if c.is_whitespace() && !quoted {
+            if in_word {
+                preceding += 1;
+                in_word = false;
+            }
+            start = i + c.len_utf8();
+            opens_quote = false;
+        } else {
+            in_word = true;
+        }
+    }
+    (start, opens_quote, preceding)
+}
+
+/// Completes command names and lemmas for the line editor.
+struct ShellHelper<'a> {
+    wn: &'a WordNet,
+}
+
+impl Completer for ShellHelper<'_> {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let (start, opens_quote, preceding) = current_word(line);
+        let prefix = line[start..].trim_start_matches('"');
+
+        if preceding == 0 {
+            let commands = COMMANDS
+                .iter()
+                .filter(|(name, _, _)| name.starts_with(prefix))
+                .map(|(name, _, _)| Pair {
+                    display: name.to_string(),
+                    replacement: format!("{} ", name),
+                })
+                .collect();
+            return Ok((start, commands));
+        }
+        let command = split_words(line).into_iter().next().unwrap_or_default();
+        if prefix.is_empty() || !LEMMA_COMMANDS.contains(&command.as_str()) {
+            return Ok((start, Vec::new()));
+        }
+
+        // Completion failures only mean no candidates
+        let completions = self
+            .wn
+            .complete(prefix, None, COMPLETION_LIMIT)
+            .unwrap_or_default();
+        let candidates = completions
+            .into_iter()
+            .map(|completion| {
+                let replacement = if opens_quote || completion.lemma.contains(' ') {
+                    format!("\"{}\" ", completion.lemma)
+                } else {
+                    format!("{} ", completion.lemma)
+                };
+                Pair {
+                    display: completion.lemma,
+                    replacement,
+                }
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ShellHelper<'_> {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper<'_> {}
+
+impl Validator for ShellHelper<'_> {}
+
+impl Helper for ShellHelper<'_> {}
+
+fn readline_error(e: ReadlineError) -> OewnError {
+    match e {
+        ReadlineError::Io(e) => OewnError::Io(e),
+        e => OewnError::Internal(format!("Line editor error: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words("  define   dog  n "),
+            vec!["define", "dog", "n"]
+        );
+        assert_eq!(
+            split_words("path \"ice cream\" \"hot dog\" hypernym"),
+            vec!["path", "ice cream", "hot dog", "hypernym"]
+        );
+        assert_eq!(
+            split_words("define ice\" \"cream"),
+            vec!["define", "ice cream"]
+        );
+        assert_eq!(split_words("define \"\""), vec!["define", ""]);
+        assert!(split_words("   ").is_empty());
+    }
+
+    #[test]
+    fn test_split_words_unclosed_quote() {
+        // An unclosed quote runs to the end of the line
+        assert_eq!(
+            split_words("define \"ice cream  "),
+            vec!["define", "ice cream  "]
+        );
+        assert_eq!(split_words("search \""), vec!["search", ""]);
+    }
+
+    #[test]
+    fn test_current_word() {
+        assert_eq!(current_word(""), (0, false, 0));
+        assert_eq!(current_word("def"), (0, false, 0));
+        assert_eq!(current_word("define "), (7, false, 1));
+        assert_eq!(current_word("define do"), (7, false, 1));
+        assert_eq!(current_word("define \"ice cr"), (7, true, 1));
+        assert_eq!(current_word("défine ca"), (8, false, 1));
+    }
+
+    #[test]
+    fn test_current_word_after_quoted_argument() {
+        let line = "path \"ice cream\" ho";
+        let (start, opens_quote, preceding) = current_word(line);
+        assert_eq!((&line[start..], opens_quote, preceding), ("ho", false, 2));
+
+        let line = "path \"ice cream\" \"hot d";
+        let (start, opens_quote, preceding) = current_word(line);
+        assert_eq!(
+            (&line[start..], opens_quote, preceding),
+            ("\"hot d", true, 2)
+        );
+
+        // A closed quote still being typed is the current word
+        let line = "path \"ice cream\"";
+        let (start, opens_quote, preceding) = current_word(line);
+        assert_eq!(
+            (&line[start..], opens_quote, preceding),
+            ("\"ice cream\"", true, 1)
+        );
+    }
+}
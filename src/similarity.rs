@@ -0,0 +1,303 @@
+//! Semantic similarity of synsets, based on the hypernym taxonomy.
+//!
+//! Standard measures are offered with the conventions of NLTK's WordNet interface
+//! (without a simulated root, so synsets without a common hypernym have no similarity).
+//! Three are based on path lengths:
+//!
+//! - **Path**: `1 / (1 + d)`, where `d` is the number of steps on the shortest path
+//!   between the synsets through a common hypernym.
+//! - **Leacock–Chodorow**: `-ln((d + 1) / (2 * D))`, where `D` is the maximum depth of
+//!   the taxonomy of the synsets' part of speech. Only defined for synsets with the
+//!   same part of speech.
+//! - **Wu–Palmer**: `2 * depth(lcs) / (len(a) + len(b))`, where depths count synsets
+//!   from the root on the longest path, and `len(x)` is the number of steps from `x` to
+//!   `lcs` plus `depth(lcs)`. As in NLTK, `lcs` is the common hypernym whose shortest
+//!   path to a root is longest, which need not be the deepest one where a synset has
+//!   several hypernyms, so that a synset's similarity to itself can be below 1; the
+//!   first synset itself is preferred, then the lowest ID.
+//!
+//! Three are based on the [information content](crate::information_content) (IC) of
+//! synsets, where `lcs` is the common hypernym with the highest IC. They are only
+//! defined for synsets with the same part of speech:
+//!
+//! - **Resnik**: `IC(lcs)`.
+//! - **Lin**: `2 * IC(lcs) / (IC(a) + IC(b))`.
+//! - **Jiang–Conrath**: `1 / (IC(a) + IC(b) - 2 * IC(lcs))`, which is infinite for
+//!   synsets with the same IC as their subsumer, e.g. a synset and itself.
+
+use crate::error::Result;
+use crate::information_content::information_content;
+use crate::taxonomy::{HypernymGraph, taxonomy_depth};
+use rusqlite::{Connection, params};
+use std::fmt;
+use std::str::FromStr;
+
+/// A taxonomy-based similarity measure between synsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityMeasure {
+    /// Inverse of the shortest path length; between 0 and 1
+    Path,
+    /// Shortest path length scaled by the taxonomy depth, on a log scale
+    LeacockChodorow,
+    /// Depth of the lowest common hypernym relative to the synsets' depths; between 0 and 1
+    WuPalmer,
+    /// Information content of the most informative common hypernym
+    Resnik,
+    /// Information content of the common hypernym relative to the synsets'; between 0 and 1
+    Lin,
+    /// Inverse of the information content distance between the synsets
+    JiangConrath,
+}
+
+impl fmt::Display for SimilarityMeasure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimilarityMeasure::Path => write!(f, "path"),
+            SimilarityMeasure::LeacockChodorow => write!(f, "lch"),
+            SimilarityMeasure::WuPalmer => write!(f, "wup"),
+            SimilarityMeasure::Resnik => write!(f, "res"),
+            SimilarityMeasure::Lin => write!(f, "lin"),
+            SimilarityMeasure::JiangConrath => write!(f, "jcn"),
+        }
+    }
+}
+
+impl FromStr for SimilarityMeasure {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "path" => Ok(SimilarityMeasure::Path),
+            "lch" | "leacock-chodorow" => Ok(SimilarityMeasure::LeacockChodorow),
+            "wup" | "wu-palmer" => Ok(SimilarityMeasure::WuPalmer),
+            "res" | "resnik" => Ok(SimilarityMeasure::Resnik),
+            "lin" => Ok(SimilarityMeasure::Lin),
+            "jcn" | "jiang-conrath" => Ok(SimilarityMeasure::JiangConrath),
+            _ => Err(format!("Invalid similarity measure: {}", s)),
+        }
+    }
+}
+
+/// A synset with the part of the taxonomy above it.
+pub(crate) struct TaxonomyNode {
+    synset_id: String,
+    part_of_speech: String,
+    graph: HypernymGraph,
+    /// Ancestors (the synset included) with their distance, nearest first
+    distances: Vec<(String, usize)>,
+}
+
+impl TaxonomyNode {
+    /// Loads a synset's hypernyms with one query for the whole ancestor set.
+    pub(crate) fn load(conn: &Connection, synset_id: &str) -> Result<Self> {
+        let graph = HypernymGraph::load(conn, synset_id)?;
+        let part_of_speech = conn
+            .prepare_cached("SELECT part_of_speech FROM synsets WHERE id = ?1")?
+            .query_row(params![synset_id], |row| row.get(0))?;
+        let distances = graph.distances(synset_id);
+        Ok(TaxonomyNode {
+            synset_id: synset_id.to_string(),
+            part_of_speech,
+            graph,
+            distances,
+        })
+    }
+
+    /// Returns the distance from this synset to an ancestor, if it is one.
+    fn distance_to(&self, synset_id: &str) -> Option<usize> {
+        self.distances
+            .iter()
+            .find(|(id, _)| id == synset_id)
+            .map(|(_, distance)| *distance)
+    }
+}
+
+/// Returns the hypernyms shared by two synsets (either synset included), with their
+/// distance from each, in order of distance from `a`.
+fn common_hypernyms<'a>(a: &'a TaxonomyNode, b: &TaxonomyNode) -> Vec<(&'a str, usize, usize)> {
+    a.distances
+        .iter()
+        .filter_map(|(id, distance_a)| {
+            b.distance_to(id)
+                .map(|distance_b| (id.as_str(), *distance_a, distance_b))
+        })
+        .collect()
+}
+
+/// Returns the deepest hypernyms shared by two synsets, in order of distance from `a`.
+///
+/// Depth is the longest path to a root. A synset that is a hypernym of the other (or the
+/// same synset) is its own lowest common hypernym.
+pub(crate) fn lowest_common_hypernyms(a: &TaxonomyNode, b: &TaxonomyNode) -> Vec<String> {
+    let common: Vec<(&str, usize)> = common_hypernyms(a, b)
+        .into_iter()
+        .map(|(id, _, _)| (id, a.graph.max_depth(id)))
+        .collect();
+    let deepest = common.iter().map(|(_, depth)| *depth).max();
+    common
+        .into_iter()
+        .filter(|(_, depth)| Some(*depth) == deepest)
+        .map(|(id, _)| id.to_string())
+        .collect()
+}
+
+/// Returns the common hypernym the Wu–Palmer measure is based on, chosen as NLTK's
+/// `wup_similarity` does: among the common hypernyms with the longest shortest path to a
+/// root, `a` itself if it is one, otherwise the one with the lowest ID.
+fn wu_palmer_subsumer<'a>(a: &'a TaxonomyNode, b: &TaxonomyNode) -> Option<&'a str> {
+    let common: Vec<(&str, usize)> = common_hypernyms(a, b)
+        .into_iter()
+        .map(|(id, _, _)| (id, a.graph.min_depth(id)))
+        .collect();
+    let deepest = common.iter().map(|(_, depth)| *depth).max()?;
+    let mut subsumers: Vec<&str> = common
+        .into_iter()
+        .filter(|(_, depth)| *depth == deepest)
+        .map(|(id, _)| id)
+        .collect();
+    if subsumers.contains(&a.synset_id.as_str()) {
+        return Some(&a.synset_id);
+    }
+    subsumers.sort_unstable();
+    subsumers.first().copied()
+}
+
+/// Returns the number of steps on the shortest path between two synsets through a
+/// common hypernym.
+fn shortest_path_distance(a: &TaxonomyNode, b: &TaxonomyNode) -> Option<usize> {
+    common_hypernyms(a, b)
+        .into_iter()
+        .map(|(_, distance_a, distance_b)| distance_a + distance_b)
+        .min()
+}
+
+/// Scores two loaded synsets with `measure`; `None` where the measure is undefined.
+pub(crate) fn similarity(
+    conn: &Connection,
+    a: &TaxonomyNode,
+    b: &TaxonomyNode,
+    measure: SimilarityMeasure,
+) -> Result<Option<f64>> {
+    let score = match measure {
+        SimilarityMeasure::Path => {
+            shortest_path_distance(a, b).map(|distance| 1.0 / (distance as f64 + 1.0))
+        }
+        SimilarityMeasure::LeacockChodorow => {
+            if a.part_of_speech != b.part_of_speech {
+                return Ok(None);
+            }
+            let max_depth = taxonomy_depth(conn, &a.part_of_speech)?.filter(|depth| *depth > 0);
+            match (shortest_path_distance(a, b), max_depth) {
+                (Some(distance), Some(max_depth)) => {
+                    Some(-((distance as f64 + 1.0) / (2.0 * max_depth as f64)).ln())
+                }
+                _ => None,
+            }
+        }
+        SimilarityMeasure::WuPalmer => wu_palmer_subsumer(a, b).and_then(|subsumer| {
+            let depth = (a.graph.max_depth(subsumer) + 1) as f64;
+            let length_a = a.distance_to(subsumer)? as f64 + depth;
+            let length_b = b.distance_to(subsumer)? as f64 + depth;
+            Some(2.0 * depth / (length_a + length_b))
+        }),
+        SimilarityMeasure::Resnik | SimilarityMeasure::Lin | SimilarityMeasure::JiangConrath => {
+            information_content_similarity(conn, a, b, measure)?
+        }
+    };
+    Ok(score)
+}
+
+/// Scores two synsets with one of the information content measures.
+fn information_content_similarity(
+    conn: &Connection,
+    a: &TaxonomyNode,
+    b: &TaxonomyNode,
+    measure: SimilarityMeasure,
+) -> Result<Option<f64>> {
+    if a.part_of_speech != b.part_of_speech {
+        return Ok(None);
+    }
+    let mut subsumer_ic: Option<f64> = None;
+    for (id, _, _) in common_hypernyms(a, b) {
+        if let Some(ic) = information_content(conn, id)?
+            && subsumer_ic.is_none_or(|best| ic > best)
+        {
+            subsumer_ic = Some(ic);
+        }
+    }
+    let Some(subsumer_ic) = subsumer_ic else {
+        return Ok(None);
+    };
+    if measure == SimilarityMeasure::Resnik {
+        return Ok(Some(subsumer_ic));
+    }
+
+    let (Some(ic_a), Some(ic_b)) = (
+        information_content(conn, &a.synset_id)?,
+        information_content(conn, &b.synset_id)?,
+    ) else {
+        return Ok(None);
+    };
+    let score = if measure == SimilarityMeasure::Lin {
+        (ic_a + ic_b > 0.0).then(|| 2.0 * subsumer_ic / (ic_a + ic_b))
+    } else {
+        let distance = ic_a + ic_b - 2.0 * subsumer_ic;
+        Some(if distance > 0.0 {
+            1.0 / distance
+        } else {
+            f64::INFINITY
+        })
+    };
+    Ok(score)
+}
+
+/// Returns the highest score of `measure` over all pairs of synsets from `a` and `b`.
+///
+/// Each synset's taxonomy is loaded once, however many pairs it is part of.
+pub(crate) fn max_similarity(
+    conn: &Connection,
+    a: &[String],
+    b: &[String],
+    measure: SimilarityMeasure,
+) -> Result<Option<f64>> {
+    let nodes_a = a
+        .iter()
+        .map(|id| TaxonomyNode::load(conn, id))
+        .collect::<Result<Vec<_>>>()?;
+    let nodes_b = b
+        .iter()
+        .map(|id| TaxonomyNode::load(conn, id))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut best: Option<f64> = None;
+    for node_a in &nodes_a {
+        for node_b in &nodes_b {
+            if let Some(score) = similarity(conn, node_a, node_b, measure)?
+                && best.is_none_or(|best| score > best)
+            {
+                best = Some(score);
+            }
+        }
+    }
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_measure_from_str() {
+        for measure in [
+            SimilarityMeasure::Path,
+            SimilarityMeasure::LeacockChodorow,
+            SimilarityMeasure::WuPalmer,
+            SimilarityMeasure::Resnik,
+            SimilarityMeasure::Lin,
+            SimilarityMeasure::JiangConrath,
+        ] {
+            assert_eq!(measure.to_string().parse(), Ok(measure));
+        }
+        assert_eq!("Wu-Palmer".parse(), Ok(SimilarityMeasure::WuPalmer));
+        assert!("hso".parse::<SimilarityMeasure>().is_err());
+    }
+}
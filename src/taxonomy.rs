@@ -0,0 +1,304 @@
+//! Navigation of the hypernym taxonomy.
+//!
+//! Both `hypernym` and `instance_hypernym` relations are followed upwards. The part of
+//! the taxonomy above a synset is loaded with a single recursive query, and paths are
+//! then enumerated in memory. The data may contain cycles, so the recursive query only
+//! visits each synset once and no path visits a synset twice.
+//!
+//! The maximum depth of each part of speech's taxonomy, needed to normalise some
+//! similarity measures, is computed once after loading and stored in `taxonomy_depths`.
+
+use crate::db::synset_rel_type_to_string;
+use crate::error::{OewnError, Result};
+use crate::models::SynsetRelType;
+use log::debug;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::collections::{HashMap, VecDeque};
+
+/// Relations followed from a synset to its more general synsets.
+pub const HYPERNYM_REL_TYPES: [SynsetRelType; 2] =
+    [SynsetRelType::Hypernym, SynsetRelType::InstanceHypernym];
+
+/// The hypernym relations above a synset, as `(synset, hypernym)` edges.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HypernymGraph {
+    edges: Vec<(String, String)>,
+}
+
+impl HypernymGraph {
+    /// Loads the hypernym relations of `synset_id` and of all of its ancestors.
+    ///
+    /// Fails with [`OewnError::SynsetNotFound`] if the synset does not exist.
+    pub(crate) fn load(conn: &Connection, synset_id: &str) -> Result<Self> {
+        let exists = conn
+            .prepare_cached("SELECT 1 FROM synsets WHERE id = ?1")?
+            .query_row(params![synset_id], |_| Ok(()))
+            .optional()?;
+        if exists.is_none() {
+            return Err(OewnError::SynsetNotFound(synset_id.to_string()));
+        }
+
+        let [hypernym, instance_hypernym] = hypernym_rel_type_strings();
+        // UNION (rather than UNION ALL) visits each ancestor once, even in a cycle
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE ancestors(id) AS (
+                 SELECT ?1
+                 UNION
+                 SELECT sr.target_synset_id
+                 FROM synset_relations sr
+                 JOIN ancestors a ON sr.source_synset_id = a.id
+                 WHERE sr.rel_type IN (?2, ?3)
+             )
+             SELECT sr.source_synset_id, sr.target_synset_id
+             FROM ancestors a
+             JOIN synset_relations sr ON sr.source_synset_id = a.id
+             WHERE sr.rel_type IN (?2, ?3)
+             ORDER BY sr.rowid",
+        )?;
+        let edges = stmt
+            .query_map(params![synset_id, hypernym, instance_hypernym], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(HypernymGraph { edges })
+    }
+
+    /// Returns the direct hypernyms of a synset, in relation order.
+    pub(crate) fn hypernyms<'a>(&'a self, synset_id: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |(source, _)| source == synset_id)
+            .map(|(_, target)| target.as_str())
+    }
+
+    /// Returns the synsets reachable upwards from `synset_id` (itself included) with the
+    /// fewest hypernym steps to each, nearest first.
+    pub(crate) fn distances(&self, synset_id: &str) -> Vec<(String, usize)> {
+        let mut distances = vec![(synset_id.to_string(), 0)];
+        let mut queue = VecDeque::from([(synset_id.to_string(), 0)]);
+        while let Some((current, distance)) = queue.pop_front() {
+            for hypernym in self.hypernyms(&current) {
+                if !distances.iter().any(|(id, _)| id == hypernym) {
+                    distances.push((hypernym.to_string(), distance + 1));
+                    queue.push_back((hypernym.to_string(), distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the number of steps on the longest hypernym path from `synset_id` to a
+    /// root, which must be `synset_id` itself or one of the loaded ancestors.
+    pub(crate) fn max_depth(&self, synset_id: &str) -> usize {
+        depth_range(&self.paths_to_roots(synset_id)).1
+    }
+
+    /// Returns the number of steps on the shortest hypernym path from `synset_id` to a
+    /// root, which must be `synset_id` itself or one of the loaded ancestors.
+    pub(crate) fn min_depth(&self, synset_id: &str) -> usize {
+        depth_range(&self.paths_to_roots(synset_id)).0
+    }
+
+    /// Enumerates every path from a root to `synset_id`.
+    ///
+    /// Each path starts with a root (a synset without hypernyms) and ends with
+    /// `synset_id`. Hypernyms that would close a cycle are skipped, so a synset whose
+    /// every hypernym lies on a cycle ends the path as if it were a root.
+    pub(crate) fn paths_to_roots(&self, synset_id: &str) -> Vec<Vec<String>> {
+        let mut paths = Vec::new();
+        let mut path = vec![synset_id.to_string()];
+        self.extend_paths(&mut path, &mut paths);
+        paths
+    }
+
+    /// Depth-first extension of `path` (ordered from the synset upwards) to the roots.
+    fn extend_paths(&self, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+        let current = path.last().cloned().unwrap_or_default();
+        let mut extended = false;
+        for hypernym in self.hypernyms(&current) {
+            if path.iter().any(|id| id == hypernym) {
+                continue;
+            }
+            extended = true;
+            path.push(hypernym.to_string());
+            self.extend_paths(path, paths);
+            path.pop();
+        }
+        if !extended {
+            paths.push(path.iter().rev().cloned().collect());
+        }
+    }
+}
+
+/// Returns every path from a root to `synset_id`, see [`crate::WordNet::hypernym_paths`].
+pub(crate) fn hypernym_paths(conn: &Connection, synset_id: &str) -> Result<Vec<Vec<String>>> {
+    Ok(HypernymGraph::load(conn, synset_id)?.paths_to_roots(synset_id))
+}
+
+/// Returns the roots of the paths, in order of first appearance.
+pub(crate) fn roots(paths: &[Vec<String>]) -> Vec<String> {
+    let mut roots: Vec<String> = Vec::new();
+    for root in paths.iter().filter_map(|path| path.first()) {
+        if !roots.contains(root) {
+            roots.push(root.clone());
+        }
+    }
+    roots
+}
+
+/// Returns the number of edges of the shortest and of the longest path.
+pub(crate) fn depth_range(paths: &[Vec<String>]) -> (usize, usize) {
+    let depths = paths.iter().map(|path| path.len().saturating_sub(1));
+    (depths.clone().min().unwrap_or(0), depths.max().unwrap_or(0))
+}
+
+/// Returns the stored names of [`HYPERNYM_REL_TYPES`].
+fn hypernym_rel_type_strings() -> [String; 2] {
+    HYPERNYM_REL_TYPES.map(|rel_type| synset_rel_type_to_string(&rel_type).to_string())
+}
+
+/// Computes the maximum depth of each part of speech's taxonomy and stores it in
+/// `taxonomy_depths`, replacing any previous values.
+///
+/// Called after the data is loaded, within the loading transaction.
+pub(crate) fn store_taxonomy_depths(conn: &Connection) -> Result<()> {
+    let edges = all_hypernym_edges(conn)?;
+    let hypernyms = hypernym_map(&edges);
+    let mut memo = HashMap::new();
+    let mut depths: Vec<(&str, usize)> = Vec::new();
+    for (source, _, pos) in &edges {
+        let depth = longest_chain(source, &hypernyms, &mut Vec::new(), &mut memo);
+        match depths.iter_mut().find(|(p, _)| p == pos) {
+            Some((_, max_depth)) => *max_depth = (*max_depth).max(depth),
+            None => depths.push((pos, depth)),
+        }
+    }
+
+    conn.execute("DELETE FROM taxonomy_depths", [])?;
+    let mut insert_stmt =
+        conn.prepare("INSERT INTO taxonomy_depths (part_of_speech, max_depth) VALUES (?1, ?2)")?;
+    for (pos, depth) in depths {
+        debug!("Maximum taxonomy depth for '{}': {}", pos, depth);
+        insert_stmt.execute(params![pos, depth])?;
+    }
+    Ok(())
+}
+
+/// Loads every hypernym relation, as `(synset, hypernym, synset part of speech)`.
+pub(crate) fn all_hypernym_edges(conn: &Connection) -> Result<Vec<(String, String, String)>> {
+    let [hypernym, instance_hypernym] = hypernym_rel_type_strings();
+    let mut stmt = conn.prepare(
+        "SELECT sr.source_synset_id, sr.target_synset_id, s.part_of_speech
+         FROM synset_relations sr
+         JOIN synsets s ON s.id = sr.source_synset_id
+         WHERE sr.rel_type IN (?1, ?2)
+         ORDER BY sr.rowid",
+    )?;
+    let edges = stmt
+        .query_map(params![hypernym, instance_hypernym], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(edges)
+}
+
+/// Indexes hypernym edges by their source synset.
+pub(crate) fn hypernym_map(edges: &[(String, String, String)]) -> HashMap<&str, Vec<&str>> {
+    let mut hypernyms: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (source, target, _) in edges {
+        hypernyms.entry(source).or_default().push(target);
+    }
+    hypernyms
+}
+
+/// Returns the stored maximum taxonomy depth of a part of speech, if it has hypernyms.
+pub(crate) fn taxonomy_depth(conn: &Connection, pos: &str) -> Result<Option<usize>> {
+    let depth = conn
+        .prepare_cached("SELECT max_depth FROM taxonomy_depths WHERE part_of_speech = ?1")?
+        .query_row(params![pos], |row| row.get(0))
+        .optional()?;
+    Ok(depth)
+}
+
+/// Memoized length of the longest hypernym chain above `synset_id`.
+///
+/// Hypernyms already on the current chain are skipped. With cycles, a memoized depth
+/// may have been computed while part of its chain was skipped, so depths in a cycle
+/// are lower bounds; the data is otherwise a DAG and depths are exact.
+fn longest_chain<'a>(
+    synset_id: &'a str,
+    hypernyms: &HashMap<&'a str, Vec<&'a str>>,
+    chain: &mut Vec<&'a str>,
+    memo: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(depth) = memo.get(synset_id) {
+        return *depth;
+    }
+    chain.push(synset_id);
+    let mut depth = 0;
+    for hypernym in hypernyms.get(synset_id).into_iter().flatten() {
+        if !chain.contains(hypernym) {
+            depth = depth.max(1 + longest_chain(hypernym, hypernyms, chain, memo));
+        }
+    }
+    chain.pop();
+    memo.insert(synset_id, depth);
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> HypernymGraph {
+        HypernymGraph {
+            edges: edges
+                .iter()
+                .map(|(source, target)| (source.to_string(), target.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_paths_to_roots() {
+        // dog -> canine -> animal -> entity, dog -> pet -> animal
+        let taxonomy = graph(&[
+            ("dog", "canine"),
+            ("dog", "pet"),
+            ("canine", "animal"),
+            ("pet", "animal"),
+            ("animal", "entity"),
+        ]);
+        let paths = taxonomy.paths_to_roots("dog");
+        assert_eq!(
+            paths,
+            [
+                ["entity", "animal", "canine", "dog"],
+                ["entity", "animal", "pet", "dog"]
+            ]
+        );
+        assert_eq!(roots(&paths), ["entity"]);
+        assert_eq!(depth_range(&paths), (3, 3));
+        assert_eq!(taxonomy.paths_to_roots("entity"), [["entity"]]);
+        assert_eq!(
+            taxonomy.distances("dog"),
+            [
+                ("dog".to_string(), 0),
+                ("canine".to_string(), 1),
+                ("pet".to_string(), 1),
+                ("animal".to_string(), 2),
+                ("entity".to_string(), 3)
+            ]
+        );
+        assert_eq!(taxonomy.max_depth("pet"), 2);
+    }
+
+    #[test]
+    fn test_paths_to_roots_skips_cycles() {
+        let taxonomy = graph(&[("a", "b"), ("b", "c"), ("c", "a"), ("b", "root")]);
+        let paths = taxonomy.paths_to_roots("a");
+        assert_eq!(paths, [vec!["c", "b", "a"], vec!["root", "b", "a"]]);
+        assert_eq!(roots(&paths), ["c", "root"]);
+        assert_eq!(depth_range(&paths), (2, 2));
+    }
+}
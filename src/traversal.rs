@@ -0,0 +1,249 @@
+//! Transitive traversal of synset and sense relations.
+//!
+//! A traversal follows a set of relation types from a start node, up to an optional
+//! maximum depth, and yields every node reached once, with the depth at which it was
+//! first reached and the relation it was reached by. As for the hypernym taxonomy, the
+//! nodes reachable from the start are found with a single recursive query that visits
+//! each node once, even in a cycle, and their relations are then walked in memory in
+//! source relation order.
+
+use crate::error::{OewnError, Result};
+use crate::models::{SenseRelType, SynsetRelType};
+use rusqlite::types::Value;
+use rusqlite::{Connection, params, params_from_iter};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Order in which a traversal visits nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraversalOrder {
+    /// All nodes at one depth before any node at the next
+    #[default]
+    BreadthFirst,
+    /// Each node's descendants before its next sibling
+    DepthFirst,
+}
+
+/// A node reached by a traversal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraversalStep<R> {
+    /// ID of the synset or sense reached
+    pub id: String,
+    /// Number of relations followed to reach it
+    pub depth: usize,
+    /// The relation by which it was reached
+    pub via: R,
+}
+
+/// Iterator over the nodes reached by a traversal, each once, in traversal order.
+#[derive(Debug, Clone)]
+pub struct Traversal<R> {
+    steps: std::vec::IntoIter<TraversalStep<R>>,
+}
+
+impl<R> Iterator for Traversal<R> {
+    type Item = TraversalStep<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.steps.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+/// Relation types that can be traversed, with the table their relations are stored in.
+pub(crate) trait TraversableRelation: for<'a> From<&'a str> {
+    /// Relations table, with `source_<node>_id`, `target_<node>_id` and `rel_type` columns
+    const TABLE: &'static str;
+    /// Table of the related nodes
+    const NODE_TABLE: &'static str;
+    /// Node name in the relation table's column names
+    const NODE: &'static str;
+
+    fn as_rel_str(&self) -> &str;
+
+    /// The error for a start node that does not exist.
+    fn not_found(id: &str) -> OewnError;
+}
+
+impl TraversableRelation for SynsetRelType {
+    const TABLE: &'static str = "synset_relations";
+    const NODE_TABLE: &'static str = "synsets";
+    const NODE: &'static str = "synset";
+
+    fn as_rel_str(&self) -> &str {
+        self.as_str()
+    }
+
+    fn not_found(id: &str) -> OewnError {
+        OewnError::SynsetNotFound(id.to_string())
+    }
+}
+
+impl TraversableRelation for SenseRelType {
+    const TABLE: &'static str = "sense_relations";
+    const NODE_TABLE: &'static str = "senses";
+    const NODE: &'static str = "sense";
+
+    fn as_rel_str(&self) -> &str {
+        self.as_str()
+    }
+
+    fn not_found(id: &str) -> OewnError {
+        OewnError::SenseNotFound(id.to_string())
+    }
+}
+
+/// Traverses `rel_types` relations from `start`, see [`crate::WordNet::traverse_synsets`].
+pub(crate) fn traverse<R: TraversableRelation>(
+    conn: &Connection,
+    start: &str,
+    rel_types: &[R],
+    max_depth: Option<usize>,
+    order: TraversalOrder,
+) -> Result<Traversal<R>> {
+    let exists = conn
+        .prepare_cached(&format!("SELECT 1 FROM {} WHERE id = ?1", R::NODE_TABLE))?
+        .exists(params![start])?;
+    if !exists {
+        return Err(R::not_found(start));
+    }
+    if rel_types.is_empty() || max_depth == Some(0) {
+        return Ok(Traversal {
+            steps: Vec::new().into_iter(),
+        });
+    }
+
+    let placeholders: Vec<String> = (0..rel_types.len())
+        .map(|i| format!("?{}", i + 3))
+        .collect();
+    // Without a maximum depth, depths are not tracked, so that UNION visits each node
+    // once; with one, each node is visited at most once per depth
+    let sql = format!(
+        "WITH RECURSIVE reachable(id, depth) AS (
+             SELECT ?1, 0
+             UNION
+             SELECT r.target_{node}_id, CASE WHEN ?2 IS NULL THEN 0 ELSE n.depth + 1 END
+             FROM reachable n
+             JOIN {table} r ON r.source_{node}_id = n.id
+             WHERE r.rel_type IN ({rel_types})
+               AND (?2 IS NULL OR n.depth < ?2)
+         )
+         SELECT r.source_{node}_id, r.target_{node}_id, r.rel_type
+         FROM {table} r
+         WHERE r.source_{node}_id IN (SELECT id FROM reachable)
+           AND r.rel_type IN ({rel_types})
+         ORDER BY r.rowid",
+        node = R::NODE,
+        table = R::TABLE,
+        rel_types = placeholders.join(", "),
+    );
+    let params = [
+        Value::Text(start.to_string()),
+        max_depth
+            .map(|depth| Value::Integer(i64::try_from(depth).unwrap_or(i64::MAX)))
+            .unwrap_or(Value::Null),
+    ]
+    .into_iter()
+    .chain(
+        rel_types
+            .iter()
+            .map(|rel_type| Value::Text(rel_type.as_rel_str().to_string())),
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let edges = stmt
+        .query_map(params_from_iter(params), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut targets: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for (source, target, rel_type) in &edges {
+        targets.entry(source).or_default().push((target, rel_type));
+    }
+    let walk = Walk {
+        targets: &targets,
+        max_depth: max_depth.unwrap_or(usize::MAX),
+    };
+    let steps = match order {
+        TraversalOrder::BreadthFirst => walk.breadth_first(start),
+        TraversalOrder::DepthFirst => walk.depth_first(start),
+    };
+    Ok(Traversal {
+        steps: steps.into_iter(),
+    })
+}
+
+/// In-memory walk of the relations loaded for a traversal.
+struct Walk<'a> {
+    /// Targets of each node's relations, with the relation type, in relation order
+    targets: &'a HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+    max_depth: usize,
+}
+
+impl<'a> Walk<'a> {
+    /// Returns the relations followed from `id` as `(target, relation type)`.
+    fn targets(&self, id: &str) -> impl Iterator<Item = &(&'a str, &'a str)> {
+        self.targets.get(id).into_iter().flatten()
+    }
+
+    fn breadth_first<R: TraversableRelation>(&self, start: &'a str) -> Vec<TraversalStep<R>> {
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut steps = Vec::new();
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth == self.max_depth {
+                continue;
+            }
+            for (target, rel_type) in self.targets(id) {
+                if visited.insert(target) {
+                    steps.push(step(target, depth + 1, rel_type));
+                    queue.push_back((target, depth + 1));
+                }
+            }
+        }
+        steps
+    }
+
+    fn depth_first<R: TraversableRelation>(&self, start: &'a str) -> Vec<TraversalStep<R>> {
+        // The shallowest depth each node was reached at. With a maximum depth, a node
+        // reached again by a shorter route is expanded again, as some of its
+        // descendants may only be within the maximum depth by that route.
+        let mut shallowest = HashMap::from([(start, 0)]);
+        let limited = self.max_depth != usize::MAX;
+        let mut steps = Vec::new();
+        // Each entry is a node's next relations to follow, so that the walk is iterative
+        let mut stack = vec![(self.targets(start), 0)];
+        while let Some((targets, depth)) = stack.last_mut() {
+            let depth = *depth;
+            let Some((target, rel_type)) = targets.next() else {
+                stack.pop();
+                continue;
+            };
+            if depth >= self.max_depth {
+                continue;
+            }
+            match shallowest.get(target) {
+                None => steps.push(step(target, depth + 1, rel_type)),
+                Some(&reached) if limited && depth + 1 < reached => {}
+                Some(_) => continue,
+            }
+            shallowest.insert(target, depth + 1);
+            stack.push((self.targets(target), depth + 1));
+        }
+        steps
+    }
+}
+
+fn step<R: TraversableRelation>(id: &str, depth: usize, rel_type: &str) -> TraversalStep<R> {
+    TraversalStep {
+        id: id.to_string(),
+        depth,
+        via: R::from(rel_type),
+    }
+}
@@ -0,0 +1,440 @@
+//! Relation trees of a word's synsets, as drawn by the `tree` command.
+//!
+//! Each synset of the word is the root of a tree whose children are the synsets it is
+//! related to, followed transitively in source relation order. A synset met again after
+//! its children were listed is shown once more, marked as repeated, but not expanded,
+//! which also ends cycles.
+
+use crate::output::{
+    OutputFormat, TREE_TSV_HEADER, TreeNodeRecord, TreeOutput, write_records, write_tree_tsv,
+};
+use crate::{synset_lemmas, target_synsets};
+use colored::*;
+use log::info;
+use oewn_rs::{OewnError, Synset, SynsetRelType, WordNet, error::Result, models::PartOfSpeech};
+use std::collections::HashSet;
+
+/// Maximum number of relations followed down from a root by default, as hyponym trees
+/// near the top of the taxonomy are very large.
+pub const DEFAULT_HYPONYM_DEPTH: usize = 3;
+
+/// Synset relation types grouped under a general relation name.
+const RELATION_GROUPS: &[(&str, &[SynsetRelType])] = &[
+    (
+        "meronym",
+        &[
+            SynsetRelType::Meronym,
+            SynsetRelType::MeroMember,
+            SynsetRelType::MeroPart,
+            SynsetRelType::MeroSubstance,
+            SynsetRelType::MeroLocation,
+            SynsetRelType::MeroPortion,
+        ],
+    ),
+    (
+        "holonym",
+        &[
+            SynsetRelType::Holonym,
+            SynsetRelType::HoloMember,
+            SynsetRelType::HoloPart,
+            SynsetRelType::HoloSubstance,
+            SynsetRelType::HoloLocation,
+            SynsetRelType::HoloPortion,
+        ],
+    ),
+];
+
+/// How a tree is drawn as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeStyle {
+    /// Box-drawing branches
+    Box,
+    /// Plain indentation, with `=>` before each related synset
+    Indent,
+}
+
+/// What to draw and how far.
+#[derive(Debug, Clone)]
+pub struct TreeOptions {
+    /// Relation types followed from each synset
+    pub rel_types: Vec<SynsetRelType>,
+    /// Maximum number of relations followed from the root, if any
+    pub max_depth: Option<usize>,
+    /// Maximum number of children listed per synset, if any
+    pub max_children: Option<usize>,
+    pub style: TreeStyle,
+}
+
+/// Direction in which the taxonomy is followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxonomyDirection {
+    /// Hypernyms, towards the roots
+    Up,
+    /// Hyponyms, towards the leaves
+    Down,
+}
+
+/// Returns the relation types followed in a direction of the taxonomy, including
+/// instance relations.
+pub fn taxonomy_rel_types(direction: TaxonomyDirection) -> Vec<SynsetRelType> {
+    match direction {
+        TaxonomyDirection::Up => vec![SynsetRelType::Hypernym, SynsetRelType::InstanceHypernym],
+        TaxonomyDirection::Down => {
+            vec![SynsetRelType::Hyponym, SynsetRelType::InstanceHyponym]
+        }
+    }
+}
+
+/// Resolves relation names to synset relation types, expanding `meronym` and `holonym`
+/// to all their kinds.
+pub fn parse_tree_relation_types(names: &[String]) -> Result<Vec<SynsetRelType>> {
+    let mut rel_types: Vec<SynsetRelType> = Vec::new();
+    for name in names {
+        let named = match RELATION_GROUPS.iter().find(|(group, _)| group == name) {
+            Some((_, group_types)) => group_types.to_vec(),
+            None => {
+                let rel_type = SynsetRelType::from(name.as_str());
+                if !SynsetRelType::KNOWN.contains(&rel_type) {
+                    return Err(OewnError::InvalidArgument(format!(
+                        "Unknown synset relation type: {}",
+                        name
+                    )));
+                }
+                vec![rel_type]
+            }
+        };
+        for rel_type in named {
+            if !rel_types.contains(&rel_type) {
+                rel_types.push(rel_type);
+            }
+        }
+    }
+    Ok(rel_types)
+}
+
+/// Handles the tree command by drawing a relation tree for each synset of a word.
+pub fn handle_tree(
+    wn: &WordNet,
+    word: &str,
+    pos_filter: Option<PartOfSpeech>,
+    options: &TreeOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    info!(
+        "Drawing the tree of '{}', PoS filter: {:?}, options: {:?}",
+        word, pos_filter, options
+    );
+    let mut trees = Vec::new();
+    for synset in target_synsets(wn, word, pos_filter)? {
+        let mut expanded = HashSet::new();
+        trees.push(build_node(wn, &synset, None, 0, options, &mut expanded)?);
+    }
+
+    if format != OutputFormat::Text {
+        let relations = options.rel_types.iter().map(|r| r.to_string()).collect();
+        return write_records(
+            format,
+            trees,
+            |trees| TreeOutput {
+                word: word.to_string(),
+                relations,
+                trees,
+            },
+            TREE_TSV_HEADER,
+            write_tree_tsv,
+        );
+    }
+    if trees.is_empty() {
+        println!("No synsets found for '{}'.", word.yellow());
+        return Ok(());
+    }
+    // Outside the taxonomy, each relation is named
+    let show_rel_types = options
+        .rel_types
+        .iter()
+        .any(|rel_type| !is_taxonomic(rel_type));
+    for (index, tree) in trees.iter().enumerate() {
+        println!(
+            "\n{}: {} ~ {} {}",
+            (index + 1).to_string().bold(),
+            tree.lemmas.join(", ").bold().cyan(),
+            tree.part_of_speech.to_string().italic(),
+            format!("({})", tree.id).dimmed()
+        );
+        print_children(tree, "", options.style, show_rel_types);
+    }
+    Ok(())
+}
+
+/// Builds the tree of a synset, listing the children of each synset the first time it is
+/// met.
+fn build_node(
+    wn: &WordNet,
+    synset: &Synset,
+    rel_type: Option<&SynsetRelType>,
+    depth: usize,
+    options: &TreeOptions,
+    expanded: &mut HashSet<String>,
+) -> Result<TreeNodeRecord> {
+    let mut node = TreeNodeRecord {
+        id: synset.id.clone(),
+        part_of_speech: synset.part_of_speech,
+        lemmas: synset_lemmas(wn, synset)?,
+        rel_type: rel_type.map(|rel_type| rel_type.to_string()),
+        repeated: expanded.contains(&synset.id),
+        children: Vec::new(),
+        omitted_children: 0,
+    };
+    if node.repeated || options.max_depth.is_some_and(|max| depth >= max) {
+        return Ok(node);
+    }
+    expanded.insert(synset.id.clone());
+
+    let relations: Vec<_> = synset
+        .synset_relations
+        .iter()
+        .filter(|relation| options.rel_types.contains(&relation.rel_type))
+        .collect();
+    let shown = options
+        .max_children
+        .unwrap_or(usize::MAX)
+        .min(relations.len());
+    node.omitted_children = relations.len() - shown;
+    for relation in &relations[..shown] {
+        let target = wn.get_synset(&relation.target)?;
+        let child = build_node(
+            wn,
+            &target,
+            Some(&relation.rel_type),
+            depth + 1,
+            options,
+            expanded,
+        )?;
+        node.children.push(child);
+    }
+    Ok(node)
+}
+
+fn is_taxonomic(rel_type: &SynsetRelType) -> bool {
+    matches!(
+        rel_type,
+        SynsetRelType::Hypernym
+            | SynsetRelType::Hyponym
+            | SynsetRelType::InstanceHypernym
+            | SynsetRelType::InstanceHyponym
+    )
+}
+
+/// Prints the children of a node, each line starting with `prefix`.
+fn print_children(node: &TreeNodeRecord, prefix: &str, style: TreeStyle, show_rel_types: bool) {
+    let count = node.children.len();
+    for (index, child) in node.children.iter().enumerate() {
+        let last = index + 1 == count && node.omitted_children == 0;
+        let (branch, continuation) = match style {
+            TreeStyle::Box if last => ("└── ", "    "),
+            TreeStyle::Box => ("├── ", "│   "),
+            TreeStyle::Indent => ("=> ", "   "),
+        };
+        println!(
+            "{}{}{}",
+            prefix,
+            branch.dimmed(),
+            node_label(child, show_rel_types)
+        );
+        print_children(
+            child,
+            &format!("{}{}", prefix, continuation),
+            style,
+            show_rel_types,
+        );
+    }
+    if node.omitted_children > 0 {
+        let branch = match style {
+            TreeStyle::Box => "└── ",
+            TreeStyle::Indent => "=> ",
+        };
+        println!(
+            "{}{}{}",
+            prefix,
+            branch.dimmed(),
+            format!("… {} more", node.omitted_children).dimmed()
+        );
+    }
+}
+
+fn node_label(node: &TreeNodeRecord, show_rel_types: bool) -> String {
+    let mut label = format!(
+        "{} {}",
+        node.lemmas.join(", ").cyan(),
+        format!("({})", node.id).dimmed()
+    );
+    let marker = match node.rel_type.as_deref() {
+        Some(rel_type) if show_rel_types => Some(rel_type),
+        Some("instance_hypernym") => Some("instance of"),
+        Some("instance_hyponym") => Some("instance"),
+        _ => None,
+    };
+    if let Some(marker) = marker {
+        label = format!("{} {}", marker.magenta(), label);
+    }
+    if node.repeated {
+        label = format!("{} {}", label, "(see above)".yellow());
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oewn_rs::LoadOptions;
+    use tempfile::tempdir;
+
+    /// A diamond, `a` to `d` through both `b` and `c`, closed into a cycle by `d` to `a`.
+    const DIAMOND: &[(&str, &[&str])] = &[
+        ("a", &["b", "c"]),
+        ("b", &["d"]),
+        ("c", &["d"]),
+        ("d", &["a"]),
+    ];
+
+    /// Loads synsets linked by hyponym relations, each with one member lemma named
+    /// after it.
+    async fn load_hyponyms(dir: &std::path::Path, synsets: &[(&str, &[&str])]) -> WordNet {
+        let mut items = String::new();
+        for (id, _) in synsets {
+            items.push_str(&format!(
+                r#"<LexicalEntry id="w-{id}"><Lemma writtenForm="{id}" partOfSpeech="n"/><Sense id="s-{id}" synset="{id}"/></LexicalEntry>"#
+            ));
+        }
+        for (id, targets) in synsets {
+            let relations: String = targets
+                .iter()
+                .map(|target| format!(r#"<SynsetRelation relType="hyponym" target="{target}"/>"#))
+                .collect();
+            items.push_str(&format!(
+                r#"<Synset id="{id}" partOfSpeech="n" members="s-{id}">{relations}</Synset>"#
+            ));
+        }
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LexicalResource xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <Lexicon id="test-en" label="Test" language="en" email="a@b.c" license="l" version="1">
+    {items}
+  </Lexicon>
+</LexicalResource>"#
+        );
+        let source = dir.join("test.xml");
+        std::fs::write(&source, xml).unwrap();
+        let options = LoadOptions {
+            db_path: Some(dir.join("test.db")),
+            source: Some(source),
+            offline: true,
+            ..Default::default()
+        };
+        WordNet::load_with_options(options, None).await.unwrap()
+    }
+
+    fn down_options(max_depth: Option<usize>, max_children: Option<usize>) -> TreeOptions {
+        TreeOptions {
+            rel_types: taxonomy_rel_types(TaxonomyDirection::Down),
+            max_depth,
+            max_children,
+            style: TreeStyle::Box,
+        }
+    }
+
+    fn tree(wn: &WordNet, root: &str, options: &TreeOptions) -> TreeNodeRecord {
+        let synset = wn.get_synset(root).unwrap();
+        build_node(wn, &synset, None, 0, options, &mut HashSet::new()).unwrap()
+    }
+
+    /// Lists the nodes of a tree, parents first, as `(depth, id, rel_type, repeated,
+    /// omitted_children)`.
+    fn flatten(node: &TreeNodeRecord, depth: usize) -> Vec<(usize, String, String, bool, usize)> {
+        let mut nodes = vec![(
+            depth,
+            node.id.clone(),
+            node.rel_type.clone().unwrap_or_default(),
+            node.repeated,
+            node.omitted_children,
+        )];
+        for child in &node.children {
+            nodes.extend(flatten(child, depth + 1));
+        }
+        nodes
+    }
+
+    fn assert_nodes(root: &TreeNodeRecord, expected: &[(usize, &str, &str, bool, usize)]) {
+        let nodes = flatten(root, 0);
+        let nodes: Vec<_> = nodes
+            .iter()
+            .map(|(depth, id, rel_type, repeated, omitted)| {
+                (*depth, id.as_str(), rel_type.as_str(), *repeated, *omitted)
+            })
+            .collect();
+        assert_eq!(nodes, expected);
+    }
+
+    #[tokio::test]
+    async fn test_build_node_marks_repeated_synsets() {
+        let temp_dir = tempdir().unwrap();
+        let wn = load_hyponyms(temp_dir.path(), DIAMOND).await;
+
+        let root = tree(&wn, "a", &down_options(None, None));
+        assert_eq!(root.lemmas, ["a"]);
+        assert_eq!(root.rel_type, None);
+        // `d` is expanded under `b` only, and the cycle back to `a` ends at `a`
+        assert_nodes(
+            &root,
+            &[
+                (0, "a", "", false, 0),
+                (1, "b", "hyponym", false, 0),
+                (2, "d", "hyponym", false, 0),
+                (3, "a", "hyponym", true, 0),
+                (1, "c", "hyponym", false, 0),
+                (2, "d", "hyponym", true, 0),
+            ],
+        );
+        assert!(root.children[1].children[0].children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_node_limits_breadth_and_depth() {
+        let temp_dir = tempdir().unwrap();
+        let wn = load_hyponyms(temp_dir.path(), DIAMOND).await;
+
+        let root = tree(&wn, "a", &down_options(None, Some(1)));
+        assert_nodes(
+            &root,
+            &[
+                (0, "a", "", false, 1),
+                (1, "b", "hyponym", false, 0),
+                (2, "d", "hyponym", false, 0),
+                (3, "a", "hyponym", true, 0),
+            ],
+        );
+
+        // Synsets at the depth limit are not expanded, so none of their children count
+        // as omitted
+        let root = tree(&wn, "a", &down_options(Some(1), Some(1)));
+        assert_nodes(
+            &root,
+            &[(0, "a", "", false, 1), (1, "b", "hyponym", false, 0)],
+        );
+
+        let root = tree(&wn, "d", &down_options(Some(0), None));
+        assert_nodes(&root, &[(0, "d", "", false, 0)]);
+    }
+
+    #[test]
+    fn test_taxonomy_rel_types() {
+        assert_eq!(
+            taxonomy_rel_types(TaxonomyDirection::Up),
+            [SynsetRelType::Hypernym, SynsetRelType::InstanceHypernym]
+        );
+        assert_eq!(
+            taxonomy_rel_types(TaxonomyDirection::Down),
+            [SynsetRelType::Hyponym, SynsetRelType::InstanceHyponym]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Word sense disambiguation.
+//!
+//! The simplified Lesk algorithm picks the sense of a word whose gloss shares the most
+//! words with the context the word occurs in. The signature of a synset is the set of
+//! words of its definitions and examples, optionally extended with those of its
+//! hypernyms and hyponyms. Context and signature words are compared in lowercase,
+//! without stopwords and without the target word itself; a synset's score is the number
+//! of distinct context words found in its signature.
+
+use crate::WordNet;
+use crate::error::Result;
+use crate::models::{PartOfSpeech, Synset, SynsetRelType};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Common English words that carry no sense information, in sorted order.
+#[rustfmt::skip]
+pub const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "also", "am", "an", "and",
+    "any", "are", "as", "at", "be", "because", "been", "before", "being", "below",
+    "between", "both", "but", "by", "can", "could", "did", "do", "does", "doing", "down",
+    "during", "each", "etc", "few", "for", "from", "further", "had", "has", "have",
+    "having", "he", "her", "here", "hers", "herself", "him", "himself", "his", "how", "i",
+    "if", "in", "into", "is", "it", "its", "itself", "just", "me", "more", "most", "my",
+    "myself", "no", "nor", "not", "now", "of", "off", "on", "once", "one", "only", "or",
+    "other", "our", "ours", "ourselves", "out", "over", "own", "s", "same", "she", "should",
+    "so", "some", "someone", "something", "such", "t", "than", "that", "the", "their",
+    "theirs", "them", "themselves", "then", "there", "these", "they", "this", "those",
+    "through", "to", "too", "under", "until", "up", "used", "very", "was", "we", "were",
+    "what", "when", "where", "which", "while", "who", "whom", "why", "will", "with",
+    "would", "you", "your", "yours", "yourself", "yourselves",
+];
+
+/// Hypernym and hyponym relations whose glosses extend a signature.
+const EXTENSION_REL_TYPES: [SynsetRelType; 4] = [
+    SynsetRelType::Hypernym,
+    SynsetRelType::InstanceHypernym,
+    SynsetRelType::Hyponym,
+    SynsetRelType::InstanceHyponym,
+];
+
+/// Options for [`lesk_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeskOptions {
+    /// Add the glosses of each synset's hypernyms and hyponyms to its signature
+    pub extended: bool,
+    /// Ignore [`STOPWORDS`] in the context and in glosses
+    pub remove_stopwords: bool,
+}
+
+impl Default for LeskOptions {
+    fn default() -> Self {
+        LeskOptions {
+            extended: false,
+            remove_stopwords: true,
+        }
+    }
+}
+
+/// A synset of the target word with its Lesk score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredSynset {
+    pub synset: Synset,
+    /// Number of distinct context words found in the synset's signature
+    pub score: usize,
+    /// The context words found in the signature, in context order
+    pub overlap: Vec<String>,
+}
+
+/// Ranks the synsets of `word` by the overlap of their glosses with `context`, using
+/// the default [`LeskOptions`].
+///
+/// # Example
+///
+/// ```no_run
+/// use oewn_rs::{PartOfSpeech, WordNet, wsd};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let wn = WordNet::load().await?;
+///     let context = "I went to the bank to deposit my money";
+///     if let Some(best) = wsd::lesk(&wn, context, "bank", Some(PartOfSpeech::N))?.first() {
+///         println!("{} ({})", best.synset.id, best.score);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn lesk(
+    wn: &WordNet,
+    context: &str,
+    word: &str,
+    pos: Option<PartOfSpeech>,
+) -> Result<Vec<ScoredSynset>> {
+    lesk_with_options(wn, context, word, pos, &LeskOptions::default())
+}
+
+/// Ranks the synsets of `word` by the overlap of their glosses with `context`.
+///
+/// The word is looked up as in [`WordNet::lookup_entries`]. Synsets are returned
+/// highest score first; synsets with the same score keep the word's sense order, so
+/// the most frequent sense comes first when the context does not help.
+pub fn lesk_with_options(
+    wn: &WordNet,
+    context: &str,
+    word: &str,
+    pos: Option<PartOfSpeech>,
+    options: &LeskOptions,
+) -> Result<Vec<ScoredSynset>> {
+    let target: HashSet<String> = words(word, false).collect();
+    let mut context_words: Vec<String> = Vec::new();
+    for context_word in words(context, options.remove_stopwords) {
+        if !target.contains(&context_word) && !context_words.contains(&context_word) {
+            context_words.push(context_word);
+        }
+    }
+
+    let mut scored = Vec::new();
+    for synset_id in wn.word_synset_ids(word, pos)? {
+        let synset = wn.get_synset(&synset_id)?;
+        let mut signature: HashSet<String> = HashSet::new();
+        add_glosses(&mut signature, &synset, options);
+        if options.extended {
+            for rel_type in EXTENSION_REL_TYPES {
+                for related in wn.get_related_synsets(&synset_id, rel_type)? {
+                    add_glosses(&mut signature, &related, options);
+                }
+            }
+        }
+        let overlap: Vec<String> = context_words
+            .iter()
+            .filter(|context_word| signature.contains(*context_word))
+            .cloned()
+            .collect();
+        scored.push(ScoredSynset {
+            synset,
+            score: overlap.len(),
+            overlap,
+        });
+    }
+    // Stable, so that ties keep sense order
+    scored.sort_by_key(|scored| Reverse(scored.score));
+    Ok(scored)
+}
+
+/// Adds the words of a synset's definitions and examples to a signature.
+fn add_glosses(signature: &mut HashSet<String>, synset: &Synset, options: &LeskOptions) {
+    let definitions = synset.definitions.iter().map(|d| d.text.as_str());
+    let examples = synset.examples.iter().map(|e| e.text.as_str());
+    for gloss in definitions.chain(examples) {
+        signature.extend(words(gloss, options.remove_stopwords));
+    }
+}
+
+/// Splits text into lowercase words, optionally without stopwords.
+fn words(text: &str, remove_stopwords: bool) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(move |word| !remove_stopwords || STOPWORDS.binary_search(&word.as_str()).is_err())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stopwords_sorted() {
+        assert!(STOPWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_words() {
+        let text = "The bank's slope, by the River.";
+        assert_eq!(
+            words(text, true).collect::<Vec<_>>(),
+            ["bank", "slope", "river"]
+        );
+        assert_eq!(words(text, false).count(), 7);
+    }
+}